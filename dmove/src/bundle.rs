@@ -0,0 +1,288 @@
+use std::{
+    fs::{create_dir_all, read_dir, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use hashbrown::HashMap;
+
+//layout: magic line, BE u64 index length, index text, then the concatenated files
+//index lines: "fingerprint <fp>", "ns <name>" and "file <offset> <len> <rel path>"
+const MAGIC: &[u8] = b"DMOVE-BUNDLE-1\n";
+const COPY_BUF: usize = 1 << 20;
+
+static MANIFESTS: OnceLock<Mutex<HashMap<PathBuf, Arc<BundleManifest>>>> = OnceLock::new();
+
+pub struct BundleManifest {
+    pub fingerprint: String,
+    pub namespaces: Vec<String>,
+    entries: HashMap<String, (u64, u64)>,
+    data_start: u64,
+}
+
+pub struct BundleSlice {
+    file: File,
+    start: u64,
+    len: u64,
+    pos: u64,
+}
+
+pub enum DataFile {
+    Plain(File),
+    Bundled(BundleSlice),
+}
+
+impl BundleManifest {
+    pub fn read(bundle_path: &Path) -> io::Result<Self> {
+        let mut br = BufReader::new(File::open(bundle_path)?);
+        let mut magic = [0; MAGIC.len()];
+        br.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid(format!("{bundle_path:?} is not a bundle")));
+        }
+        let mut len_buf = [0; 8];
+        br.read_exact(&mut len_buf)?;
+        let index_len = u64::from_be_bytes(len_buf);
+        let mut out = Self {
+            fingerprint: String::new(),
+            namespaces: Vec::new(),
+            entries: HashMap::new(),
+            data_start: (MAGIC.len() + len_buf.len()) as u64 + index_len,
+        };
+        for line in br.take(index_len).lines() {
+            let line = line?;
+            let (kind, rest) = line.split_once(' ').unwrap_or((&line, ""));
+            match kind {
+                "fingerprint" => out.fingerprint = rest.to_string(),
+                "ns" => out.namespaces.push(rest.to_string()),
+                "file" => {
+                    let mut parts = rest.splitn(3, ' ');
+                    let mut num = || -> io::Result<u64> {
+                        let p = parts.next().unwrap_or("");
+                        p.parse()
+                            .map_err(|_| invalid(format!("bad index line {line}")))
+                    };
+                    let (offset, len) = (num()?, num()?);
+                    let rel = parts.next().unwrap_or("").to_string();
+                    out.entries.insert(rel, (offset, len));
+                }
+                _ => return Err(invalid(format!("bad index line {line}"))),
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl BundleSlice {
+    fn open(bundle_path: &Path, manifest: &BundleManifest, rel: &str) -> io::Result<Self> {
+        let (offset, len) = *manifest.entries.get(rel).ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{rel} not in {bundle_path:?}"),
+        ))?;
+        let start = manifest.data_start + offset;
+        let mut file = File::open(bundle_path)?;
+        file.seek(SeekFrom::Start(start))?;
+        Ok(Self {
+            file,
+            start,
+            len,
+            pos: 0,
+        })
+    }
+}
+
+impl DataFile {
+    pub fn len(&self) -> u64 {
+        match self {
+            Self::Plain(f) => f.metadata().unwrap().len(),
+            Self::Bundled(s) => s.len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Read for BundleSlice {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        //a seek may go past the end, as on files, reading there gives nothing
+        let remaining = self.len.saturating_sub(self.pos) as usize;
+        let n = buf.len().min(remaining);
+        let read = self.file.read(&mut buf[..n])?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for BundleSlice {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => p as i64,
+            SeekFrom::End(d) => self.len as i64 + d,
+            SeekFrom::Current(d) => self.pos as i64 + d,
+        };
+        if new_pos < 0 {
+            return Err(invalid("seek before start of bundled file".to_string()));
+        }
+        self.pos = new_pos as u64;
+        self.file.seek(SeekFrom::Start(self.start + self.pos))?;
+        Ok(self.pos)
+    }
+}
+
+impl Read for DataFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(f) => f.read(buf),
+            Self::Bundled(s) => s.read(buf),
+        }
+    }
+}
+
+impl Seek for DataFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Plain(f) => f.seek(pos),
+            Self::Bundled(s) => s.seek(pos),
+        }
+    }
+}
+
+impl Write for DataFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(f) => f.write(buf),
+            Self::Bundled(_) => Err(invalid("bundles are read only".to_string())),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(f) => f.flush(),
+            Self::Bundled(_) => Ok(()),
+        }
+    }
+}
+
+impl From<File> for DataFile {
+    fn from(value: File) -> Self {
+        Self::Plain(value)
+    }
+}
+
+///opens a plain file, or the slice of a bundle if some ancestor of the path is a bundle file
+pub fn open_data(path: &Path) -> io::Result<DataFile> {
+    if let Some((bundle_path, rel)) = bundle_parts(path) {
+        let manifest = get_manifest(&bundle_path)?;
+        return Ok(DataFile::Bundled(BundleSlice::open(
+            &bundle_path,
+            &manifest,
+            &rel,
+        )?));
+    }
+    Ok(DataFile::Plain(File::open(path)?))
+}
+
+pub fn is_bundled(path: &Path) -> bool {
+    bundle_parts(path).is_some()
+}
+
+pub fn get_manifest(bundle_path: &Path) -> io::Result<Arc<BundleManifest>> {
+    let mut manifests = MANIFESTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    if let Some(m) = manifests.get(bundle_path) {
+        return Ok(m.clone());
+    }
+    let m = Arc::new(BundleManifest::read(bundle_path)?);
+    manifests.insert(bundle_path.to_path_buf(), m.clone());
+    Ok(m)
+}
+
+pub fn pack(root: &Path, namespaces: &[&str], fingerprint: &str, out: &Path) -> io::Result<()> {
+    let mut files = Vec::new();
+    for ns in namespaces {
+        collect_files(root, &root.join(ns), &mut files)?;
+    }
+    files.sort();
+    let mut index = vec![format!("fingerprint {fingerprint}")];
+    index.extend(namespaces.iter().map(|ns| format!("ns {ns}")));
+    let mut offset = 0;
+    for rel in files.iter() {
+        let len = root.join(rel).metadata()?.len();
+        index.push(format!("file {offset} {len} {rel}"));
+        offset += len;
+    }
+    let index_bytes = (index.join("\n") + "\n").into_bytes();
+
+    let mut bw = BufWriter::new(File::create(out)?);
+    bw.write_all(MAGIC)?;
+    bw.write_all(&(index_bytes.len() as u64).to_be_bytes())?;
+    bw.write_all(&index_bytes)?;
+    let mut buf = vec![0; COPY_BUF];
+    for rel in files.iter() {
+        let mut f = File::open(root.join(rel))?;
+        loop {
+            let n = f.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            bw.write_all(&buf[..n])?;
+        }
+    }
+    bw.flush()
+}
+
+pub fn unpack(bundle_path: &Path, root: &Path) -> io::Result<Arc<BundleManifest>> {
+    let manifest = get_manifest(bundle_path)?;
+    for rel in manifest.entries.keys() {
+        let target = root.join(rel);
+        create_dir_all(target.parent().unwrap())?;
+        let mut slice = BundleSlice::open(bundle_path, &manifest, rel)?;
+        io::copy(&mut slice, &mut BufWriter::new(File::create(target)?))?;
+    }
+    Ok(manifest)
+}
+
+fn bundle_parts(path: &Path) -> Option<(PathBuf, String)> {
+    if path.exists() {
+        return None;
+    }
+    for anc in path.ancestors().skip(1) {
+        if anc.is_file() {
+            let rel = path.strip_prefix(anc).ok()?;
+            return Some((anc.to_path_buf(), rel_str(rel)));
+        }
+    }
+    None
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> io::Result<()> {
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            out.push(rel_str(path.strip_prefix(root).unwrap()));
+        }
+    }
+    Ok(())
+}
+
+fn rel_str(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_str().unwrap())
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -263,6 +263,18 @@ impl MainBuilder {
         ))
     }
 
+    ///marks an attribute with a marker written into the generated code, for attributes not known when compiling
+    pub fn declare_generated_marker<Main>(&mut self, name: &str) -> String {
+        let marker = format!("{}Marker", camel_case(name));
+        self.meta_elems.push(MarkedAttributeTraitMeta::meta(
+            &get_type_name::<Main>(),
+            &marker,
+            &camel_case(name),
+        ));
+        self.definables.insert(marker.clone());
+        marker
+    }
+
     pub fn declare_link<S, T>(&mut self, name: &str) {
         self.meta_elems.push(LinkTraitMeta::meta(
             &camel_case(name),
@@ -292,6 +304,114 @@ impl MainBuilder {
     }
 }
 
+///an entity or attribute as the code written by MainBuilder declares it
+#[derive(Default, Debug)]
+pub struct GeneratedEntity {
+    pub struct_name: String,
+    pub name: String,
+    pub type_name: String,
+    pub n: usize,
+    pub key_type: Option<String>,
+    pub size_type: Option<String>,
+    ///structs of the source and the target
+    pub link: Option<(String, String)>,
+    ///(main entity, marker) structs for each attribute marking it
+    pub marks: Vec<(String, String)>,
+}
+
+impl GeneratedEntity {
+    ///byte width of an unsigned type name
+    pub fn uint_width(type_name: &str) -> Option<usize> {
+        match type_name {
+            "u8" => Some(1),
+            "u16" => Some(2),
+            "u32" => Some(4),
+            "u64" => Some(8),
+            _ => None,
+        }
+    }
+}
+
+///reads back the code written by MainBuilder::write_code
+pub fn parse_generated_code(code: &str) -> Vec<GeneratedEntity> {
+    let mut out: Vec<GeneratedEntity> = Vec::new();
+    let mut by_struct: HashMap<String, usize> = HashMap::new();
+    let mut marks = Vec::new();
+    for line in code.lines() {
+        let Some((trait_part, rest)) = line
+            .strip_prefix("impl ")
+            .and_then(|e| e.split_once(" for "))
+        else {
+            continue;
+        };
+        let sname = last_segment(rest.split_whitespace().next().unwrap_or(""));
+        if let Some(marker) = trait_part
+            .strip_prefix("MarkedAttribute<")
+            .and_then(|e| e.strip_suffix('>'))
+        {
+            if let Some(att) = between(rest, "type AttributeEntity = ", ";") {
+                marks.push((
+                    att.to_string(),
+                    sname.to_string(),
+                    last_segment(marker).to_string(),
+                ));
+            }
+            continue;
+        }
+        if trait_part == "Entity" {
+            by_struct.insert(sname.to_string(), out.len());
+            out.push(GeneratedEntity {
+                struct_name: sname.to_string(),
+                name: between(rest, "NAME: & str = \"", "\"")
+                    .unwrap_or("")
+                    .to_string(),
+                type_name: between(rest, "type T = ", "; const N")
+                    .unwrap_or("")
+                    .to_string(),
+                n: between(rest, "const N: usize = ", ";")
+                    .and_then(|e| e.parse().ok())
+                    .unwrap_or(0),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(ge) = by_struct.get(sname).map(|i| &mut out[*i]) else {
+            continue;
+        };
+        match trait_part {
+            "MappableEntity" => {
+                ge.key_type = between(rest, "type KeyType = ", ";").map(String::from)
+            }
+            "VariableSizeAttribute" => {
+                ge.size_type = between(rest, "type SizeType = ", ";").map(String::from)
+            }
+            "Link" => {
+                let source = between(rest, "type Source = ", ";").map(last_segment);
+                let target = between(rest, "type Target = ", ";").map(last_segment);
+                if let (Some(s), Some(t)) = (source, target) {
+                    ge.link = Some((s.to_string(), t.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    for (att, main, marker) in marks {
+        if let Some(i) = by_struct.get(&att) {
+            out[*i].marks.push((main, marker));
+        }
+    }
+    out
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &s[s.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
 impl ByteArrayInterface for String {
     fn to_bytes(&self) -> Box<[u8]> {
         self.to_owned().into_bytes().into()
@@ -302,6 +422,29 @@ impl ByteArrayInterface for String {
     }
 }
 
+//each string of a list is prefixed by its byte length
+impl ByteArrayInterface for Box<[String]> {
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut out = Vec::new();
+        for e in self.iter() {
+            out.extend(u16::try_from(e.len()).unwrap().to_fbytes().iter());
+            out.extend(e.as_bytes());
+        }
+        out.into()
+    }
+
+    fn from_bytes(buf: &[u8]) -> Self {
+        let mut out = Vec::new();
+        let mut s = 0;
+        while s < buf.len() {
+            let e = s + u16::S + u16::from_fbytes(&buf[s..s + u16::S]) as usize;
+            out.push(String::from_bytes(&buf[s + u16::S..e]));
+            s = e;
+        }
+        out.into()
+    }
+}
+
 impl_fbarrs!(6);
 
 impl<T> InitEmpty for Option<T> {
@@ -310,6 +453,12 @@ impl<T> InitEmpty for Option<T> {
     }
 }
 
+impl<T> InitEmpty for Box<[T]> {
+    fn init_empty() -> Self {
+        Vec::new().into()
+    }
+}
+
 impl<T, const S: usize> InitEmpty for [T; S]
 where
     T: InitEmpty,
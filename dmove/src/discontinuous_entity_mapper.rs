@@ -13,6 +13,7 @@ use crate::common::{
     MappableEntityTraitMeta, MetaIntegrator, UnsignedNumber,
 };
 use crate::{
+    bundle::{is_bundled, open_data},
     ByteFixArrayInterface, EntityImmutableMapperBackend, EntityImmutableRefMapperBackend,
     FixWriteSizeEntity,
 };
@@ -118,8 +119,10 @@ where
 
     fn post(mut self, builder: &mut MainBuilder) {
         self.map.extend();
-        let n =
-            file_record_count(&File::open(self.map.map_path).unwrap(), self.map.full_size) as usize;
+        let n = record_count(
+            open_data(&self.map.map_path).unwrap().len(),
+            self.map.full_size,
+        ) as usize;
         let camel_name = builder.add_simple_etrait(&self.name, type_name::<V>(), n, false);
         let mappable_type = get_type_name::<K>();
         builder
@@ -138,7 +141,7 @@ where
         PathBuf: From<T>,
     {
         let map_path = PathBuf::from(id_map_path);
-        if !map_path.is_file() {
+        if !map_path.is_file() && !is_bundled(&map_path) {
             File::create(&map_path).unwrap();
         }
         Self {
@@ -183,7 +186,7 @@ where
     }
 
     pub fn get(&mut self, k: &K) -> Option<V> {
-        let hfile = File::open(&self.map_path).unwrap();
+        let hfile = open_data(&self.map_path).unwrap();
 
         let k_arr = k.to_fbytes();
         let rec_u64: u64 = self.full_size as u64;
@@ -191,7 +194,7 @@ where
         let (key_buffer, value_buffer) =
             self.main_buf[..self.full_size].split_at_mut(self.key_size);
         let mut seek_blocks_l: u64 = 0;
-        let mut seek_blocks_r: u64 = file_record_count(&hfile, self.full_size);
+        let mut seek_blocks_r: u64 = record_count(hfile.len(), self.full_size);
         let mut seek_mid = (seek_blocks_r + seek_blocks_l) / 2;
 
         let mut reader = hfile;
@@ -227,7 +230,7 @@ where
 
     pub fn to_map(&mut self) -> HashMap<K, V> {
         let mut record_buffer = &mut self.main_buf[..self.full_size];
-        let mut br = BufReader::new(File::open(&self.map_path).unwrap());
+        let mut br = BufReader::new(open_data(&self.map_path).unwrap());
         let mut out = HashMap::new();
         loop {
             if let Ok(_) = br.read_exact(&mut record_buffer) {
@@ -291,8 +294,8 @@ fn merge_chunks(a1: Vec<u8>, a2: Vec<u8>, size: &usize) -> Vec<u8> {
     out
 }
 
-fn file_record_count(file: &File, record_size: usize) -> u64 {
-    file.metadata().unwrap().len() / (record_size as u64)
+fn record_count(file_len: u64, record_size: usize) -> u64 {
+    file_len / (record_size as u64)
 }
 
 #[cfg(test)]
@@ -6,6 +6,7 @@ use std::{
 };
 
 use crate::{
+    bundle::{open_data, DataFile},
     common::{
         get_type_name, get_uscale, ByteFixArrayInterface, Entity, MainBuilder, MappableEntity,
         MetaIntegrator, MAX_FIXBUF,
@@ -30,7 +31,7 @@ pub struct FixAttIterator<E>
 where
     E: FixWriteSizeEntity,
 {
-    file: File,
+    file: DataFile,
     buf: [u8; MAX_FIXBUF],
     p: PhantomData<E>,
 }
@@ -68,7 +69,7 @@ where
     fn load_backend(path: &PathBuf) -> Self {
         let mut out = Vec::new();
         let fp = path.join(E::NAME);
-        let mut br = BufReader::new(File::open(&fp).expect(fp.to_str().unwrap_or("???")));
+        let mut br = BufReader::new(open_data(&fp).expect(fp.to_str().unwrap_or("???")));
         // let size: usize = std::mem::size_of::<E::T>();
         // const SIZE: usize = std::mem::size_of::<<Self as Entity>::T>();
         let size: usize = E::WS;
@@ -85,7 +86,7 @@ where
     E: FixWriteSizeEntity,
 {
     fn load_backend(path: &PathBuf) -> Self {
-        let file = File::open(path.join(E::NAME)).unwrap();
+        let file = open_data(&path.join(E::NAME)).unwrap();
         Self {
             file,
             buf: [0; MAX_FIXBUF],
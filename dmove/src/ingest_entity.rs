@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use hashbrown::{HashMap, HashSet};
 
+use crate::bundle::{is_bundled, open_data};
 use crate::common::{
     get_type_name, BackendLoading, BigId, Entity, EntityMutableMapperBackend, MainBuilder,
     MappableEntity, MappableEntityTraitMeta, MetaIntegrator, UnsignedNumber,
@@ -125,12 +126,12 @@ impl IdMap {
     {
         let map_buffer = PathBuf::from(id_map_path);
         let mut current_non_null_count: u64 = 0;
-        if !map_buffer.is_file() {
+        if !map_buffer.is_file() && !is_bundled(&map_buffer) {
             let msg = format!("trying to create {map_buffer:?}");
             create_dir_all(&map_buffer.parent().expect(&msg)).expect(&msg);
             File::create(&map_buffer).expect(&msg);
         } else {
-            current_non_null_count = file_record_count(&File::open(&map_buffer).unwrap());
+            current_non_null_count = record_count(open_data(&map_buffer).unwrap().len());
         }
         Self {
             map_buffer,
@@ -181,14 +182,14 @@ impl IdMap {
     }
 
     pub fn get(&mut self, k: &BigId) -> Option<BigId> {
-        let hfile = File::open(&self.map_buffer).unwrap();
-        let mut br = BufReader::new(&hfile);
+        let hfile = open_data(&self.map_buffer).unwrap();
+        let mut seek_blocks_r: u64 = record_count(hfile.len());
+        let mut br = BufReader::new(hfile);
         const REC_U64: u64 = ID_RECORD_SIZE as u64;
 
         let mut key_buffer = [0; ID_TYPE_SIZE];
         let mut value_buffer = [0; ID_TYPE_SIZE];
         let mut seek_blocks_l: u64 = 0;
-        let mut seek_blocks_r: u64 = file_record_count(&hfile);
         let mut seek_mid = (seek_blocks_r + seek_blocks_l) / 2;
         loop {
             br.seek(SeekFrom::Start(seek_mid * REC_U64)).unwrap();
@@ -226,7 +227,7 @@ impl IdMap {
         T: UnsignedNumber,
     {
         let mut record_buffer = [0; ID_RECORD_SIZE];
-        let mut br = BufReader::new(File::open(&self.map_buffer).unwrap());
+        let mut br = BufReader::new(open_data(&self.map_buffer).unwrap());
         let mut out = HashMap::new();
         loop {
             if let Ok(_) = br.read_exact(&mut record_buffer) {
@@ -243,6 +244,6 @@ impl IdMap {
     }
 }
 
-fn file_record_count(file: &File) -> u64 {
-    file.metadata().unwrap().len() / (ID_RECORD_SIZE as u64)
+fn record_count(file_len: u64) -> u64 {
+    file_len / (ID_RECORD_SIZE as u64)
 }
@@ -1,18 +1,21 @@
 #![feature(min_specialization)]
 // #![feature(generic_const_exprs)]
 // rustup override set nightly-2024-07-25
+pub mod bundle;
 mod common;
 mod discontinuous_entity_mapper;
 mod fixed_size_attributes;
 mod ingest_entity;
 pub mod para;
+pub mod stats;
 mod var_size_attributes;
 
 pub use common::{
-    camel_case, BackendLoading, BigId, ByteArrayInterface, ByteFixArrayInterface, CompactEntity,
-    Entity, EntityImmutableMapperBackend, EntityImmutableRefMapperBackend,
-    EntityMutableMapperBackend, InitEmpty, Link, MainBuilder, MappableEntity, MarkedAttribute,
-    MetaIntegrator, NamespacedEntity, UnsignedNumber, VariableSizeAttribute, ET, MAA,
+    camel_case, parse_generated_code, BackendLoading, BigId, ByteArrayInterface,
+    ByteFixArrayInterface, CompactEntity, Entity, EntityImmutableMapperBackend,
+    EntityImmutableRefMapperBackend, EntityMutableMapperBackend, GeneratedEntity, InitEmpty, Link,
+    MainBuilder, MappableEntity, MarkedAttribute, MetaIntegrator, NamespacedEntity, UnsignedNumber,
+    VariableSizeAttribute, ET, MAA,
 };
 pub use discontinuous_entity_mapper::{DiscoMapEntityBuilder, UniqueMap};
 pub use fixed_size_attributes::{
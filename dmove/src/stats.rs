@@ -0,0 +1,287 @@
+use std::{
+    fmt::Display,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{bundle::open_data, parse_generated_code, GeneratedEntity};
+
+//quantiles come from a uniform sample of at most this many values
+const RESERVOIR: usize = 1 << 20;
+//0 gets its own bin, then one per power of 2
+const LOG_BINS: usize = 65;
+pub const DEFAULT_QUANTILES: [f64; 7] = [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99];
+
+pub struct StatsBuilder {
+    count: usize,
+    min: u64,
+    max: u64,
+    sum: f64,
+    bins: [usize; LOG_BINS],
+    sample: Vec<u64>,
+    rng: StdRng,
+}
+
+///an attribute declared in generated code that stats can be taken of
+pub struct GeneratedAttribute {
+    pub name: String,
+    pub kind: StatKind,
+}
+
+pub enum StatKind {
+    ///unsigned values of this many bytes
+    Fix(usize),
+    ///element counts of a var size attribute, sizes of this many bytes
+    VarLen(usize),
+}
+
+pub struct AttributeStats {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub exact: bool,
+    pub quantiles: Vec<(f64, f64)>,
+    ///(inclusive lower, exclusive upper, count) of non-empty bins
+    pub histogram: Vec<(u64, u64, usize)>,
+}
+
+impl StatsBuilder {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            min: u64::MAX,
+            max: 0,
+            sum: 0.0,
+            bins: [0; LOG_BINS],
+            sample: Vec::new(),
+            rng: StdRng::seed_from_u64(42),
+        }
+    }
+
+    pub fn add(&mut self, v: u64) {
+        self.count += 1;
+        self.min = self.min.min(v);
+        self.max = self.max.max(v);
+        self.sum += v as f64;
+        self.bins[bin_of(v)] += 1;
+        //reservoir sampling, every value seen so far is kept with the same chance
+        if self.sample.len() < RESERVOIR {
+            self.sample.push(v);
+        } else {
+            let i = self.rng.gen_range(0..self.count);
+            if i < RESERVOIR {
+                self.sample[i] = v;
+            }
+        }
+    }
+
+    pub fn finish(mut self, qs: &[f64]) -> AttributeStats {
+        let exact = self.count <= RESERVOIR;
+        self.sample.sort_unstable();
+        let quantiles = qs
+            .iter()
+            .map(|q| (*q, sorted_quantile(&self.sample, *q)))
+            .collect();
+        let histogram = self
+            .bins
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c > 0)
+            .map(|(i, c)| {
+                let (l, u) = bin_bounds(i);
+                (l, u, *c)
+            })
+            .collect();
+        AttributeStats {
+            count: self.count,
+            min: if self.count > 0 { self.min } else { 0 },
+            max: self.max,
+            mean: self.sum / (self.count.max(1) as f64),
+            exact,
+            quantiles,
+            histogram,
+        }
+    }
+}
+
+impl Default for StatsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<u64> for AttributeStats {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut builder = StatsBuilder::new();
+        iter.into_iter().for_each(|v| builder.add(v));
+        builder.finish(&DEFAULT_QUANTILES)
+    }
+}
+
+impl Display for AttributeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "count: {}, min: {}, max: {}, mean: {:.3}",
+            self.count, self.min, self.max, self.mean
+        )?;
+        let qkind = if self.exact { "exact" } else { "approx" };
+        for (q, v) in self.quantiles.iter() {
+            writeln!(f, "  q{:<5} {v:.1} ({qkind})", q * 100.0)?;
+        }
+        let max_c = self.histogram.iter().map(|e| e.2).max().unwrap_or(1);
+        for (l, u, c) in self.histogram.iter() {
+            let bar = "#".repeat((c * 40).div_ceil(max_c));
+            writeln!(f, "  [{l}, {u}) {c:>12} {bar}")?;
+        }
+        Ok(())
+    }
+}
+
+impl GeneratedAttribute {
+    pub fn from_generated(ge: GeneratedEntity) -> Option<Self> {
+        let kind = match &ge.size_type {
+            Some(st) => StatKind::VarLen(GeneratedEntity::uint_width(st)?),
+            None => StatKind::Fix(GeneratedEntity::uint_width(&ge.type_name)?),
+        };
+        Some(Self {
+            name: ge.name,
+            kind,
+        })
+    }
+
+    ///stats in one pass over the file of the attribute, in the directory of its namespace
+    pub fn stats(&self, ns_path: &Path) -> AttributeStats {
+        let mut builder = StatsBuilder::new();
+        self.for_each_number(ns_path, |v| builder.add(v));
+        builder.finish(&DEFAULT_QUANTILES)
+    }
+
+    ///values of a fixed size attribute or element counts of a var size one, in id order
+    pub fn for_each_number<F: FnMut(u64)>(&self, ns_path: &Path, mut f: F) {
+        let (path, width) = match self.kind {
+            StatKind::Fix(width) => (ns_path.join(&self.name), width),
+            StatKind::VarLen(width) => (ns_path.join(&self.name).join("sizes"), width),
+        };
+        let mut br = BufReader::new(open_data(&path).unwrap_or_else(|_| panic!("{path:?}")));
+        let mut buf = [0; 8];
+        while br.read_exact(&mut buf[8 - width..]).is_ok() {
+            f(u64::from_be_bytes(buf));
+        }
+    }
+
+    ///elements of a var size attribute of strings, in id order
+    pub fn strings(&self, ns_path: &Path) -> Vec<String> {
+        assert!(
+            matches!(self.kind, StatKind::VarLen(_)),
+            "{} not var size",
+            self.name
+        );
+        let path = ns_path.join(&self.name).join("targets");
+        let mut br = BufReader::new(open_data(&path).unwrap_or_else(|_| panic!("{path:?}")));
+        let mut out = Vec::new();
+        self.for_each_number(ns_path, |size| {
+            let mut buf = vec![0; size as usize];
+            br.read_exact(&mut buf).unwrap();
+            out.push(String::from_utf8(buf).unwrap());
+        });
+        out
+    }
+}
+
+///numeric and var size attributes declared in the code generated for a namespace
+pub fn generated_attributes(code: &str) -> Vec<GeneratedAttribute> {
+    parse_generated_code(code)
+        .into_iter()
+        .filter_map(GeneratedAttribute::from_generated)
+        .collect()
+}
+
+fn bin_of(v: u64) -> usize {
+    (u64::BITS - v.leading_zeros()) as usize
+}
+
+fn bin_bounds(bin: usize) -> (u64, u64) {
+    match bin {
+        0 => (0, 1),
+        64 => (1 << 63, u64::MAX),
+        _ => (1 << (bin - 1), 1 << bin),
+    }
+}
+
+fn sorted_quantile(sorted: &[u64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    let frac = pos - lo as f64;
+    sorted[lo] as f64 * (1.0 - frac) + sorted[hi] as f64 * frac
+}
+
+#[cfg(test)]
+mod stats_test {
+    use super::*;
+
+    #[test]
+    fn basic_stats() {
+        let stats: AttributeStats = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 100].into_iter().collect();
+        assert_eq!(stats.count, 10);
+        assert_eq!(stats.min, 0);
+        assert_eq!(stats.max, 100);
+        assert_eq!(stats.mean, 13.6);
+        assert!(stats.exact);
+        assert_eq!(stats.quantiles[3], (0.5, 4.5));
+        let bins: Vec<(u64, u64, usize)> = vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 4, 2),
+            (4, 8, 4),
+            (8, 16, 1),
+            (64, 128, 1),
+        ];
+        assert_eq!(stats.histogram, bins);
+    }
+
+    #[test]
+    fn sampled() {
+        let n = 2 * RESERVOIR as u64;
+        let mut builder = StatsBuilder::new();
+        (0..n).for_each(|v| builder.add(v));
+        assert_eq!(builder.sample.len(), RESERVOIR);
+        let stats = builder.finish(&DEFAULT_QUANTILES);
+        assert!(!stats.exact);
+        assert_eq!((stats.count, stats.max), (n as usize, n - 1));
+        for (q, v) in stats.quantiles {
+            assert!((v / n as f64 - q).abs() < 0.01, "{q} {v}");
+        }
+    }
+
+    #[test]
+    fn from_code() {
+        let code = [
+            "use dmove::{Entity, VariableSizeAttribute};",
+            "pub struct WorkYears { }",
+            "impl Entity for WorkYears { type T = u8; const N: usize = 10; const NAME: & str = \"work-years\"; }",
+            "impl Entity for WorkRefs { type T = Box<[u32]>; const N: usize = 10; const NAME: & str = \"work-refs\"; }",
+            "impl VariableSizeAttribute for WorkRefs { type SizeType = u16; }",
+            "impl Entity for WorkImpact { type T = f64; const N: usize = 10; const NAME: & str = \"work-impact\"; }",
+        ]
+        .join("\n\n");
+        let atts: Vec<(String, usize, bool)> = generated_attributes(&code)
+            .into_iter()
+            .map(|e| match e.kind {
+                StatKind::Fix(w) => (e.name, w, false),
+                StatKind::VarLen(w) => (e.name, w, true),
+            })
+            .collect();
+        let expected = vec![
+            ("work-years".to_string(), 1, false),
+            ("work-refs".to_string(), 2, true),
+        ];
+        assert_eq!(atts, expected);
+    }
+}
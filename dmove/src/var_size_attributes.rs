@@ -6,6 +6,7 @@ use std::{
 };
 
 use crate::{
+    bundle::{open_data, DataFile},
     common::{
         get_type_name, BackendLoading, ByteArrayInterface, ByteFixArrayInterface, Entity,
         EntityImmutableRefMapperBackend, MainBuilder, MetaIntegrator, UnsignedNumber,
@@ -26,8 +27,8 @@ pub struct VarAttBuilder {
 }
 
 pub struct VattFilePair {
-    counts: File,
-    targets: File,
+    counts: DataFile,
+    targets: DataFile,
 }
 
 pub struct VattReadingMap<E>
@@ -111,15 +112,15 @@ impl<T> FromIterator<T> for VarBox<T> {
 
 impl VattFilePair {
     fn open(parent_dir: &PathBuf) -> Self {
-        let op = |s: &str| File::open(&parent_dir.join(s)).expect(&format!("{parent_dir:?}/{s}"));
+        let op = |s: &str| open_data(&parent_dir.join(s)).expect(&format!("{parent_dir:?}/{s}"));
         let counts = op("sizes");
         let targets = op("targets");
         Self { counts, targets }
     }
 
     fn create(parent_dir: &PathBuf) -> Self {
-        let counts = File::create(&parent_dir.join("sizes")).unwrap();
-        let targets = File::create(&parent_dir.join("targets")).unwrap();
+        let counts = File::create(&parent_dir.join("sizes")).unwrap().into();
+        let targets = File::create(&parent_dir.join("targets")).unwrap().into();
         Self { counts, targets }
     }
 }
@@ -280,6 +281,10 @@ where
     type SubType = T;
 }
 
+impl VarSizedAttributeElement for Box<[String]> {
+    type SubType = u8;
+}
+
 impl<T> MetaIntegrator<T> for VarAttBuilder
 where
     T: VarSizedAttributeElement,
@@ -478,7 +483,7 @@ where
     ))
 }
 
-fn from_buf<E>(full_size: usize, targets: &mut File, buf: &mut [u8]) -> E::T
+fn from_buf<E>(full_size: usize, targets: &mut DataFile, buf: &mut [u8]) -> E::T
 where
     E: Entity,
     E::T: ByteArrayInterface,
@@ -0,0 +1,11 @@
+use dmove::ByteArrayInterface;
+
+#[test]
+fn string_lists() {
+    let names: Box<[String]> =
+        vec!["Jane Doe".to_string(), "".to_string(), "J. Dœ".to_string()].into();
+    let back = Box::<[String]>::from_bytes(&names.to_bytes());
+    assert_eq!(back, names);
+    let empty: Box<[String]> = Vec::new().into();
+    assert_eq!(Box::<[String]>::from_bytes(&empty.to_bytes()), empty);
+}
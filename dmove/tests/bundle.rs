@@ -0,0 +1,36 @@
+use std::{io::Read, path::Path};
+
+use dmove::{bundle, UniqueMap};
+
+#[test]
+fn bundle_roundtrip() {
+    let root = Path::new("testbundle-root");
+    let bundle_path = Path::new("testbundle");
+    std::fs::create_dir_all(root.join("ns1").join("att")).unwrap();
+    let mut map = UniqueMap::<u16, u8>::new(root.join("ns1").join("map"));
+    std::fs::write(root.join("ns1").join("att").join("sizes"), [1, 2, 3]).unwrap();
+    for (k, v) in vec![(20, 1), (30, 2), (40, 10)] {
+        map.push((k, v));
+    }
+    map.extend();
+    bundle::pack(root, &["ns1"], "fp", bundle_path).unwrap();
+
+    let manifest = bundle::get_manifest(bundle_path).unwrap();
+    assert_eq!(manifest.fingerprint, "fp");
+    assert_eq!(manifest.namespaces, vec!["ns1".to_string()]);
+    assert_eq!(manifest.file_count(), 2);
+
+    let mut bmap = UniqueMap::<u16, u8>::new(bundle_path.join("ns1").join("map"));
+    assert_eq!(bmap.get(&30), Some(2));
+    assert_eq!(bmap.get(&50), None);
+    assert_eq!(bmap.to_map().len(), 3);
+    let mut sizes = Vec::new();
+    bundle::open_data(&bundle_path.join("ns1").join("att").join("sizes"))
+        .unwrap()
+        .read_to_end(&mut sizes)
+        .unwrap();
+    assert_eq!(sizes, vec![1, 2, 3]);
+
+    std::fs::remove_file(bundle_path).unwrap();
+    std::fs::remove_dir_all(root).unwrap();
+}
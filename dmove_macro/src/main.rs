@@ -7,6 +7,8 @@ const LIB_MACRO: &str = "mods_as_comms";
 const MOD_STEM: &str = "mod";
 const STEPS_MODULE: &str = "steps";
 const GEN_MODULE: &str = "gen";
+//generated modules of imported attributes, that belong to no step
+const EXT_PREFIX: &str = "ext_";
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -103,10 +105,26 @@ fn build_ends(steps: &Vec<String>, step: &String, src_path: &Path, skip_last_gen
     if skip_last_gen {
         upto_steps.truncate(upto_steps.len() - 1)
     }
-    pub_mods_to_file(&src_path.join(GEN_MODULE), &upto_steps);
+    let gen_dir = src_path.join(GEN_MODULE);
+    let ext_mods = ext_modules(&gen_dir);
+    upto_steps.extend(ext_mods.iter());
+    pub_mods_to_file(&gen_dir, &upto_steps);
     std::fs::write(&lib_path, clean_inner).unwrap();
 }
 
+fn ext_modules(gen_dir: &Path) -> Vec<String> {
+    let mut out: Vec<String> = gen_dir
+        .read_dir()
+        .unwrap()
+        .filter_map(|e| {
+            let stem = e.unwrap().path().file_stem()?.to_str()?.to_owned();
+            stem.starts_with(EXT_PREFIX).then_some(stem)
+        })
+        .collect();
+    out.sort();
+    out
+}
+
 fn rs_file_name(src_path: &Path, name: &str) -> String {
     src_path
         .join(format!("{name}.rs"))
@@ -456,7 +456,7 @@ impl StackWordSet {
             breaks_count: 0,
         };
         let mut i: u8 = 0;
-        for c in deunicode(&words.to_lowercase()).as_bytes().iter() {
+        for c in transliterate(words).as_bytes().iter() {
             if (*c == SPLIT_CHAR) && (i > 0) {
                 out.new_break(i);
                 if out.breaks_count == MAX_QUERY_WORDS {
@@ -534,6 +534,11 @@ impl SearchEngine {
     }
 }
 
+///ascii rendering of any script, lowercased after deunicode so transliterated capitals survive
+pub fn transliterate(s: &str) -> String {
+    deunicode(s).to_lowercase()
+}
+
 //adds elems that are not present
 fn extend_sorted<T: PartialOrd>(int_v: &mut Vec<T>, add_v: Vec<T>) {
     let mut i = 0;
@@ -646,6 +651,19 @@ mod tests {
         assert_eq!(v1, vec![1, 2, 3, 4]);
     }
 
+    #[test]
+    fn transliterates_scripts() {
+        assert_eq!(
+            StackWordSet::new("Université Paris").to_words(),
+            vec!["universite", "paris"]
+        );
+        assert_eq!(
+            StackWordSet::new("北京大学").to_words(),
+            vec!["bei", "jing", "da", "xue"]
+        );
+        assert_eq!(StackWordSet::new("Москва").to_words(), vec!["moskva"]);
+    }
+
     #[test]
     fn gets_empty() {
         let engine = get_test_engine();
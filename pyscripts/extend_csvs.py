@@ -1,4 +1,5 @@
 import json
+from pathlib import Path
 
 import pandas as pd
 import polars as pl
@@ -14,17 +15,43 @@ from ccl_science_data.common import (
 # link_frame = "https://tmp-borza-public-cyx.s3.amazonaws.com/{}.csv.gz"
 link_frame = "s3://tmp-borza-public-cyx/{}.csv.gz"
 
+SHARD_EXT = ".csv.gz"
+SUPERSEDED_FILE = "superseded.tsv"
+
 
 def get_best_q_by_year():
     return pl.read_csv(link_frame.format("metascience/q-by-year"))
 
 
+def read_table(entity, table):
+    # to-csv writes one shard per snapshot partition into a directory per table
+    single = Path(get_csv_path(entity, table))
+    shard_dir = single.parent / single.name.removesuffix(SHARD_EXT)
+    if single.exists() or not shard_dir.is_dir():
+        return pd.read_csv(single)
+    newest = {}
+    sup_path = single.parent / SUPERSEDED_FILE
+    if sup_path.exists():
+        sup_df = pd.read_csv(sup_path, sep="\t", header=None, names=["id", "shard"])
+        newest = sup_df.groupby("id")["shard"].max().to_dict()
+    dfs = []
+    for path in sorted(shard_dir.glob(f"*{SHARD_EXT}")):
+        shard = int(path.name.removesuffix(SHARD_EXT))
+        df = pd.read_csv(path)
+        owner = next((c for c in ["parent_id", "id"] if c in df.columns), None)
+        if owner is not None and newest:
+            # records of an entity updated in a later shard are stale
+            df = df.loc[lambda df: df[owner].map(newest).fillna(shard) <= shard, :]
+        dfs.append(df)
+    return pd.concat(dfs, ignore_index=True)
+
+
 if __name__ == "__main__":
 
     source_filter = get_last_filter(EntC.SOURCES)
     adf = pd.read_csv(link_frame.format("metascience/areas")).drop_duplicates()
     sodf = (
-        pd.read_csv(get_csv_path(EntC.SOURCES, "ids"))
+        read_table(EntC.SOURCES, "ids")
         .assign(id=lambda df: df["openalex"].pipe(parse_id))
         .loc[lambda df: df["id"].isin(source_filter), :]
         .set_index("id")
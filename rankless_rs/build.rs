@@ -1,37 +1,39 @@
-use std::{env, ops::AddAssign};
+//generated modules of imported attributes, as dmove_macro keeps them
+const EXT_PREFIX: &str = "ext_";
 
 fn main() {
-    // println!("cargo:rerun-if-changed=build.rs");
-    // println!("cargo:rerun-if-changed=data.txt");
-    let path = std::path::Path::new("src").join("env_consts.rs");
-    // std::fs::write(&path, "pub const {}: {} = {}").unwrap();
+    println!("cargo:rustc-env=GEN_FINGERPRINT={}", gen_fingerprint());
+    println!("cargo:rustc-env=EXT_NAMESPACES={}", ext_modules().join(","));
+}
 
-    let rankless_env = env::var_os("RANKLESS_ENV").unwrap_or("full".into());
-    let envs = vec!["nano", "micro", "mini"];
+fn ext_modules() -> Vec<String> {
+    let gen_dir = std::path::Path::new("src").join("gen");
+    let mut out: Vec<String> = std::fs::read_dir(gen_dir)
+        .unwrap()
+        .filter_map(|e| {
+            let stem = e.unwrap().path().file_stem()?.to_str()?.to_owned();
+            stem.starts_with(EXT_PREFIX).then_some(stem)
+        })
+        .collect();
+    out.sort();
+    out
+}
 
-    let mut e_ind = 0;
-    for e in envs.iter() {
-        if *e == rankless_env {
-            break;
+fn gen_fingerprint() -> String {
+    //FNV-1a over the generated code, so a bundle can be matched with the binary reading it
+    let gen_dir = std::path::Path::new("src").join("gen");
+    let mut paths: Vec<_> = std::fs::read_dir(gen_dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().map(|e| e == "rs").unwrap_or(false))
+        .collect();
+    paths.sort();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for p in paths {
+        for b in std::fs::read(p).unwrap() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
         }
-        e_ind.add_assign(1);
-    }
-
-    let year = 2025;
-    let env_dependent_vars = vec![
-        ("FINAL_YEAR", [year, year, year, year]),
-        ("START_YEAR", [1990, 1990, 1980, 1950]),
-        ("MIN_PAPERS_FOR_INST", [40, 20, 30, 250]),
-        ("MIN_PAPERS_FOR_SOURCE", [10, 20, 50, 200]),
-        ("MIN_AUTHOR_WORK_COUNT", [10, 10, 10, 8]),
-        ("MIN_AUTHOR_CITE_COUNT", [500, 500, 500, 400]),
-        // ("MIN_AUTHOR_H_INDEX", [3, 3, 3, 5]),
-        // ("MIN_AUTHOR_I10_INDEX", [2, 2, 2, 3]),
-    ];
-
-    let mut env_lines = Vec::new();
-    for e_var in env_dependent_vars.iter() {
-        env_lines.push(format!("pub const {}: u16 = {};", e_var.0, e_var.1[e_ind]))
     }
-    std::fs::write(&path, env_lines.join("\n")).unwrap();
+    format!("{hash:016x}")
 }
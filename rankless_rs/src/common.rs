@@ -1,36 +1,49 @@
 use std::fmt::{Debug, Display};
 use std::io::{prelude::*, BufWriter};
+use std::marker::PhantomData;
 use std::ops::Range;
+use std::os::unix::fs::symlink;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::{
-    fs::{create_dir_all, read_dir, File},
+    fs::{copy, create_dir_all, read_dir, read_link, remove_dir_all, remove_file, rename, File},
     io::{self, BufReader, Write},
     path::{Path, PathBuf},
 };
 
-use csv::{DeserializeRecordsIntoIter, Reader, ReaderBuilder};
+use csv::{Reader, ReaderBuilder, StringRecord, StringRecordsIntoIter};
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use hashbrown::{HashMap, HashSet};
 use serde::Deserialize;
 use serde::{de::DeserializeOwned, Serialize};
 use tqdm::{Iter, Tqdm};
 
+use crate::config::{self, PipelineConfig, CONFIG_FILE};
+use crate::csv_writers::{self, IngestState, MERGED_IDS, SUPERSEDED_FILE};
+use crate::record_store::{StoredRecords, StoredRow};
 use dmove::{
-    BackendLoading, BigId, CompactEntity, Entity, FixAttIterator, FixWriteSizeEntity, InitEmpty,
-    LoadedIdMap, MainBuilder, MappableEntity, MarkedAttribute, MetaIntegrator, NamespacedEntity,
-    UnsignedNumber, VarAttIterator, VarBox, VarSizedAttributeElement, VariableSizeAttribute,
-    VattArrPair, VattReadingMap, ET, MAA,
+    bundle, parse_generated_code,
+    stats::{AttributeStats, GeneratedAttribute},
+    BackendLoading, BigId, CompactEntity, Entity, FixAttIterator, FixWriteSizeEntity,
+    GeneratedEntity, InitEmpty, LoadedIdMap, MainBuilder, MappableEntity, MarkedAttribute,
+    MetaIntegrator, NamespacedEntity, UnsignedNumber, VarAttIterator, VarBox,
+    VarSizedAttributeElement, VariableSizeAttribute, VattArrPair, VattReadingMap, ET, MAA,
 };
 
 pub type StowReader = Reader<BufReader<GzDecoder<File>>>;
 pub type BeS<M, E> = <M as BackendSelector<E>>::BE;
 pub type NET<E> = <E as NumberedEntity>::T;
 
-type InIterator<T> = Tqdm<DeserializeRecordsIntoIter<BufReader<flate2::read::GzDecoder<File>>, T>>;
+type InIterator = Tqdm<StringRecordsIntoIter<BufReader<flate2::read::GzDecoder<File>>>>;
 
 pub const MAIN_NAME: &str = "main";
 pub const BUILD_LOC: &str = "qc-builds";
 pub const SEM_DIR: &str = "semantic-ids";
+pub const STAGING_SUFFIX: &str = "staging";
+pub const BACKUP_SUFFIX: &str = "bak";
+const VERSION_PREFIX: &str = "v";
+pub const NS_CODE_FILE: &str = "gen-code.rs";
+pub const GEN_FINGERPRINT: &str = env!("GEN_FINGERPRINT");
+pub const JSONL_SOURCE_FILE: &str = "jsonl-source.txt";
 // pub const A_STAT_PATH: &str = "attribute-statics";
 // pub const QC_CONF: &str = "qc-specs";
 
@@ -39,6 +52,8 @@ pub const ID_PREFIX: &str = "https://openalex.org/";
 pub struct NameMarker;
 pub struct NameExtensionMarker;
 pub struct DoiMarker;
+pub struct OrcidMarker;
+pub struct AlternativeNamesMarker;
 pub struct SemanticIdMarker;
 pub struct MainWorkMarker;
 pub struct WorkCountMarker;
@@ -55,6 +70,21 @@ pub struct Top3AuthorMarker;
 pub struct Top3CitingTopicMarker;
 pub struct Top3JournalMarker;
 pub struct Top3AffCountryMarker;
+pub struct NormalizedImpactMarker;
+pub struct TopDecileShareMarker;
+pub struct TwoYearCiteCountMarker;
+pub struct FiveYearCiteCountMarker;
+pub struct PageRankMarker;
+pub struct CollaboratorsMarker;
+pub struct CollabWeightsMarker;
+pub struct CollabDegreeMarker;
+pub struct WeightedCollabDegreeMarker;
+pub struct ClusteringMarker;
+pub struct CollabComponentMarker;
+pub struct RefSubfieldsConcentrationMarker;
+pub struct CitSubfieldsConcentrationMarker;
+pub struct RefSubfieldsDiversityMarker;
+pub struct CitSubfieldsDiversityMarker;
 
 #[macro_export]
 macro_rules! add_parsed_id_traits {
@@ -170,6 +200,15 @@ macro_rules! pathfields_fn {
                     )*
                 }
             }
+
+            ///the paths under a root, without creating them
+            pub fn locate(root_path: &str) -> Self{
+                Self {
+                    $(
+                        $k: Path::new(root_path).join(stringify!($k).replace("_","-")),
+                    )*
+                }
+            }
         }
     };
 }
@@ -178,13 +217,31 @@ pub struct Stowage {
     pub paths: PathCollection,
     current_ns: String,
     builder: Option<Mutex<MainBuilder>>,
+    bundle: Option<PathBuf>,
+    //snapshot data dir read directly instead of the csvs
+    jsonl: Option<PathBuf>,
+    //one per entity, held while its records are stored, so its concurrent readers wait for it
+    store_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    pub config: PipelineConfig,
 }
 
 pub struct ObjIter<T>
 where
     T: DeserializeOwned,
 {
-    iterable: InIterator<T>,
+    iterable: Option<InIterator>,
+    headers: StringRecord,
+    //column of the entity id the record belongs to
+    owner_col: Option<usize>,
+    shard: usize,
+    //shards of the table not read yet, in reverse order
+    rest: Vec<(usize, PathBuf)>,
+    //newest shard of entities updated by to-csv-update
+    superseded: HashMap<String, usize>,
+    //shards of the jsonl record store, read instead of csvs
+    stored: Option<Tqdm<StoredRecords>>,
+    desc: String,
+    _t: PhantomData<T>,
 }
 
 //TODO/clarity: this is sort of a mess - could be just generic types
@@ -233,7 +290,14 @@ pub trait NumberedEntity: MappableEntity<KeyType = BigId> {
 
 pub trait MainEntity: NumberedEntity + Entity<T = NET<Self>> {}
 
-pathfields_fn!(PathCollection, entity_csvs, filter_steps, cache);
+//the jsonl store is kept out of the cache, that filter runs clear
+pathfields_fn!(
+    PathCollection,
+    entity_csvs,
+    filter_steps,
+    cache,
+    jsonl_store
+);
 
 pub trait ParsedId {
     fn get_parsed_id(&self) -> BigId;
@@ -241,79 +305,271 @@ pub trait ParsedId {
 
 impl Stowage {
     pub fn new(root_path: &str) -> Self {
+        let config = PipelineConfig::load_or_init(Path::new(root_path)).unwrap();
+        Self::with_config(PathCollection::new(root_path), config)
+    }
+
+    ///opens a root without writing into it, for tools reading finished builds,
+    ///a root with no config of its own takes the installed one
+    pub fn read_only(root_path: &str) -> Self {
+        let config_path = Path::new(root_path).join(CONFIG_FILE);
+        let config = match bundle::open_data(&config_path) {
+            Ok(_) => PipelineConfig::read(&config_path).unwrap(),
+            Err(_) => config::installed()
+                .unwrap_or_else(|| panic!("no {config_path:?} and no config installed"))
+                .clone(),
+        };
+        Self::with_config(PathCollection::locate(root_path), config)
+    }
+
+    fn with_config(paths: PathCollection, config: PipelineConfig) -> Self {
+        let root_path = paths.entity_csvs.parent().unwrap().to_path_buf();
+        config::install(&config).unwrap();
+        let jsonl = std::fs::read_to_string(root_path.join(JSONL_SOURCE_FILE))
+            .ok()
+            .map(|e| PathBuf::from(e.trim()));
+        if let Some(src) = &jsonl {
+            println!("reading entities directly from {src:?}");
+        }
         Self {
-            paths: PathCollection::new(root_path),
+            paths,
             current_ns: "".to_string(),
             builder: None,
+            bundle: None,
+            jsonl,
+            store_locks: Mutex::new(HashMap::new()),
+            config,
         }
     }
 
-    pub fn set_namespace(&mut self, ns: &'static str) {
+    ///reads entities from the snapshot instead of the csvs from now on, to-csv switches back
+    pub fn link_jsonl(&self, snapshot_data: &str) -> io::Result<()> {
+        let src = Path::new(snapshot_data).canonicalize()?;
+        //records stored from an earlier snapshot are stale
+        if self.paths.jsonl_store.exists() {
+            remove_dir_all(&self.paths.jsonl_store)?;
+        }
+        std::fs::write(self.jsonl_source_path(), src.to_str().unwrap())?;
+        println!("linked {src:?} as entity source");
+        Ok(())
+    }
+
+    pub fn unlink_jsonl(&self) -> io::Result<()> {
+        let path = self.jsonl_source_path();
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+            println!("reading entities from csvs again");
+        }
+        Ok(())
+    }
+
+    pub fn jsonl_source(&self) -> Option<&Path> {
+        self.jsonl.as_deref()
+    }
+
+    fn jsonl_source_path(&self) -> PathBuf {
+        self.paths
+            .entity_csvs
+            .parent()
+            .unwrap()
+            .join(JSONL_SOURCE_FILE)
+    }
+
+    pub fn from_bundle(bundle_path: &str) -> Self {
+        let bundle = PathBuf::from(bundle_path);
+        let manifest = bundle::get_manifest(&bundle).expect("readable bundle");
+        assert_eq!(
+            manifest.fingerprint, GEN_FINGERPRINT,
+            "bundle {bundle_path} was packed with different gen code"
+        );
+        let root = bundle.parent().unwrap().to_str().unwrap();
+        //the config the bundled build was made with, not the one next to it
+        let config_path = bundle.join(&manifest.namespaces[0]).join(CONFIG_FILE);
+        let config = PipelineConfig::read(&config_path).unwrap();
+        config::install(&config).unwrap();
+        Self {
+            paths: PathCollection::new(root),
+            current_ns: "".to_string(),
+            builder: None,
+            bundle: Some(bundle),
+            jsonl: None,
+            store_locks: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    ///checks that the filters and the given namespaces were built with the current config
+    pub fn check_config(&self, namespaces: &[&str]) -> io::Result<()> {
+        if self.bundle.is_none() {
+            self.config.check_recorded(&self.paths.filter_steps)?;
+        }
+        for ns in namespaces {
+            self.config.check_recorded(&self.path_from_ns(ns))?;
+        }
+        Ok(())
+    }
+
+    pub fn pack_bundle(&self, namespaces: &[&str], out_path: &str) -> io::Result<()> {
+        let root = self.paths.entity_csvs.parent().unwrap();
+        bundle::pack(root, namespaces, GEN_FINGERPRINT, Path::new(out_path))?;
+        println!("packed {} namespaces into {out_path}", namespaces.len());
+        Ok(())
+    }
+
+    pub fn unpack_bundle(&self, bundle_path: &str) -> io::Result<()> {
+        let root = self.paths.entity_csvs.parent().unwrap();
+        let manifest = bundle::unpack(Path::new(bundle_path), root)?;
+        if manifest.fingerprint != GEN_FINGERPRINT {
+            println!("WARNING: {bundle_path} was packed with different gen code");
+        }
+        println!("unpacked {} files to {root:?}", manifest.file_count());
+        Ok(())
+    }
+
+    pub fn set_namespace(&mut self, ns: &str) {
         self.current_ns = ns.to_string();
-        let path = self.path_from_ns(&self.current_ns);
+        let path = self.staging_path(ns);
+        if path.exists() {
+            println!("removing stale staging {path:?}");
+            remove_dir_all(&path).unwrap();
+        }
         create_dir_all(&path).unwrap();
         self.builder = Some(Mutex::new(MainBuilder::new(&path)));
     }
 
     pub fn write_code(&self) -> io::Result<usize> {
         let suffix = self.current_ns.replace("-", "_");
-        self.mu_bu().write_code(&code_path(&suffix))
+        let cpath = code_path(&suffix);
+        let out = self.mu_bu().write_code(&cpath)?;
+        copy(
+            &cpath,
+            self.staging_path(&self.current_ns).join(NS_CODE_FILE),
+        )?;
+        self.config
+            .write(&self.staging_path(&self.current_ns).join(CONFIG_FILE))?;
+        self.publish_namespace()?;
+        Ok(out)
+    }
+
+    ///moves staging into a new version dir and points live at it with one rename,
+    ///so readers find either the old or the new namespace, never a half written one
+    pub fn publish_namespace(&self) -> io::Result<()> {
+        let ns = &self.current_ns;
+        let live = self.live_path(ns);
+        let backup = self.backup_path(ns);
+        let latest = self.versions(ns).pop();
+        let next = latest.as_ref().map(|e| e.0 + 1).unwrap_or(1);
+        let is_plain_dir = |p: &Path| p.symlink_metadata().map(|m| m.is_dir()).unwrap_or(false);
+        //namespaces published as plain dirs become the version before the new one
+        let (legacy, version) = match is_plain_dir(&live) {
+            true => (
+                Some(self.version_path(ns, next)),
+                self.version_path(ns, next + 1),
+            ),
+            false => (None, self.version_path(ns, next)),
+        };
+        rename(self.staging_path(ns), &version)?;
+        let previous = match &legacy {
+            Some(legacy) => {
+                rename(&live, legacy)?;
+                Some(legacy.clone())
+            }
+            //with no live link, readers were given the latest version
+            None => read_link(&live).ok().or(latest.map(|e| e.1)),
+        };
+        point_link(&live, &version)?;
+        if let Some(previous) = previous {
+            if is_plain_dir(&backup) {
+                remove_dir_all(&backup)?;
+            }
+            point_link(&backup, &previous)?;
+        }
+        self.prune_versions(ns)?;
+        println!("published {ns} to {version:?}");
+        Ok(())
+    }
+
+    pub fn restore_namespace(&self, ns: &str) -> io::Result<()> {
+        //swaps backup and live, so a restore can be undone the same way
+        let live = self.live_path(ns);
+        let backup = self.backup_path(ns);
+        let (Ok(live_target), Ok(backup_target)) = (read_link(&live), read_link(&backup)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no backup for {ns} at {backup:?}"),
+            ));
+        };
+        point_link(&live, &backup_target)?;
+        point_link(&backup, &live_target)?;
+        let code_backup = live.join(NS_CODE_FILE);
+        if code_backup.exists() {
+            copy(code_backup, code_path(&ns.replace("-", "_")))?;
+        }
+        println!("restored {ns} from {backup_target:?}");
+        Ok(())
     }
 
     pub fn get_out_csv_path(&self) -> &str {
         self.paths.entity_csvs.to_str().unwrap()
     }
 
-    pub fn get_filter_dir(&self, step_id: u8) -> PathBuf {
+    pub fn get_filter_dir(&self, step_id: u32) -> PathBuf {
         let out_root = self.paths.filter_steps.join(step_id.to_string());
         create_dir_all(&out_root).unwrap();
         out_root
     }
 
     pub fn get_last_filter(&self, entity_type: &str) -> Option<HashSet<BigId>> {
-        let mut out_path = None;
+        let pb = self.last_filter_path(entity_type)?;
+        let mut out = HashSet::new();
+        let mut br: [u8; 8] = [0; std::mem::size_of::<BigId>()];
+        let mut file = BufReader::new(File::open(pb).unwrap());
+        while let Ok(_) = file.read_exact(&mut br) {
+            out.insert(BigId::from_be_bytes(br));
+        }
+        Some(out)
+    }
 
-        if !self.paths.entity_csvs.join(entity_type).exists() {
+    pub fn last_filter_count(&self, entity_type: &str) -> Option<usize> {
+        let pb = self.last_filter_path(entity_type)?;
+        Some(pb.metadata().unwrap().len() as usize / std::mem::size_of::<BigId>())
+    }
+
+    fn last_filter_path(&self, entity_type: &str) -> Option<PathBuf> {
+        let entity_dir = match &self.jsonl {
+            Some(src) => src.join(entity_type),
+            None => self.paths.entity_csvs.join(entity_type),
+        };
+        if !entity_dir.exists() {
             println!("no such type {entity_type}");
             return None;
         }
-        let dirs = match read_dir(&self.paths.filter_steps) {
+        //step dirs are numbered, later steps override earlier ones
+        let mut dirs: Vec<(u32, PathBuf)> = match read_dir(&self.paths.filter_steps) {
             Err(_) => vec![],
-            Ok(rdir) => {
-                let mut v: Vec<PathBuf> = rdir.map(|e| e.unwrap().path()).collect();
-                v.sort();
-                v
-            }
+            Ok(rdir) => rdir
+                .map(|e| e.unwrap().path())
+                .filter_map(|p| Some((p.file_name()?.to_str()?.parse().ok()?, p)))
+                .collect(),
         };
-        for edir in dirs {
-            let maybe_path = edir.join(entity_type);
-            if maybe_path.exists() {
-                out_path = Some(maybe_path);
-            }
-        }
-        match out_path {
-            Some(pb) => {
-                let mut out = HashSet::new();
-                let mut br: [u8; 8] = [0; std::mem::size_of::<BigId>()];
-                let mut file = File::open(pb).unwrap();
-                while let Ok(_) = file.read_exact(&mut br) {
-                    out.insert(BigId::from_be_bytes(br));
-                }
-                Some(out)
-            }
-            None => None,
-        }
+        dirs.sort();
+        dirs.into_iter()
+            .map(|(_, edir)| edir.join(entity_type))
+            .filter(|p| p.exists())
+            .last()
     }
 
-    pub fn write_filter<'a, T>(&self, step_id: u8, entity_type: &str, id_iter: T) -> io::Result<()>
+    pub fn write_filter<'a, T>(&self, step_id: u32, entity_type: &str, id_iter: T) -> io::Result<()>
     where
         T: Iterator<Item = BigId>,
     {
-        let mut file = File::create(self.get_filter_dir(step_id).join(entity_type))?;
+        let mut file = BufWriter::new(File::create(
+            self.get_filter_dir(step_id).join(entity_type),
+        )?);
         for e in id_iter {
             file.write_all(&e.to_be_bytes())?;
         }
-        Ok(())
+        file.flush()
     }
 
     pub fn add_iter_owned<B, I, T>(&self, iter: I, name_o: Option<&str>)
@@ -334,6 +590,16 @@ impl Stowage {
         self.mu_bu().declare_marked_attribute::<E, Marker>(&name);
     }
 
+    ///attribute with a marker that is defined in the generated code of the namespace
+    pub fn declare_iter_generated<B, I, E, S>(&self, iter: I, name: &str) -> String
+    where
+        B: MetaIntegrator<E>,
+        I: Iterator<Item = E>,
+    {
+        self.add_iter_owned::<B, I, E>(iter, Some(name));
+        self.mu_bu().declare_generated_marker::<S>(name)
+    }
+
     pub fn declare_iter<B, I, E, S, M>(&self, iter: I, name: &str)
     where
         B: MetaIntegrator<E>,
@@ -348,7 +614,95 @@ impl Stowage {
         main_path: &str,
         sub_path: &str,
     ) -> ObjIter<T> {
-        read_deser_obj::<T>(&self.paths.entity_csvs, main_path, sub_path)
+        //tables that are not in the snapshot are still read from the csvs
+        if let Some(table_dir) = self.jsonl_store(main_path, sub_path) {
+            return ObjIter::from_store(&table_dir, main_path, sub_path);
+        }
+        read_deser_obj::<T>(&self.paths.entity_csvs, main_path, sub_path, 0)
+    }
+
+    ///records of the table stored from the linked snapshot, stored on first use
+    ///and again when they were stored from another one
+    fn jsonl_store(&self, main_path: &str, sub_path: &str) -> Option<PathBuf> {
+        let src = self.jsonl.as_ref()?;
+        let store = csv_writers::storer(main_path, sub_path)?;
+        let entity_dir = self.paths.jsonl_store.join(main_path);
+        let done_path = entity_dir.join(JSONL_SOURCE_FILE);
+        let entity_lock = self
+            .store_locks
+            .lock()
+            .unwrap()
+            .entry(main_path.to_string())
+            .or_default()
+            .clone();
+        let _storing = entity_lock.lock().unwrap();
+        let stored_from = std::fs::read_to_string(&done_path).ok();
+        if stored_from.as_deref() != src.to_str() {
+            if let Some(other) = stored_from {
+                println!("{main_path} was stored from {other}, storing it again");
+            }
+            let stats = store(src, &entity_dir).unwrap();
+            csv_writers::check_parse_stats(&[stats], self.config.max_parse_error_rate).unwrap();
+            std::fs::write(&done_path, src.to_str().unwrap()).unwrap();
+        }
+        Some(entity_dir.join(sub_path))
+    }
+
+    ///rows of a table added from outside the snapshot, empty if it was not added
+    pub fn read_optional_csv_objs<T: DeserializeOwned>(
+        &self,
+        main_path: &str,
+        sub_path: &str,
+    ) -> ObjIter<T> {
+        //side tables are read from the csvs even when reading the snapshot directly
+        let stored = self.jsonl.is_some() && csv_writers::storer(main_path, sub_path).is_some();
+        let path = self.paths.entity_csvs.join(main_path).join(sub_path);
+        if !stored && !path.exists() && !path.with_extension("csv.gz").exists() {
+            return ObjIter::empty(main_path, sub_path);
+        }
+        self.read_csv_objs(main_path, sub_path)
+    }
+
+    ///only the shards written since the given one, for extending earlier results
+    pub fn read_csv_objs_from<T: DeserializeOwned>(
+        &self,
+        main_path: &str,
+        sub_path: &str,
+        first_shard: usize,
+    ) -> ObjIter<T> {
+        read_deser_obj::<T>(&self.paths.entity_csvs, main_path, sub_path, first_shard)
+    }
+
+    ///rows of the merged id lists of the entity, empty if the snapshot had none
+    pub fn read_merged_ids<T: DeserializeOwned>(&self, entity: &str) -> ObjIter<T> {
+        let files = match &self.jsonl {
+            Some(src) => csv_writers::snapshot_partitions(&src.join(MERGED_IDS), entity),
+            None => {
+                csv_writers::snapshot_partitions(&self.paths.entity_csvs.join(entity), MERGED_IDS)
+            }
+        }
+        .unwrap();
+        if files.is_empty() {
+            return ObjIter::empty(entity, MERGED_IDS);
+        }
+        ObjIter::from_shards(files.into_iter().enumerate().collect(), entity, MERGED_IDS)
+    }
+
+    ///none when reading the snapshot directly, so nothing is extended incrementally
+    pub fn ingest_state(&self, entity: &str) -> Option<IngestState> {
+        if self.jsonl.is_some() {
+            return None;
+        }
+        IngestState::read(&self.paths.entity_csvs.join(entity))
+    }
+
+    ///the published version of a file of the current namespace
+    pub fn published_file(&self, name: &str) -> PathBuf {
+        self.live_path(&self.current_ns).join(name)
+    }
+
+    pub fn staged_file(&self, name: &str) -> PathBuf {
+        self.staging_path(&self.current_ns).join(name)
     }
 
     pub fn get_entity_interface<E, Marker>(&self) -> Marker::BE
@@ -368,10 +722,106 @@ impl Stowage {
         self.get_entity_interface::<MAA<E, AttMarker>, BeMarker>()
     }
 
+    ///entities and attributes of the data namespaces, as the code generated with them declares them
+    pub fn generated_entities(&self) -> Vec<(&'static str, GeneratedEntity)> {
+        let mut out = Vec::new();
+        for ns in crate::data_namespaces() {
+            let mut code = String::new();
+            let code_path = self.path_from_ns(ns).join(NS_CODE_FILE);
+            if bundle::open_data(&code_path)
+                .and_then(|mut f| f.read_to_string(&mut code))
+                .is_err()
+            {
+                continue;
+            }
+            out.extend(parse_generated_code(&code).into_iter().map(|e| (ns, e)));
+        }
+        out
+    }
+
+    ///numeric and var size attributes of the data namespaces
+    pub fn generated_attributes(&self) -> Vec<(&'static str, GeneratedAttribute)> {
+        self.generated_entities()
+            .into_iter()
+            .filter_map(|(ns, ge)| Some((ns, GeneratedAttribute::from_generated(ge)?)))
+            .collect()
+    }
+
+    pub fn attribute_stats(&self, ns: &str, att: &GeneratedAttribute) -> AttributeStats {
+        att.stats(&self.path_from_ns(ns))
+    }
+
     pub fn path_from_ns(&self, ns: &str) -> PathBuf {
+        if let Some(bundle) = &self.bundle {
+            return bundle.join(ns);
+        }
+        //entities of the namespace under construction are read from staging
+        let staging = self.staging_path(ns);
+        if ns == self.current_ns && staging.exists() {
+            return staging;
+        }
+        let live = self.live_path(ns);
+        //a publish stopped before pointing live leaves its versions complete
+        if !live.exists() {
+            if let Some((_, latest)) = self.versions(ns).pop() {
+                return latest;
+            }
+        }
+        live
+    }
+
+    fn live_path(&self, ns: &str) -> PathBuf {
         self.paths.entity_csvs.parent().unwrap().join(ns)
     }
 
+    fn staging_path(&self, ns: &str) -> PathBuf {
+        self.live_path(&ns_suffixed(ns, STAGING_SUFFIX))
+    }
+
+    fn backup_path(&self, ns: &str) -> PathBuf {
+        self.live_path(&ns_suffixed(ns, BACKUP_SUFFIX))
+    }
+
+    fn version_path(&self, ns: &str, version: usize) -> PathBuf {
+        self.live_path(&ns_suffixed(ns, &format!("{VERSION_PREFIX}{version}")))
+    }
+
+    ///published versions of a namespace, oldest first
+    fn versions(&self, ns: &str) -> Vec<(usize, PathBuf)> {
+        let prefix = ns_suffixed(ns, VERSION_PREFIX);
+        let root = self.paths.entity_csvs.parent().unwrap();
+        let mut out: Vec<(usize, PathBuf)> = read_dir(root)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| {
+                        let path = e.ok()?.path();
+                        let name = path.file_name()?.to_str()?;
+                        Some((name.strip_prefix(&prefix)?.parse().ok()?, path))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        out.sort();
+        out
+    }
+
+    ///removes the versions neither live nor backup point at
+    fn prune_versions(&self, ns: &str) -> io::Result<()> {
+        let pointed: Vec<PathBuf> = [self.live_path(ns), self.backup_path(ns)]
+            .iter()
+            .filter_map(|link| read_link(link).ok())
+            .collect();
+        for (_, path) in self.versions(ns) {
+            if !pointed
+                .iter()
+                .any(|e| Some(e.as_os_str()) == path.file_name())
+            {
+                remove_dir_all(path)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn mu_bu(&self) -> MutexGuard<MainBuilder> {
         self.builder.as_ref().unwrap().lock().unwrap()
     }
@@ -392,11 +842,63 @@ where
     T: DeserializeOwned,
 {
     pub fn new(reader: StowReader, main: &str, sub: &str) -> Self {
-        let iterable = reader
-            .into_deserialize::<T>()
-            .tqdm()
-            .desc(Some(format!("reading {} / {}", main, sub)));
-        Self { iterable }
+        let mut out = Self::empty(main, sub);
+        out.open(0, reader, out.desc.clone());
+        out
+    }
+
+    fn empty(main: &str, sub: &str) -> Self {
+        Self {
+            iterable: None,
+            headers: StringRecord::new(),
+            owner_col: None,
+            shard: 0,
+            rest: Vec::new(),
+            superseded: HashMap::new(),
+            stored: None,
+            desc: format!("reading {} / {}", main, sub),
+            _t: PhantomData,
+        }
+    }
+
+    fn from_shards(mut shards: Vec<(usize, PathBuf)>, main: &str, sub: &str) -> Self {
+        shards.sort();
+        shards.reverse();
+        let (shard, first) = shards.pop().expect("at least one shard");
+        let mut out = Self::new(csv_reader(&first), main, sub);
+        out.shard = shard;
+        out.rest = shards;
+        out
+    }
+
+    fn from_store(table_dir: &Path, main: &str, sub: &str) -> Self {
+        let mut out = Self::empty(main, sub);
+        //no shards if no line of the snapshot had rows in the table
+        if let Ok(rdir) = read_dir(table_dir) {
+            let mut shards: Vec<PathBuf> = rdir.map(|e| e.unwrap().path()).collect();
+            shards.sort();
+            out.rest = shards.into_iter().enumerate().collect();
+            out.rest.reverse();
+        }
+        out.stored = Some(StoredRecords::empty().tqdm());
+        out
+    }
+
+    fn open(&mut self, shard: usize, mut reader: StowReader, desc: String) {
+        self.headers = reader.headers().unwrap().clone();
+        self.owner_col = ["parent_id", "id"]
+            .iter()
+            .find_map(|k| self.headers.iter().position(|h| h == *k));
+        self.shard = shard;
+        self.iterable = Some(reader.into_records().tqdm().desc(Some(desc)));
+    }
+
+    fn is_superseded(&self, rec: &StringRecord) -> bool {
+        let owner = self.owner_col.and_then(|i| rec.get(i));
+        match owner.and_then(|id| self.superseded.get(id)) {
+            Some(newest) => *newest > self.shard,
+            None => false,
+        }
     }
 }
 
@@ -497,10 +999,32 @@ where
 impl<T: DeserializeOwned> Iterator for ObjIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(obj) = self.iterable.next() {
-            return Some(obj.unwrap());
-        } else {
-            return None;
+        if let Some(stored) = self.stored.as_mut() {
+            loop {
+                if let Some(fields) = stored.next() {
+                    let row = StoredRow::new(&self.headers, fields);
+                    return Some(
+                        T::deserialize(row).unwrap_or_else(|e| panic!("{}: {e}", self.desc)),
+                    );
+                }
+                let (_, path) = self.rest.pop()?;
+                let desc = format!("{} [{} left]", self.desc, self.rest.len());
+                let (headers, records) = StoredRecords::open(&path);
+                self.headers = headers;
+                *stored = records.tqdm().desc(Some(desc));
+            }
+        }
+        loop {
+            if let Some(rec) = self.iterable.as_mut().and_then(|e| e.next()) {
+                let rec = rec.unwrap();
+                if self.is_superseded(&rec) {
+                    continue;
+                }
+                return Some(rec.deserialize(Some(&self.headers)).unwrap());
+            }
+            let (shard, path) = self.rest.pop()?;
+            let desc = format!("{} [{} left]", self.desc, self.rest.len());
+            self.open(shard, csv_reader(&path), desc);
         }
     }
 }
@@ -588,15 +1112,311 @@ pub fn code_path(suffix: &str) -> String {
     format!("rankless_rs/src/gen/{}.rs", suffix)
 }
 
-fn read_deser_obj<T: DeserializeOwned>(root: &Path, main_path: &str, sub_path: &str) -> ObjIter<T> {
-    let gz_buf = get_gz_buf(
-        root.join(main_path)
-            .join(sub_path)
-            .with_extension("csv.gz")
-            .to_str()
-            .unwrap(),
-    )
-    .unwrap();
-    let reader = ReaderBuilder::new().from_reader(gz_buf);
-    ObjIter::new(reader, main_path, sub_path)
+fn ns_suffixed(ns: &str, suffix: &str) -> String {
+    format!("{ns}.{suffix}")
+}
+
+///points a link at a dir next to it, replacing the link with a single rename
+fn point_link(link: &Path, target: &Path) -> io::Result<()> {
+    let tmp = PathBuf::from(format!("{}.link", link.display()));
+    if tmp.symlink_metadata().is_ok() {
+        remove_file(&tmp)?;
+    }
+    symlink(target.file_name().unwrap(), &tmp)?;
+    rename(&tmp, link)
+}
+
+fn read_deser_obj<T: DeserializeOwned>(
+    root: &Path,
+    main_path: &str,
+    sub_path: &str,
+    first_shard: usize,
+) -> ObjIter<T> {
+    let single = root.join(main_path).join(sub_path).with_extension("csv.gz");
+    if single.exists() {
+        return ObjIter::new(csv_reader(&single), main_path, sub_path);
+    }
+    //to-csv writes one shard per snapshot partition into a directory per table
+    let shard_dir = root.join(main_path).join(sub_path);
+    let shards: Vec<(usize, PathBuf)> = read_dir(&shard_dir)
+        .unwrap_or_else(|_| panic!("no table at {single:?} or {shard_dir:?}"))
+        .map(|e| e.unwrap().path())
+        .filter_map(|p| {
+            Some((
+                p.to_str()?
+                    .strip_suffix(".csv.gz")?
+                    .rsplit('/')
+                    .next()?
+                    .parse()
+                    .ok()?,
+                p,
+            ))
+        })
+        .filter(|(i, _)| *i >= first_shard)
+        .collect();
+    if shards.is_empty() {
+        return ObjIter::empty(main_path, sub_path);
+    }
+    let mut out = ObjIter::from_shards(shards, main_path, sub_path);
+    out.superseded = read_superseded(&root.join(main_path));
+    out
+}
+
+fn read_superseded(entity_dir: &Path) -> HashMap<String, usize> {
+    let mut out = HashMap::new();
+    let file = match File::open(entity_dir.join(SUPERSEDED_FILE)) {
+        Ok(f) => BufReader::new(f),
+        Err(_) => return out,
+    };
+    for line in file.lines() {
+        let line = line.unwrap();
+        let (id, shard) = line.split_once('\t').expect("id and shard");
+        let shard: usize = shard.parse().unwrap();
+        let newest = out.entry(id.to_string()).or_insert(shard);
+        *newest = shard.max(*newest);
+    }
+    out
+}
+
+fn csv_reader(path: &Path) -> StowReader {
+    let gz_buf = get_gz_buf(path).unwrap_or_else(|_| panic!("{path:?}"));
+    ReaderBuilder::new().from_reader(gz_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oa_structs::{post, FieldLike, ReferencedWork, Work, WorkTopic};
+
+    #[test]
+    fn jsonl_store() {
+        let root = std::env::temp_dir().join(format!("rankless-jsonl-{}", std::process::id()));
+        let partition = root.join("snapshot/fields/updated_date=2024-01-01");
+        create_dir_all(&partition).unwrap();
+        let mut gz = get_gz_bufw(partition.join("part_000.gz"));
+        for line in [
+            r#"{"id":"https://openalex.org/fields/17","display_name":"Computer Science","counts_by_year":[{"year":2020,"cited_by_count":5}]}"#,
+            r#"{"id":"https://openalex.org/fields/26","display_name":3}"#,
+            r#"{"id":"https://openalex.org/fields/31","display_name":"Physics"}"#,
+        ] {
+            writeln!(gz, "{line}").unwrap();
+        }
+        drop(gz);
+        let mut config = PipelineConfig::preset("nano");
+        config.max_parse_error_rate = 0.5;
+        config.write(&root.join(CONFIG_FILE)).unwrap();
+
+        let stowage = Stowage::new(root.to_str().unwrap());
+        stowage
+            .link_jsonl(root.join("snapshot").to_str().unwrap())
+            .unwrap();
+        let stowage = Stowage::new(root.to_str().unwrap());
+        let names: Vec<String> = stowage
+            .read_csv_objs::<FieldLike>("fields", MAIN_NAME)
+            .map(|e| e.display_name)
+            .collect();
+        assert_eq!(names, vec!["Computer Science", "Physics"]);
+        assert_eq!(
+            stowage
+                .read_csv_objs::<CountRow>("fields", "counts")
+                .count(),
+            1
+        );
+        assert_eq!(
+            stowage.read_csv_objs::<CountRow>("fields", "ids").count(),
+            0
+        );
+        let side_table = stowage.read_optional_csv_objs::<CountRow>("fields", "areas");
+        assert_eq!(side_table.count(), 0);
+        let quarantined = root.join("jsonl-store/fields/quarantine.jsonl.gz");
+        assert_eq!(get_gz_buf(quarantined).unwrap().lines().count(), 1);
+
+        //filter runs clear the cache, the stored records stay
+        remove_dir_all(root.join("cache")).unwrap();
+        assert!(root
+            .join("jsonl-store/fields")
+            .join(JSONL_SOURCE_FILE)
+            .exists());
+        //records stored from another snapshot are stored again
+        let other = root.join("other");
+        let partition = other.join("fields/updated_date=2024-02-01");
+        create_dir_all(&partition).unwrap();
+        let mut gz = get_gz_bufw(partition.join("part_000.gz"));
+        writeln!(
+            gz,
+            r#"{{"id":"https://openalex.org/fields/11","display_name":"Biology"}}"#
+        )
+        .unwrap();
+        drop(gz);
+        std::fs::write(root.join(JSONL_SOURCE_FILE), other.to_str().unwrap()).unwrap();
+        let stowage = Stowage::new(root.to_str().unwrap());
+        let names: Vec<String> = stowage
+            .read_csv_objs::<FieldLike>("fields", MAIN_NAME)
+            .map(|e| e.display_name)
+            .collect();
+        assert_eq!(names, vec!["Biology"]);
+        remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn jsonl_store_reads_like_csvs() {
+        let root = std::env::temp_dir().join(format!("rankless-store-csvs-{}", std::process::id()));
+        let snapshot = root.join("snapshot");
+        for (entity, line) in [
+            (
+                "works",
+                r#"{"id":"https://openalex.org/W1","display_name":"A paper","publication_year":2020,"type":"article","cited_by_count":3,"is_retracted":false,"referenced_works":["https://openalex.org/W2"],"topics":[{"id":"https://openalex.org/T5","score":0.9}],"locations":[{"source":{"id":"https://openalex.org/S7"},"is_oa":true},{"source":null}],"authorships":[{"author":{"id":"https://openalex.org/A3"},"institutions":[{"id":"https://openalex.org/I4"},{"id":"https://openalex.org/I6"}],"author_position":"first"}],"grants":[{"funder":"https://openalex.org/F8","award_id":"x1"}],"biblio":{"volume":"2"},"ids":{"openalex":"https://openalex.org/W1","doi":"https://doi.org/10.1/x"}}"#,
+            ),
+            (
+                "sources",
+                r#"{"id":"https://openalex.org/S7","issn_l":"1234-5678","alternate_titles":["J A"],"host_organization":"https://openalex.org/P9"}"#,
+            ),
+        ] {
+            let partition = snapshot.join(entity).join("updated_date=2024-01-01");
+            create_dir_all(&partition).unwrap();
+            let mut gz = get_gz_bufw(partition.join("part_000.gz"));
+            writeln!(gz, "{line}").unwrap();
+        }
+        PipelineConfig::preset("nano")
+            .write(&root.join(CONFIG_FILE))
+            .unwrap();
+        let read_all = |stowage: &Stowage| {
+            let mut out = debug_rows::<Work>(stowage, "works", MAIN_NAME);
+            out.extend(debug_rows::<post::Authorship>(
+                stowage,
+                "works",
+                "authorships",
+            ));
+            out.extend(debug_rows::<post::Location>(stowage, "works", "locations"));
+            out.extend(debug_rows::<post::Grant>(stowage, "works", "grants"));
+            out.extend(debug_rows::<WorkTopic>(stowage, "works", "topics"));
+            out.extend(debug_rows::<ReferencedWork>(
+                stowage,
+                "works",
+                "referenced_works",
+            ));
+            out.extend(debug_rows::<post::Source>(stowage, "sources", MAIN_NAME));
+            out
+        };
+
+        let stowage = Stowage::new(root.to_str().unwrap());
+        let (in_root, out_root) = (
+            snapshot.to_str().unwrap(),
+            stowage.paths.entity_csvs.to_str().unwrap(),
+        );
+        csv_writers::works::write(in_root, out_root, false).unwrap();
+        csv_writers::sources::write(in_root, out_root, false).unwrap();
+        let from_csvs = read_all(&stowage);
+        assert_eq!(from_csvs.len(), 8);
+        stowage.link_jsonl(in_root).unwrap();
+        let linked = Stowage::new(root.to_str().unwrap());
+        assert_eq!(read_all(&linked), from_csvs);
+        remove_dir_all(&root).unwrap();
+    }
+
+    fn debug_rows<T: DeserializeOwned + Debug>(
+        stowage: &Stowage,
+        main: &str,
+        sub: &str,
+    ) -> Vec<String> {
+        stowage
+            .read_csv_objs::<T>(main, sub)
+            .map(|e| format!("{e:?}"))
+            .collect()
+    }
+
+    #[test]
+    fn read_only_roots() {
+        let root = std::env::temp_dir().join(format!("rankless-read-only-{}", std::process::id()));
+        let built = Stowage::new(root.join("built").to_str().unwrap());
+        let out_root = root.join("out");
+        let out = Stowage::read_only(out_root.to_str().unwrap());
+        assert!(!out_root.exists());
+        assert_eq!(out.config, built.config);
+
+        let other_root = root.join("other");
+        create_dir_all(&other_root).unwrap();
+        let mut other = built.config.clone();
+        other.min_papers_for_inst += 1;
+        other.write(&other_root.join(CONFIG_FILE)).unwrap();
+        let opened = std::panic::catch_unwind(|| Stowage::read_only(other_root.to_str().unwrap()));
+        assert!(opened.is_err());
+        remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn publish_and_restore() {
+        let root = std::env::temp_dir().join(format!("rankless-publish-{}", std::process::id()));
+        let mut stowage = Stowage::new(root.to_str().unwrap());
+        let ns = "test_ns";
+        let read_live = |stowage: &Stowage| {
+            std::fs::read_to_string(stowage.path_from_ns(ns).join("x")).unwrap()
+        };
+        //a namespace published before versions were kept
+        create_dir_all(root.join(ns)).unwrap();
+        std::fs::write(root.join(ns).join("x"), "0").unwrap();
+        for content in ["1", "2"] {
+            stowage.set_namespace(ns);
+            std::fs::write(stowage.staging_path(ns).join("x"), content).unwrap();
+            stowage.publish_namespace().unwrap();
+        }
+        stowage.current_ns = "".to_string();
+        assert_eq!(read_live(&stowage), "2");
+        assert_eq!(stowage.versions(ns).len(), 2);
+        stowage.restore_namespace(ns).unwrap();
+        assert_eq!(read_live(&stowage), "1");
+        stowage.restore_namespace(ns).unwrap();
+        assert_eq!(read_live(&stowage), "2");
+
+        //stopped after the new version is in place, live still points at the old one
+        stowage.set_namespace(ns);
+        std::fs::write(stowage.staging_path(ns).join("x"), "3").unwrap();
+        rename(stowage.staging_path(ns), stowage.version_path(ns, 9)).unwrap();
+        stowage.current_ns = "".to_string();
+        assert_eq!(read_live(&stowage), "2");
+        //stopped with no live link, the latest complete version is read
+        remove_file(root.join(ns)).unwrap();
+        assert_eq!(read_live(&stowage), "3");
+        stowage.set_namespace(ns);
+        std::fs::write(stowage.staging_path(ns).join("x"), "4").unwrap();
+        stowage.publish_namespace().unwrap();
+        stowage.current_ns = "".to_string();
+        assert_eq!(read_live(&stowage), "4");
+        assert_eq!(stowage.versions(ns).len(), 2);
+        stowage.restore_namespace(ns).unwrap();
+        assert_eq!(read_live(&stowage), "3");
+        remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn generated_attribute_stats() {
+        let root = std::env::temp_dir().join(format!("rankless-stats-{}", std::process::id()));
+        let stowage = Stowage::new(root.to_str().unwrap());
+        let ns_dir = stowage.path_from_ns("a1_entity_mapping");
+        create_dir_all(&ns_dir).unwrap();
+        let code = [
+            "impl Entity for Counts { type T = u16; const N: usize = 4; const NAME: & str = \"counts\"; }",
+            "impl Entity for Names { type T = String; const N: usize = 4; const NAME: & str = \"names\"; }",
+        ];
+        std::fs::write(ns_dir.join(NS_CODE_FILE), code.join("\n\n")).unwrap();
+        let values: Vec<u8> = [0_u16, 3, 300, 5]
+            .iter()
+            .flat_map(|e| e.to_be_bytes())
+            .collect();
+        std::fs::write(ns_dir.join("counts"), values).unwrap();
+
+        let atts = stowage.generated_attributes();
+        assert_eq!(atts.len(), 1);
+        let (ns, att) = &atts[0];
+        assert_eq!((*ns, att.name.as_str()), ("a1_entity_mapping", "counts"));
+        let stats = stowage.attribute_stats(ns, att);
+        assert_eq!((stats.count, stats.min, stats.max), (4, 0, 300));
+        remove_dir_all(&root).unwrap();
+    }
+
+    #[derive(Deserialize)]
+    struct CountRow {
+        #[allow(dead_code)]
+        parent_id: String,
+    }
 }
@@ -0,0 +1,204 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::Path,
+    sync::OnceLock,
+};
+
+use dmove::{bundle::open_data, Entity};
+use serde::{Deserialize, Serialize};
+
+use crate::steps::a1_entity_mapping::{RawYear, Years, YBT};
+
+pub const CONFIG_FILE: &str = "pipeline-config.json";
+pub const ENV_VAR: &str = "RANKLESS_ENV";
+const DEFAULT_PRESET: &str = "full";
+//year ids are below Years::N
+const MAX_YEAR_SPAN: RawYear = Years::N as RawYear - 1;
+//derive_links5 keeps an era of 11 years
+const MIN_YEAR_SPAN: RawYear = 10;
+
+#[cfg(not(test))]
+static CONFIG: OnceLock<PipelineConfig> = OnceLock::new();
+#[cfg(test)]
+thread_local! {
+    static CONFIG: OnceLock<&'static PipelineConfig> = const { OnceLock::new() };
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PipelineConfig {
+    pub final_year: RawYear,
+    pub start_year: RawYear,
+    pub min_papers_for_inst: usize,
+    pub min_papers_for_source: usize,
+    pub min_author_work_count: usize,
+    pub min_author_cite_count: usize,
+    pub year_filters: YBT,
+    //share of snapshot lines per entity that may fail to parse before to-csv errors
+    #[serde(default)]
+    pub max_parse_error_rate: f64,
+}
+
+impl PipelineConfig {
+    pub fn preset(name: &str) -> Self {
+        //nano, micro, mini, full
+        let ind = ["nano", "micro", "mini"]
+            .iter()
+            .position(|e| *e == name)
+            .unwrap_or(3);
+        let start_year = [1990, 1990, 1980, 1950][ind];
+        Self {
+            final_year: 2025,
+            start_year,
+            min_papers_for_inst: [40, 20, 30, 250][ind],
+            min_papers_for_source: [10, 20, 50, 200][ind],
+            min_author_work_count: [10, 10, 10, 8][ind],
+            min_author_cite_count: [500, 500, 500, 400][ind],
+            year_filters: [start_year, 2010, 2015, 2020, 2021, 2022, 2023, 2024],
+            max_parse_error_rate: 0.0,
+        }
+    }
+
+    ///reads the config file of the root, writing the preset named by RANKLESS_ENV if it is missing
+    pub fn load_or_init(root: &Path) -> io::Result<Self> {
+        let path = root.join(CONFIG_FILE);
+        if path.exists() {
+            return Self::read(&path);
+        }
+        let preset = std::env::var(ENV_VAR).unwrap_or(DEFAULT_PRESET.to_string());
+        let out = Self::preset(&preset);
+        out.validate()?;
+        if root.is_dir() {
+            out.write(&path)?;
+            println!("no {CONFIG_FILE} in {root:?}, wrote the {preset} preset");
+        }
+        Ok(out)
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let out: Self = serde_json::from_reader(BufReader::new(open_data(path)?))
+            .map_err(|e| invalid(format!("{path:?}: {e}")))?;
+        out.validate()?;
+        Ok(out)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), self)
+            .map_err(io::Error::from)
+    }
+
+    pub fn validate(&self) -> io::Result<()> {
+        let span = self.final_year.checked_sub(self.start_year).unwrap_or(0);
+        if span < MIN_YEAR_SPAN || span > MAX_YEAR_SPAN {
+            return Err(invalid(format!(
+                "{} - {} must span {MIN_YEAR_SPAN} to {MAX_YEAR_SPAN} years",
+                self.start_year, self.final_year
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.max_parse_error_rate) {
+            return Err(invalid(format!(
+                "max parse error rate {} must be between 0 and 1",
+                self.max_parse_error_rate
+            )));
+        }
+        if self.year_filters[0] != self.start_year {
+            return Err(invalid(format!(
+                "first year filter {} must be the start year {}",
+                self.year_filters[0], self.start_year
+            )));
+        }
+        if self.year_filters.windows(2).any(|w| w[0] >= w[1])
+            || self.year_filters.iter().any(|y| *y > self.final_year)
+        {
+            return Err(invalid(format!(
+                "year filters {:?} must be increasing and not after {}",
+                self.year_filters, self.final_year
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn threshold(&self, name: &str) -> Option<usize> {
+        match name {
+            "min_papers_for_inst" => Some(self.min_papers_for_inst),
+            "min_papers_for_source" => Some(self.min_papers_for_source),
+            "min_author_work_count" => Some(self.min_author_work_count),
+            "min_author_cite_count" => Some(self.min_author_cite_count),
+            _ => None,
+        }
+    }
+
+    ///the config a finished step recorded in its namespace has to match the current one
+    pub fn check_recorded(&self, ns_dir: &Path) -> io::Result<()> {
+        let path = ns_dir.join(CONFIG_FILE);
+        if open_data(&path).is_err() {
+            println!("WARNING: no recorded config in {ns_dir:?}");
+            return Ok(());
+        }
+        let recorded = Self::read(&path)?;
+        if recorded != *self {
+            return Err(invalid(format!(
+                "{ns_dir:?} was built with {recorded:?}, current config is {self:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+///sets the process wide config, a different one installed later is an error,
+///as year ids and thresholds of one process follow one config
+pub fn install(config: &PipelineConfig) -> io::Result<()> {
+    let installed = slot(Some(config)).unwrap();
+    if installed != config {
+        return Err(invalid(format!(
+            "config {config:?} differs from the installed {installed:?}"
+        )));
+    }
+    Ok(())
+}
+
+pub fn installed() -> Option<&'static PipelineConfig> {
+    slot(None)
+}
+
+pub fn get() -> &'static PipelineConfig {
+    installed().expect("no pipeline config installed, create a Stowage first")
+}
+
+#[cfg(not(test))]
+fn slot(init: Option<&PipelineConfig>) -> Option<&'static PipelineConfig> {
+    match init {
+        Some(config) => Some(CONFIG.get_or_init(|| config.clone())),
+        None => CONFIG.get(),
+    }
+}
+
+//tests run on threads of one process, each with the stowages of its own config
+#[cfg(test)]
+fn slot(init: Option<&PipelineConfig>) -> Option<&'static PipelineConfig> {
+    CONFIG.with(|slot| match init {
+        Some(config) => Some(*slot.get_or_init(|| Box::leak(Box::new(config.clone())))),
+        None => slot.get().copied(),
+    })
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_config_fails() {
+        let config = PipelineConfig::preset("mini");
+        install(&config).unwrap();
+        install(&config).unwrap();
+        let mut other = config.clone();
+        other.start_year += 1;
+        other.year_filters[0] += 1;
+        assert!(install(&other).is_err());
+        assert_eq!(get(), &config);
+    }
+}
@@ -1,21 +1,69 @@
 use csv::Writer;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
-use serde::{de::DeserializeOwned, Deserialize};
-use std::fs::{create_dir_all, read_dir, File};
-use std::io::{self, BufRead, BufReader, BufWriter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs::{copy, create_dir_all, read_dir, remove_dir_all, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc, Mutex,
+};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tqdm::Iter;
 
 use crate::common::Stowage;
 use crate::oa_structs::{
-    Ancestor, AssociatedInstitution, Author, Authorship, Biblio, Concept, FieldLike, Geo,
-    IdCountDecorated, IdTrait, Institution, Location, OpenAccess, Publisher, RelatedConcept,
+    Ancestor, AssociatedInstitution, Author, Authorship, Biblio, Concept, FieldLike, Funder, Geo,
+    Grant, IdCountDecorated, IdTrait, Institution, Location, OpenAccess, Publisher, RelatedConcept,
     Source, SubField, SummaryStats, Topic, Work, WorkTopic,
 };
+use crate::record_store::{RowSink, StoreTable, STORE_EXTENSION};
 
 type GzInner = GzEncoder<BufWriter<File>>;
 type GzWriter = Writer<GzInner>;
 
+const QUARANTINE_FILE: &str = "quarantine.jsonl.gz";
+pub const INGEST_STATE_FILE: &str = "ingest-state.json";
+pub const SUPERSEDED_FILE: &str = "superseded.tsv";
+pub const MERGED_IDS: &str = "merged_ids";
+const PARTITION_PREFIX: &str = "updated_date=";
+
+pub type Storer = fn(&Path, &Path) -> io::Result<ParseStats>;
+
+///what the csvs of an entity were converted from, so an update can continue after it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IngestState {
+    //unix seconds of the last full conversion, updates keep it
+    pub base: u64,
+    pub last_updated_date: String,
+    pub next_shard: usize,
+}
+
+pub struct ParseStats {
+    pub entity: &'static str,
+    pub lines: usize,
+    pub errors: usize,
+    pub quarantine: PathBuf,
+}
+
+#[derive(Serialize)]
+struct QuarantineRecord<'a> {
+    partition: &'a Path,
+    error: &'a str,
+    line: &'a str,
+}
+
+///raw lines that failed to parse, with the path of the failing field, shared by the workers
+struct Quarantine {
+    path: PathBuf,
+    //updates add to the failures of the earlier runs instead of replacing them
+    append: bool,
+    out: Mutex<Option<GzInner>>,
+    lines: AtomicUsize,
+    errors: AtomicUsize,
+}
+
 macro_rules! sub_write {
     ($parent:ident, $writer_name:ident, $field_name: ident) => {
         let parent_id = $parent.get_id();
@@ -38,18 +86,35 @@ macro_rules! sub_multi_write {
 }
 macro_rules! create_csv_struct {
     ($struct_name:ident, $($rest:ident),*) => {
-        pub struct $struct_name {
-            $($rest: GzWriter),*
+        pub struct $struct_name<W: RowSink = GzWriter> {
+            $($rest: W),*
         }
 
         impl $struct_name {
-            pub fn new(root_path: &Path) -> Self {
+            pub const TABLES: &[&str] = &[$(stringify!($rest)),*];
+
+            pub fn new(root_path: &Path, shard: usize) -> Self {
                 Self {
-                    $($rest: get_writer(root_path, stringify!($rest)).unwrap()),*
+                    $($rest: get_writer(root_path, stringify!($rest), shard).unwrap()),*
                 }
             }
         }
 
+        impl $struct_name<StoreTable> {
+            fn stored(out_dir: &Path, shard: usize) -> Self {
+                Self {
+                    $($rest: StoreTable::new(
+                        out_dir.join(stringify!($rest)).join(store_shard_name(shard))
+                    )),*
+                }
+            }
+
+            fn finish(self) -> io::Result<()> {
+                $(self.$rest.finish()?;)*
+                Ok(())
+            }
+        }
+
     };
 }
 macro_rules! create_complex_writers {
@@ -87,11 +152,11 @@ macro_rules! create_complex_writers {
             create_csv_struct!(ModWriter, main, ids, counts $(, $rest_key)* $(, $rest_single_key)* $(, $rest_inner_key)*);
 
 
-            impl ModWriter {
+            impl<W: RowSink> ModWriter<W> {
 
-                fn write_line(&mut self, line: &str) {
+                fn write_line(&mut self, line: &str) -> Result<String, String> {
                     #[allow(unused_mut)]
-                    let mut outer: Decorated = deserialize_verbose(line);
+                    let mut outer: Decorated = deserialize_verbose(line)?;
 
                     $(let $rest_key = &mut self.$rest_key;)*
                     $(sub_multi_write!(outer, $rest_key, $rest_key);)*
@@ -110,31 +175,101 @@ macro_rules! create_complex_writers {
                     let cb_writer = &mut self.counts;
                     sub_write!(parent, id_writer, ids);
                     sub_multi_write!(parent, cb_writer, counts_by_year);
+                    let id = parent.get_id();
                     self.main.serialize(parent.child).unwrap();
+                    Ok(id)
                 }
             }
 
+            ///reads every snapshot line once, keeping the rows of all tables as typed records
+            pub fn store(in_root: &Path, out_dir: &Path) -> io::Result<ParseStats> {
+                let slug = stringify!($mod_name);
+                let gz_files = snapshot_partitions(in_root, slug)?;
+                if out_dir.exists() {
+                    remove_dir_all(out_dir)?;
+                }
+                create_dir_all(out_dir)?;
+                let quarantine = Quarantine::new(out_dir, false);
+                write_partitions(slug, &gz_files, |shard, gz_path| {
+                    let mut store = ModWriter::stored(out_dir, shard);
+                    let reader = BufReader::new(GzDecoder::new(File::open(gz_path)?));
+                    let (mut lines, mut errors) = (0, 0);
+                    for line in reader.lines() {
+                        let line = line?;
+                        lines += 1;
+                        if let Err(e) = store.write_line(&line) {
+                            errors += 1;
+                            quarantine.add(gz_path, &e, &line)?;
+                        }
+                    }
+                    quarantine.count(lines, errors);
+                    store.finish()
+                })?;
+                quarantine.finish(slug)
+            }
+
             pub fn write(
                 in_root_str: &str,
                 out_root_str: &str,
-            ) -> io::Result<()> {
+                update: bool,
+            ) -> io::Result<ParseStats> {
                     let slug = stringify!($mod_name);
-                    let mut gz_files: Vec<PathBuf> = vec![];
-                    let in_dir = Path::new(&in_root_str).join(slug);
-                    fill_with_files(&in_dir, &mut gz_files, "gz").unwrap();
+                    let mut gz_files = snapshot_partitions(Path::new(&in_root_str), slug).unwrap();
 
                     let out_dir = Path::new(&out_root_str).join(slug);
-                    create_dir_all(&out_dir)?;
-                    let mut writer = ModWriter::new(&out_dir);
-                    for gz_path in gz_files.iter().tqdm().desc(Some(slug)) {
-                        let file_gz = File::open(gz_path)?;
-                        let gz_decoder = GzDecoder::new(file_gz);
-                        let reader = BufReader::new(gz_decoder);
+                    let prev = if update { IngestState::read(&out_dir) } else { None };
+                    if update && prev.is_none() {
+                        println!("no ingest state for {slug}, converting everything");
+                    }
+                    let state = match &prev {
+                        Some(st) => {
+                            //partitions of entities updated since, appended as new shards
+                            gz_files.retain(|p| partition_date(p) > st.last_updated_date.as_str());
+                            println!("{slug}: {} partitions after {}", gz_files.len(), st.last_updated_date);
+                            st.clone()
+                        }
+                        None => {
+                            if out_dir.exists() {
+                                remove_dir_all(&out_dir)?;
+                            }
+                            create_dir_all(&out_dir)?;
+                            if gz_files.is_empty() {
+                                //empty tables, so readers find them
+                                ModWriter::new(&out_dir, 0);
+                            }
+                            IngestState::empty()
+                        }
+                    };
+                    let quarantine = Quarantine::new(&out_dir, prev.is_some());
+                    let updated = Mutex::new(Vec::new());
+                    write_partitions(slug, &gz_files, |i, gz_path| {
+                        let shard = state.next_shard + i;
+                        let mut writer = ModWriter::new(&out_dir, shard);
+                        let reader = BufReader::new(GzDecoder::new(File::open(gz_path)?));
+                        let (mut lines, mut errors) = (0, 0);
+                        let mut ids = Vec::new();
                         for line in reader.lines() {
-                            writer.write_line(&line.unwrap());
+                            let line = line?;
+                            lines += 1;
+                            match writer.write_line(&line) {
+                                Ok(id) if prev.is_some() => ids.push(id),
+                                Ok(_) => (),
+                                Err(e) => {
+                                    errors += 1;
+                                    quarantine.add(gz_path, &e, &line)?;
+                                }
+                            }
                         }
+                        updated.lock().unwrap().extend(ids.into_iter().map(|id| (id, shard)));
+                        quarantine.count(lines, errors);
+                        Ok(())
+                    })?;
+                    let updated = updated.into_inner().unwrap();
+                    if !updated.is_empty() {
+                        append_superseded(&out_dir, &updated)?;
                     }
-                    Ok(())
+                    state.advance(&gz_files, prev.is_none()).write(&out_dir)?;
+                    quarantine.finish(slug)
             }
         })*
 
@@ -142,16 +277,17 @@ macro_rules! create_complex_writers {
     };
 }
 macro_rules! macwrite {
-    ($inp:ident, $outp:ident, $($modname:ident),*) => {
-        $(
-            $modname::write($inp, $outp)?;
-        )*
+    ($inp:ident, $outp:ident, $update:ident, $($modname:ident),*) => {
+        vec![$(
+            $modname::write($inp, $outp, $update)?,
+        )*]
     };
 }
 
 create_complex_writers!(
     Source - sources;;;,
     Publisher - publishers;;;,
+    Funder - funders;;;,
     Author - authors;; summary_stats -> SummaryStats;,
     Topic - topics;;;,
     FieldLike - fields;;;,
@@ -162,18 +298,196 @@ create_complex_writers!(
     Work - works;
     topics => WorkTopic &
     locations => Location &
-    authorships => Authorship;
+    authorships => Authorship &
+    grants => Grant;
     biblio -> Biblio &
     open_access -> OpenAccess;
     referenced_works
 );
 
-fn get_writer(root: &Path, fname: &str) -> io::Result<GzWriter> {
-    let file_csv = File::create(root.join(fname).with_extension("csv.gz"))?;
+fn get_writer(root: &Path, fname: &str, shard: usize) -> io::Result<GzWriter> {
+    let shard_dir = root.join(fname);
+    create_dir_all(&shard_dir)?;
+    let file_csv = File::create(shard_dir.join(shard_name(shard)))?;
     let gz_encoder = GzEncoder::new(BufWriter::new(file_csv), Compression::default());
     return Ok(Writer::from_writer(gz_encoder));
 }
 
+///runs the closure on every snapshot partition, each worker writing its own shard of the tables
+fn write_partitions<F>(slug: &str, gz_files: &[PathBuf], write_one: F) -> io::Result<()>
+where
+    F: Fn(usize, &Path) -> io::Result<()> + Sync,
+{
+    let n_workers = thread::available_parallelism()
+        .map(|e| e.get())
+        .unwrap_or(1)
+        .min(gz_files.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..n_workers {
+            let tx = tx.clone();
+            let (next, write_one) = (&next, &write_one);
+            s.spawn(move || loop {
+                let shard = next.fetch_add(1, Ordering::Relaxed);
+                if shard >= gz_files.len() {
+                    break;
+                }
+                let res = write_one(shard, &gz_files[shard])
+                    .map_err(|e| io::Error::new(e.kind(), format!("{:?}: {e}", gz_files[shard])));
+                if tx.send(res).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        rx.iter()
+            .tqdm()
+            .desc(Some(slug))
+            .collect::<io::Result<()>>()
+    })
+}
+
+impl Quarantine {
+    fn new(out_dir: &Path, append: bool) -> Self {
+        Self {
+            path: out_dir.join(QUARANTINE_FILE),
+            append,
+            out: Mutex::new(None),
+            lines: AtomicUsize::new(0),
+            errors: AtomicUsize::new(0),
+        }
+    }
+
+    fn add(&self, partition: &Path, error: &str, line: &str) -> io::Result<()> {
+        let mut out = self.out.lock().unwrap();
+        if out.is_none() {
+            //appended runs are separate gzip members, that zcat and MultiGzDecoder read on
+            let file = BufWriter::new(
+                OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .append(self.append)
+                    .truncate(!self.append)
+                    .open(&self.path)?,
+            );
+            *out = Some(GzEncoder::new(file, Compression::default()));
+        }
+        let w = out.as_mut().unwrap();
+        let rec = QuarantineRecord {
+            partition,
+            error,
+            line,
+        };
+        serde_json::to_writer(&mut *w, &rec)?;
+        w.write_all(b"\n")
+    }
+
+    fn count(&self, lines: usize, errors: usize) {
+        self.lines.fetch_add(lines, Ordering::Relaxed);
+        self.errors.fetch_add(errors, Ordering::Relaxed);
+    }
+
+    fn finish(self, entity: &'static str) -> io::Result<ParseStats> {
+        if let Some(w) = self.out.into_inner().unwrap() {
+            w.finish()?.flush()?;
+        }
+        Ok(ParseStats {
+            entity,
+            lines: self.lines.into_inner(),
+            errors: self.errors.into_inner(),
+            quarantine: self.path,
+        })
+    }
+}
+
+impl IngestState {
+    fn empty() -> Self {
+        let base = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Self {
+            base,
+            last_updated_date: String::new(),
+            next_shard: 0,
+        }
+    }
+
+    pub fn read(entity_dir: &Path) -> Option<Self> {
+        let file = File::open(entity_dir.join(INGEST_STATE_FILE)).ok()?;
+        serde_json::from_reader(BufReader::new(file)).ok()
+    }
+
+    fn write(&self, entity_dir: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(entity_dir.join(INGEST_STATE_FILE))?);
+        serde_json::to_writer_pretty(file, self).map_err(io::Error::from)
+    }
+
+    fn advance(mut self, gz_files: &[PathBuf], full: bool) -> Self {
+        if let Some(last) = gz_files.iter().map(|p| partition_date(p)).max() {
+            self.last_updated_date = self.last_updated_date.max(last.to_string());
+        }
+        //a full conversion of nothing still wrote the empty shard 0
+        let written = if full {
+            gz_files.len().max(1)
+        } else {
+            gz_files.len()
+        };
+        self.next_shard += written;
+        self
+    }
+}
+
+impl ParseStats {
+    pub fn rate(&self) -> f64 {
+        self.errors as f64 / self.lines.max(1) as f64
+    }
+}
+
+fn partition_date(path: &Path) -> &str {
+    path.iter()
+        .filter_map(|c| c.to_str()?.strip_prefix(PARTITION_PREFIX))
+        .last()
+        .unwrap_or("")
+}
+
+///records the newest shard of every updated entity, older records of it are skipped on read
+fn append_superseded(out_dir: &Path, updated: &[(String, usize)]) -> io::Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(out_dir.join(SUPERSEDED_FILE))?;
+    let mut w = BufWriter::new(file);
+    for (id, shard) in updated {
+        writeln!(w, "{id}\t{shard}")?;
+    }
+    w.flush()
+}
+
+///the merged id lists are small csvs already, kept as they are next to the tables of the entity
+fn copy_merged_ids(in_root: &Path, out_root: &Path, entity: &str) -> io::Result<()> {
+    let files = snapshot_partitions(&in_root.join(MERGED_IDS), entity)?;
+    let out_dir = out_root.join(entity).join(MERGED_IDS);
+    if out_dir.exists() {
+        remove_dir_all(&out_dir)?;
+    }
+    create_dir_all(&out_dir)?;
+    for (i, path) in files.iter().enumerate() {
+        copy(path, out_dir.join(shard_name(i)))?;
+    }
+    println!("{entity}: {} merged id lists", files.len());
+    Ok(())
+}
+
+pub fn shard_name(shard: usize) -> String {
+    format!("{shard:05}.csv.gz")
+}
+
+fn store_shard_name(shard: usize) -> String {
+    format!("{shard:05}.{STORE_EXTENSION}")
+}
+
 fn fill_with_files(path: &Path, v: &mut Vec<PathBuf>, extension: &str) -> io::Result<()> {
     if path.is_dir() {
         for entry in read_dir(path)? {
@@ -188,26 +502,46 @@ fn fill_with_files(path: &Path, v: &mut Vec<PathBuf>, extension: &str) -> io::Re
     Ok(())
 }
 
-fn deserialize_verbose<T: DeserializeOwned>(s: &str) -> T {
-    let deserializer = &mut serde_json::Deserializer::from_str(s);
+///stores the snapshot lines of the entity for reading without to-csv,
+///none for tables that are not in the snapshot, like the ones added next to the csvs
+pub fn storer(entity: &str, table: &str) -> Option<Storer> {
+    let (out, tables): (Storer, &[&str]) = match entity {
+        fields::C => (fields::store, fields::ModWriter::TABLES),
+        domains::C => (domains::store, domains::ModWriter::TABLES),
+        subfields::C => (subfields::store, subfields::ModWriter::TABLES),
+        topics::C => (topics::store, topics::ModWriter::TABLES),
+        institutions::C => (institutions::store, institutions::ModWriter::TABLES),
+        concepts::C => (concepts::store, concepts::ModWriter::TABLES),
+        works::C => (works::store, works::ModWriter::TABLES),
+        authors::C => (authors::store, authors::ModWriter::TABLES),
+        publishers::C => (publishers::store, publishers::ModWriter::TABLES),
+        funders::C => (funders::store, funders::ModWriter::TABLES),
+        sources::C => (sources::store, sources::ModWriter::TABLES),
+        _ => return None,
+    };
+    tables.contains(&table).then_some(out)
+}
 
-    let result: Result<T, _> = serde_path_to_error::deserialize(deserializer);
+pub fn snapshot_partitions(in_root: &Path, entity: &str) -> io::Result<Vec<PathBuf>> {
+    let mut gz_files: Vec<PathBuf> = vec![];
+    fill_with_files(&in_root.join(entity), &mut gz_files, "gz")?;
+    gz_files.sort();
+    Ok(gz_files)
+}
 
-    match result {
-        Ok(r) => return r,
-        Err(err) => {
-            println!("err: {:?}", err);
-            println!("s: {}", s);
-            panic!("verbose err: {}", err);
-        }
-    }
+fn deserialize_verbose<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+    let deserializer = &mut serde_json::Deserializer::from_str(s);
+    serde_path_to_error::deserialize(deserializer)
+        .map_err(|err| format!("{}: {}", err.path(), err.inner()))
 }
 
-pub fn write_csvs(in_root_str: &str, stowage: &Stowage) -> io::Result<()> {
+///with update set, only partitions newer than the recorded state of each entity are converted
+pub fn write_csvs(in_root_str: &str, stowage: &Stowage, update: bool) -> io::Result<()> {
     let out_root_str = &stowage.get_out_csv_path();
-    macwrite!(
+    let stats = macwrite!(
         in_root_str,
         out_root_str,
+        update,
         fields,
         domains,
         subfields,
@@ -217,7 +551,30 @@ pub fn write_csvs(in_root_str: &str, stowage: &Stowage) -> io::Result<()> {
         works,
         authors,
         publishers,
+        funders,
         sources
     );
+    copy_merged_ids(Path::new(in_root_str), Path::new(out_root_str), authors::C)?;
+    //only fails after everything is written, so a bit of schema drift does not lose the run
+    check_parse_stats(&stats, stowage.config.max_parse_error_rate)
+}
+
+pub fn check_parse_stats(stats: &[ParseStats], max_rate: f64) -> io::Result<()> {
+    let mut failed = Vec::new();
+    for st in stats.iter().filter(|e| e.errors > 0) {
+        println!(
+            "{}: {} of {} lines failed to parse, see {:?}",
+            st.entity, st.errors, st.lines, st.quarantine
+        );
+        if st.rate() > max_rate {
+            failed.push(st.entity);
+        }
+    }
+    if !failed.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("parse error rate above {max_rate} for {failed:?}"),
+        ));
+    }
     Ok(())
 }
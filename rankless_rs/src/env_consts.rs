@@ -1,6 +0,0 @@
-pub const FINAL_YEAR: u16 = 2025;
-pub const START_YEAR: u16 = 1950;
-pub const MIN_PAPERS_FOR_INST: u16 = 250;
-pub const MIN_PAPERS_FOR_SOURCE: u16 = 200;
-pub const MIN_AUTHOR_WORK_COUNT: u16 = 8;
-pub const MIN_AUTHOR_CITE_COUNT: u16 = 400;
\ No newline at end of file
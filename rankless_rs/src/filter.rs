@@ -1,22 +1,36 @@
-use std::io;
+use std::{
+    fs::{read_dir, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
 
 use hashbrown::{HashMap, HashSet};
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    common::{oa_id_parse, ParsedId, Stowage, MAIN_NAME},
+    common::{get_gz_buf, get_gz_bufw, oa_id_parse, ParsedId, Stowage, ID_PREFIX, MAIN_NAME},
+    config::{PipelineConfig, CONFIG_FILE},
     csv_writers::{authors, institutions, sources, works},
-    env_consts::{
-        FINAL_YEAR, MIN_AUTHOR_CITE_COUNT, MIN_AUTHOR_WORK_COUNT, MIN_PAPERS_FOR_INST,
-        MIN_PAPERS_FOR_SOURCE, START_YEAR,
-    },
     oa_structs::{
         post::{Author, Authorship, Institution, Location},
-        ReferencedWork, Work,
+        IdStruct, ReferencedWork, Work,
     },
 };
 
 use dmove::BigId;
+use flate2::write::GzEncoder;
+
+pub const SPEC_FILE: &str = "filter-spec.json";
+pub const REPORT_FILE: &str = "filter-report.txt";
+const DROPPED_SUFFIX: &str = "dropped.gz";
+const LABEL_FILE: &str = "step.txt";
+//id, reason, measured value, threshold
+const DROP_RECORD_SIZE: usize = 8 + 1 + 8 + 8;
+//round r of step s is written to filter-steps/<r * ROUND_STRIDE + s>
+const ROUND_STRIDE: u32 = 100;
+const STEP_IDS: std::ops::Range<u8> = 1..(ROUND_STRIDE as u8);
+const DEFAULT_MAX_ROUNDS: u32 = 10;
+const REPORTED_ENTITIES: [&str; 4] = [works::C, authors::C, institutions::C, sources::C];
 
 const MAX_AUTHORS: usize = 20;
 const MIN_CITATIONS: usize = 1;
@@ -28,22 +42,135 @@ const FIX_AUTHORS: [BigId; 6] = [
 
 const FORCE_DROP_INSTS: [BigId; 2] = [4210095297, 4210109586];
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FilterSpec {
+    pub steps: Vec<FilterStep>,
+    //the steps are repeated until no entity set changes, or this many rounds ran
+    #[serde(default = "default_max_rounds")]
+    pub max_rounds: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FilterStep {
+    ///keeps records of an entity by their own fields
+    Records {
+        step_id: u8,
+        entity: String,
+        //kept regardless of the other conditions
+        #[serde(default)]
+        allow: Vec<BigId>,
+        #[serde(default)]
+        deny: Vec<BigId>,
+        //only keep what passed the last filter of the entity
+        #[serde(default)]
+        prefiltered: bool,
+        #[serde(default)]
+        work_types: Vec<String>,
+        //start_year < year <= final_year from the pipeline config
+        #[serde(default)]
+        year_window: bool,
+        #[serde(default)]
+        drop_retracted: bool,
+        min_works: Option<Threshold>,
+        min_citations: Option<Threshold>,
+    },
+    ///keeps sources with an allowed number of targets along an edge, and optionally those targets
+    Edges {
+        step_id: u8,
+        edge: EdgeKind,
+        #[serde(default)]
+        min: Threshold,
+        max: Option<usize>,
+        filter_targets: bool,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    References,
+    Locations,
+    InstitutionAuthorships,
+    PersonAuthorships,
+}
+
+///a number, or the name of a threshold in the pipeline config
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Threshold {
+    Fixed(usize),
+    Config(String),
+}
+
 #[derive(Deserialize)]
 struct PersonAuthorship {
     author: String,
     parent_id: String,
 }
 
+#[derive(Clone, Copy, Debug)]
+enum DropReason {
+    Denied = 1,
+    WorkType,
+    YearWindow,
+    Retracted,
+    MinWorks,
+    MinCitations,
+    TooFewEdges,
+    TooManyEdges,
+    NotLinked,
+}
+
+//id, reason, measured value, threshold
+type DropRecord = (BigId, DropReason, u64, u64);
+
+enum Verdict {
+    Keep,
+    //already dropped by an earlier step
+    Skip,
+    Drop(DropReason, u64, u64),
+}
+
+///gzipped fixed size records of the ids a step dropped and why
+struct DropWriter {
+    out: BufWriter<GzEncoder<File>>,
+    count: usize,
+}
+
+struct StepReport {
+    round: u32,
+    label: String,
+    //entity, count before (None if unfiltered), count after
+    changes: Vec<(&'static str, Option<usize>, usize)>,
+}
+
 trait FilterBase {
     const ENTITY_C: &'static str = works::C;
     const ENTITY_ATT: &'static str;
-    const MIN: usize = 0;
-    const MAX: usize = usize::MAX;
-    const HAS_MAX: bool = false;
-    const FILTER_TARGETS: bool = true;
+    //source and target entity of the edges
+    const TYPES: [&'static str; 2];
     fn iter_edges(&self) -> Vec<[String; 2]>;
 }
 
+trait RecordBase: DeserializeOwned + ParsedId {
+    fn year(&self) -> Option<u16> {
+        None
+    }
+    fn work_type(&self) -> Option<&str> {
+        None
+    }
+    fn is_retracted(&self) -> bool {
+        false
+    }
+    fn works_count(&self) -> usize {
+        0
+    }
+    fn cited_by_count(&self) -> usize {
+        0
+    }
+}
+
 impl Authorship {
     pub fn iter_insts(&self) -> Vec<String> {
         if let Some(instids) = &self.institutions {
@@ -55,8 +182,7 @@ impl Authorship {
 
 impl FilterBase for Authorship {
     const ENTITY_ATT: &'static str = works::atts::authorships;
-    const MIN: usize = MIN_PAPERS_FOR_INST as usize;
-    const FILTER_TARGETS: bool = false;
+    const TYPES: [&'static str; 2] = [institutions::C, works::C];
 
     fn iter_edges(&self) -> Vec<[String; 2]> {
         self.iter_insts()
@@ -68,8 +194,7 @@ impl FilterBase for Authorship {
 
 impl FilterBase for ReferencedWork {
     const ENTITY_ATT: &'static str = works::atts::referenced_works;
-    const MIN: usize = MIN_CITATIONS;
-    const FILTER_TARGETS: bool = false;
+    const TYPES: [&'static str; 2] = [works::C, works::C];
 
     fn iter_edges(&self) -> Vec<[String; 2]> {
         let pid = self.parent_id.clone().unwrap();
@@ -79,7 +204,7 @@ impl FilterBase for ReferencedWork {
 
 impl FilterBase for Location {
     const ENTITY_ATT: &'static str = works::atts::locations;
-    const MIN: usize = MIN_PAPERS_FOR_SOURCE as usize;
+    const TYPES: [&'static str; 2] = [sources::C, works::C];
 
     fn iter_edges(&self) -> Vec<[String; 2]> {
         match &self.source_id {
@@ -91,9 +216,7 @@ impl FilterBase for Location {
 
 impl FilterBase for PersonAuthorship {
     const ENTITY_ATT: &'static str = works::atts::authorships;
-    const MAX: usize = MAX_AUTHORS;
-    const HAS_MAX: bool = true;
-    const FILTER_TARGETS: bool = true;
+    const TYPES: [&'static str; 2] = [works::C, authors::C];
 
     fn iter_edges(&self) -> Vec<[String; 2]> {
         if self.author.len() > 0 {
@@ -104,62 +227,562 @@ impl FilterBase for PersonAuthorship {
     }
 }
 
+impl DropReason {
+    const ALL: [Self; 9] = [
+        Self::Denied,
+        Self::WorkType,
+        Self::YearWindow,
+        Self::Retracted,
+        Self::MinWorks,
+        Self::MinCitations,
+        Self::TooFewEdges,
+        Self::TooManyEdges,
+        Self::NotLinked,
+    ];
+
+    fn from_u8(v: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| *e as u8 == v)
+    }
+
+    fn describe(&self, measured: u64, threshold: u64) -> String {
+        match self {
+            Self::Denied => "on the deny list".to_string(),
+            Self::WorkType => "work type not allowed".to_string(),
+            Self::YearWindow => format!("year {measured} outside the window ending {threshold}"),
+            Self::Retracted => "retracted".to_string(),
+            Self::MinWorks => format!("works count {measured} < {threshold}"),
+            Self::MinCitations => format!("citation count {measured} < {threshold}"),
+            Self::TooFewEdges if measured == 0 => "no edges to kept entities".to_string(),
+            Self::TooFewEdges => format!("{measured} edges < {threshold}"),
+            Self::TooManyEdges => format!("{measured} edges > {threshold}"),
+            Self::NotLinked => "not linked to any kept source".to_string(),
+        }
+    }
+}
+
+impl DropWriter {
+    fn new(stowage: &Stowage, dir_id: u32, entity_type: &str) -> Self {
+        let path = stowage
+            .get_filter_dir(dir_id)
+            .join(format!("{entity_type}.{DROPPED_SUFFIX}"));
+        Self {
+            out: get_gz_bufw(path),
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, id: BigId, reason: DropReason, measured: u64, threshold: u64) {
+        let mut rec = [0; DROP_RECORD_SIZE];
+        rec[..8].copy_from_slice(&id.to_be_bytes());
+        rec[8] = reason as u8;
+        rec[9..17].copy_from_slice(&measured.to_be_bytes());
+        rec[17..].copy_from_slice(&threshold.to_be_bytes());
+        self.out.write_all(&rec).unwrap();
+        self.count += 1;
+    }
+
+    fn finish(mut self) -> io::Result<usize> {
+        self.out.flush()?;
+        Ok(self.count)
+    }
+}
+
+impl RecordBase for Work {
+    fn year(&self) -> Option<u16> {
+        self.publication_year
+    }
+
+    fn work_type(&self) -> Option<&str> {
+        self.work_type.as_deref()
+    }
+
+    fn is_retracted(&self) -> bool {
+        self.is_retracted.unwrap_or(false)
+    }
+}
+
+impl RecordBase for Author {
+    fn works_count(&self) -> usize {
+        self.works_count.unwrap_or(0) as usize
+    }
+
+    fn cited_by_count(&self) -> usize {
+        self.cited_by_count.unwrap_or(0) as usize
+    }
+}
+
+impl RecordBase for Institution {}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+impl Threshold {
+    fn resolve(&self, config: &PipelineConfig) -> io::Result<usize> {
+        match self {
+            Self::Fixed(v) => Ok(*v),
+            Self::Config(name) => config.threshold(name).ok_or(invalid(format!(
+                "no threshold {name} in the pipeline config"
+            ))),
+        }
+    }
+}
+
+impl FilterStep {
+    pub fn step_id(&self) -> u8 {
+        match self {
+            Self::Records { step_id, .. } | Self::Edges { step_id, .. } => *step_id,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Records {
+                step_id, entity, ..
+            } => format!("{step_id} {entity} records"),
+            Self::Edges { step_id, edge, .. } => format!("{step_id} {edge:?} edges"),
+        }
+    }
+
+    fn entities(&self) -> Vec<&'static str> {
+        match self {
+            Self::Records { entity, .. } => REPORTED_ENTITIES
+                .into_iter()
+                .filter(|e| e == entity)
+                .collect(),
+            Self::Edges { edge, .. } => edge.types().to_vec(),
+        }
+    }
+}
+
+impl EdgeKind {
+    fn types(&self) -> [&'static str; 2] {
+        match self {
+            Self::References => ReferencedWork::TYPES,
+            Self::Locations => Location::TYPES,
+            Self::InstitutionAuthorships => Authorship::TYPES,
+            Self::PersonAuthorships => PersonAuthorship::TYPES,
+        }
+    }
+}
+
+impl FilterSpec {
+    ///reads the spec of the root, writing the default one if it is missing
+    pub fn load_or_init(root: &Path) -> io::Result<Self> {
+        let path = root.join(SPEC_FILE);
+        if path.exists() {
+            let spec: Self = serde_json::from_reader(BufReader::new(File::open(&path)?))
+                .map_err(|e| invalid(format!("{path:?}: {e}")))?;
+            return Ok(spec);
+        }
+        let spec = Self::default();
+        spec.write(&path)?;
+        println!("no {SPEC_FILE} in {root:?}, wrote the default one");
+        Ok(spec)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), self)
+            .map_err(io::Error::from)
+    }
+
+    pub fn validate(&self, config: &PipelineConfig) -> io::Result<()> {
+        let mut last_id = 0;
+        for step in self.steps.iter() {
+            let step_id = step.step_id();
+            if !STEP_IDS.contains(&step_id) || step_id <= last_id {
+                return Err(invalid(format!(
+                    "step ids must be increasing and in {STEP_IDS:?}, got {step_id} after {last_id}"
+                )));
+            }
+            last_id = step_id;
+            match step {
+                FilterStep::Records {
+                    entity,
+                    min_works,
+                    min_citations,
+                    ..
+                } => {
+                    if ![works::C, authors::C, institutions::C].contains(&entity.as_str()) {
+                        return Err(invalid(format!("can not filter records of {entity}")));
+                    }
+                    for t in [min_works, min_citations].into_iter().flatten() {
+                        t.resolve(config)?;
+                    }
+                }
+                FilterStep::Edges { min, .. } => {
+                    min.resolve(config)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for FilterSpec {
+    fn default() -> Self {
+        let conf = |name: &str| Threshold::Config(name.to_string());
+        let steps = vec![
+            FilterStep::Records {
+                step_id: 10,
+                entity: works::C.to_string(),
+                allow: Vec::new(),
+                deny: Vec::new(),
+                prefiltered: false,
+                work_types: WORK_KINDS.iter().map(|e| e.to_string()).collect(),
+                year_window: true,
+                drop_retracted: true,
+                min_works: None,
+                min_citations: None,
+            },
+            FilterStep::Edges {
+                step_id: 11,
+                edge: EdgeKind::References,
+                min: Threshold::Fixed(MIN_CITATIONS),
+                max: None,
+                filter_targets: false,
+            },
+            FilterStep::Edges {
+                step_id: 12,
+                edge: EdgeKind::Locations,
+                min: conf("min_papers_for_source"),
+                max: None,
+                filter_targets: true,
+            },
+            FilterStep::Edges {
+                step_id: 13,
+                edge: EdgeKind::InstitutionAuthorships,
+                min: conf("min_papers_for_inst"),
+                max: None,
+                filter_targets: false,
+            },
+            FilterStep::Edges {
+                step_id: 14,
+                edge: EdgeKind::PersonAuthorships,
+                min: Threshold::default(),
+                max: Some(MAX_AUTHORS),
+                filter_targets: true,
+            },
+            FilterStep::Records {
+                step_id: 20,
+                entity: authors::C.to_string(),
+                allow: FIX_AUTHORS.to_vec(),
+                deny: Vec::new(),
+                prefiltered: true,
+                work_types: Vec::new(),
+                year_window: false,
+                drop_retracted: false,
+                min_works: Some(conf("min_author_work_count")),
+                min_citations: Some(conf("min_author_cite_count")),
+            },
+            FilterStep::Records {
+                step_id: 21,
+                entity: institutions::C.to_string(),
+                allow: Vec::new(),
+                deny: FORCE_DROP_INSTS.to_vec(),
+                prefiltered: true,
+                work_types: Vec::new(),
+                year_window: false,
+                drop_retracted: false,
+                min_works: None,
+                min_citations: None,
+            },
+        ];
+        Self {
+            steps,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+        }
+    }
+}
+
+impl StepReport {
+    fn line(&self) -> String {
+        let changes: Vec<String> = self
+            .changes
+            .iter()
+            .map(|(e, before, after)| match before {
+                Some(b) => format!("{e}: -{} ({b} -> {after})", b.saturating_sub(*after)),
+                None => format!("{e}: kept {after}"),
+            })
+            .collect();
+        format!(
+            "round {} | {} | {}",
+            self.round,
+            self.label,
+            changes.join(", ")
+        )
+    }
+}
+
 pub fn main(stowage: Stowage) -> io::Result<()> {
-    single_filter(&stowage, 10)?;
-    filter_step::<ReferencedWork>(&stowage, [works::C, works::C], 11)?;
-    filter_step::<Location>(&stowage, [sources::C, works::C], 12)?;
-    filter_step::<Authorship>(&stowage, [institutions::C, works::C], 13)?;
-    filter_step::<PersonAuthorship>(&stowage, [works::C, authors::C], 14)?;
-    author_filter(&stowage, 20)?;
-    inst_filter(&stowage, 21)
-}
-
-fn single_filter(stowage: &Stowage, step_id: u8) -> io::Result<()> {
-    filter_write::<Work, _>(stowage, step_id, works::C, |o| {
-        !o.is_retracted.unwrap_or(false)
-            & WORK_KINDS.contains(&o.work_type.as_deref().unwrap_or(""))
-            & (o.publication_year.unwrap_or(0) > START_YEAR) // > because 0 is "unknown"
-            & (o.publication_year.unwrap_or(0) <= FINAL_YEAR)
-    })
+    let root = stowage.paths.entity_csvs.parent().unwrap().to_path_buf();
+    let spec = FilterSpec::load_or_init(&root)?;
+    spec.validate(&stowage.config)?;
+    let mut reports = Vec::new();
+    let mut converged = false;
+    for round in 0..spec.max_rounds {
+        let before = entity_counts(&stowage);
+        for step in spec.steps.iter() {
+            reports.push(run_step(&stowage, step, round)?);
+        }
+        //sets only shrink after the first round, so equal counts mean equal sets
+        if (round > 0) & (entity_counts(&stowage) == before) {
+            println!("filters converged after {} rounds", round + 1);
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        println!(
+            "WARNING: filters still changing after {} rounds",
+            spec.max_rounds
+        );
+    }
+    write_report(&stowage, &reports, converged)?;
+    spec.write(&stowage.paths.filter_steps.join(SPEC_FILE))?;
+    stowage
+        .config
+        .write(&stowage.paths.filter_steps.join(CONFIG_FILE))
 }
 
-fn author_filter(stowage: &Stowage, step_id: u8) -> io::Result<()> {
-    let pre_filter = stowage.get_last_filter(authors::C).unwrap();
-    filter_write::<Author, _>(stowage, step_id, authors::C, |o| {
-        let aid = o.get_parsed_id();
-        FIX_AUTHORS.contains(&aid)
-            | (pre_filter.contains(&aid)
-                & (o.cited_by_count.unwrap_or(0) >= MIN_AUTHOR_CITE_COUNT.into())
-                & (o.works_count.unwrap_or(0) >= MIN_AUTHOR_WORK_COUNT.into()))
-    })
+fn run_step(stowage: &Stowage, step: &FilterStep, round: u32) -> io::Result<StepReport> {
+    let dir_id = round * ROUND_STRIDE + step.step_id() as u32;
+    let entities = step.entities();
+    let before: Vec<Option<usize>> = entities
+        .iter()
+        .map(|e| stowage.last_filter_count(e))
+        .collect();
+    //later rounds can only narrow down what the previous ones kept
+    let prefilter = round > 0;
+    std::fs::write(
+        stowage.get_filter_dir(dir_id).join(LABEL_FILE),
+        format!("round {round} | {}", step.label()),
+    )?;
+    match step {
+        FilterStep::Records { entity, .. } => match entity.as_str() {
+            works::C => records_step::<Work>(stowage, step, dir_id, prefilter),
+            authors::C => records_step::<Author>(stowage, step, dir_id, prefilter),
+            institutions::C => records_step::<Institution>(stowage, step, dir_id, prefilter),
+            _ => Err(invalid(format!("can not filter records of {entity}"))),
+        },
+        FilterStep::Edges { edge, .. } => match edge {
+            EdgeKind::References => filter_step::<ReferencedWork>(stowage, step, dir_id),
+            EdgeKind::Locations => filter_step::<Location>(stowage, step, dir_id),
+            EdgeKind::InstitutionAuthorships => filter_step::<Authorship>(stowage, step, dir_id),
+            EdgeKind::PersonAuthorships => filter_step::<PersonAuthorship>(stowage, step, dir_id),
+        },
+    }?;
+    let changes = entities
+        .into_iter()
+        .zip(before)
+        .map(|(e, b)| (e, b, stowage.last_filter_count(e).unwrap_or(0)))
+        .collect();
+    let report = StepReport {
+        round,
+        label: step.label(),
+        changes,
+    };
+    println!("{}", report.line());
+    Ok(report)
+}
+
+fn entity_counts(stowage: &Stowage) -> Vec<Option<usize>> {
+    REPORTED_ENTITIES
+        .iter()
+        .map(|e| stowage.last_filter_count(e))
+        .collect()
+}
+
+fn write_report(stowage: &Stowage, reports: &[StepReport], converged: bool) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(stowage.paths.filter_steps.join(REPORT_FILE))?);
+    for r in reports.iter() {
+        writeln!(file, "{}", r.line())?;
+    }
+    let rounds = reports.last().map(|r| r.round + 1).unwrap_or(0);
+    writeln!(file, "rounds: {rounds}, converged: {converged}")?;
+    for e in REPORTED_ENTITIES {
+        if let Some(c) = stowage.last_filter_count(e) {
+            writeln!(file, "final {e}: {c}")?;
+        }
+    }
+    file.flush()
 }
 
-fn inst_filter(stowage: &Stowage, step_id: u8) -> io::Result<()> {
-    let pre_filter = stowage.get_last_filter(institutions::C).unwrap();
-    filter_write::<Institution, _>(stowage, step_id, institutions::C, |o| {
-        let iid = o.get_parsed_id();
-        !FORCE_DROP_INSTS.contains(&iid) && pre_filter.contains(&iid)
+fn default_max_rounds() -> u32 {
+    DEFAULT_MAX_ROUNDS
+}
+
+fn records_step<T: RecordBase>(
+    stowage: &Stowage,
+    step: &FilterStep,
+    dir_id: u32,
+    force_prefilter: bool,
+) -> io::Result<()> {
+    let FilterStep::Records {
+        step_id,
+        entity,
+        allow,
+        deny,
+        prefiltered,
+        work_types,
+        year_window,
+        drop_retracted,
+        min_works,
+        min_citations,
+    } = step
+    else {
+        unreachable!()
+    };
+    let conf = &stowage.config;
+    let pre_filter = match *prefiltered | force_prefilter {
+        true => stowage.get_last_filter(entity),
+        false => None,
+    };
+    let resolve = |t: &Option<Threshold>| t.as_ref().map(|e| e.resolve(conf)).unwrap_or(Ok(0));
+    let (min_works, min_citations) = (resolve(min_works)?, resolve(min_citations)?);
+    println!("filtering {step_id} - {entity} records");
+    filter_write::<T, _>(stowage, dir_id, entity, |o| {
+        let id = o.get_parsed_id();
+        let year = o.year().unwrap_or(0);
+        let in_window = (year > conf.start_year) & (year <= conf.final_year); // > because 0 is "unknown"
+        let (works_count, cite_count) = (o.works_count(), o.cited_by_count());
+        if deny.contains(&id) {
+            Verdict::Drop(DropReason::Denied, 0, 0)
+        } else if allow.contains(&id) {
+            Verdict::Keep
+        } else if !pre_filter.as_ref().map(|f| f.contains(&id)).unwrap_or(true) {
+            Verdict::Skip
+        } else if !work_types.is_empty()
+            & !work_types.iter().any(|e| Some(e.as_str()) == o.work_type())
+        {
+            Verdict::Drop(DropReason::WorkType, 0, 0)
+        } else if *year_window & !in_window {
+            Verdict::Drop(DropReason::YearWindow, year as u64, conf.final_year as u64)
+        } else if *drop_retracted & o.is_retracted() {
+            Verdict::Drop(DropReason::Retracted, 0, 0)
+        } else if works_count < min_works {
+            Verdict::Drop(DropReason::MinWorks, works_count as u64, min_works as u64)
+        } else if cite_count < min_citations {
+            Verdict::Drop(
+                DropReason::MinCitations,
+                cite_count as u64,
+                min_citations as u64,
+            )
+        } else {
+            Verdict::Keep
+        }
     })
 }
 
 fn filter_write<T, F>(
     stowage: &Stowage,
-    step_id: u8,
+    dir_id: u32,
     entity_type: &str,
     closure: F,
 ) -> io::Result<()>
 where
     T: for<'de> Deserialize<'de> + ParsedId,
-    F: Fn(&T) -> bool,
+    F: Fn(&T) -> Verdict,
 {
+    let mut drops = DropWriter::new(stowage, dir_id, entity_type);
     stowage.write_filter(
-        step_id,
+        dir_id,
         entity_type,
         stowage
             .read_csv_objs::<T>(entity_type, MAIN_NAME)
-            .filter(|o| closure(&o))
-            .map(|o| o.get_parsed_id()),
-    )
+            .filter_map(|o| match closure(&o) {
+                Verdict::Keep => Some(o.get_parsed_id()),
+                Verdict::Skip => None,
+                Verdict::Drop(reason, measured, threshold) => {
+                    drops.add(o.get_parsed_id(), reason, measured, threshold);
+                    None
+                }
+            }),
+    )?;
+    drops.finish()?;
+    Ok(())
+}
+
+///prints the filter steps an OpenAlex id went through and why it was dropped
+pub fn history(stowage: &Stowage, oa_id: &str) -> io::Result<()> {
+    let short = oa_id.trim_start_matches(ID_PREFIX);
+    let entity = match short.chars().next() {
+        Some('W') => works::C,
+        Some('A') => authors::C,
+        Some('I') => institutions::C,
+        Some('S') => sources::C,
+        _ => {
+            return Err(invalid(format!(
+                "{oa_id} is not a work, author, institution or source id"
+            )))
+        }
+    };
+    let id: BigId = short[1..]
+        .parse()
+        .map_err(|_| invalid(format!("can not parse {oa_id}")))?;
+
+    let mut dirs: Vec<(u32, PathBuf)> = read_dir(&stowage.paths.filter_steps)?
+        .map(|e| e.unwrap().path())
+        .filter_map(|p| Some((p.file_name()?.to_str()?.parse().ok()?, p)))
+        .collect();
+    dirs.sort();
+    println!("{short} ({entity})");
+    let mut last_state = "never filtered".to_string();
+    for (_, dir) in dirs {
+        let kept_path = dir.join(entity);
+        if !kept_path.exists() {
+            continue;
+        }
+        let label = std::fs::read_to_string(dir.join(LABEL_FILE)).unwrap_or_default();
+        let state =
+            if let Some(reason) = find_drop(&dir.join(format!("{entity}.{DROPPED_SUFFIX}")), id)? {
+                format!("dropped: {reason}")
+            } else if contains_id(&kept_path, id)? {
+                "kept".to_string()
+            } else {
+                //neither kept nor dropped here, so it was gone before this step
+                continue;
+            };
+        println!("  {label}: {state}");
+        last_state = state;
+    }
+    println!("final: {last_state}");
+    Ok(())
+}
+
+fn find_drop(path: &Path, id: BigId) -> io::Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut br = get_gz_buf(path)?;
+    let mut rec = [0; DROP_RECORD_SIZE];
+    let id_bytes = id.to_be_bytes();
+    while br.read_exact(&mut rec).is_ok() {
+        if rec[..8] == id_bytes {
+            let reason = DropReason::from_u8(rec[8]).ok_or(invalid(format!(
+                "unknown drop reason {} in {path:?}",
+                rec[8]
+            )))?;
+            let measured = u64::from_be_bytes(rec[9..17].try_into().unwrap());
+            let threshold = u64::from_be_bytes(rec[17..].try_into().unwrap());
+            return Ok(Some(reason.describe(measured, threshold)));
+        }
+    }
+    Ok(None)
+}
+
+fn contains_id(path: &Path, id: BigId) -> io::Result<bool> {
+    let mut br = BufReader::new(File::open(path)?);
+    let mut buf = [0; std::mem::size_of::<BigId>()];
+    let id_bytes = id.to_be_bytes();
+    while br.read_exact(&mut buf).is_ok() {
+        if buf == id_bytes {
+            return Ok(true);
+        }
+    }
+    Ok(false)
 }
 
 fn olen<T>(o: &Option<HashSet<T>>) -> String {
@@ -169,10 +792,24 @@ fn olen<T>(o: &Option<HashSet<T>>) -> String {
     }
 }
 
-fn filter_step<T>(stowage: &Stowage, types: [&'static str; 2], step_id: u8) -> io::Result<()>
+fn filter_step<T>(stowage: &Stowage, step: &FilterStep, dir_id: u32) -> io::Result<()>
 where
     T: FilterBase + DeserializeOwned,
 {
+    let FilterStep::Edges {
+        step_id,
+        min,
+        max,
+        filter_targets,
+        ..
+    } = step
+    else {
+        unreachable!()
+    };
+    let filter_targets = *filter_targets;
+    let min = min.resolve(&stowage.config)?;
+    let max = max.unwrap_or(usize::MAX);
+    let types = T::TYPES;
     let [source_type, target_type] = types;
     let [source_set_o, target_set_o] = types.map(|t| stowage.get_last_filter(t));
 
@@ -185,39 +822,151 @@ where
         olen(&target_set_o),
     );
 
-    let mut source_map: HashMap<u64, HashSet<u64>> = HashMap::new();
-
-    for rec in stowage.read_csv_objs::<T>(T::ENTITY_C, T::ENTITY_ATT) {
-        'endloop: for ends in rec.iter_edges().into_iter() {
-            let [source_key, target_key] = ends.map(|e| oa_id_parse(&e));
-            for (seto, key) in [(&source_set_o, &source_key), (&target_set_o, &target_key)] {
-                if let Some(set) = seto {
-                    if !set.contains(key) {
-                        //prefiltered out;
-                        continue 'endloop;
-                    }
-                }
-            }
-            let set_entry = source_map.entry(source_key).or_insert_with(HashSet::new);
-            if T::FILTER_TARGETS | (set_entry.len() < T::MIN) | T::HAS_MAX {
-                set_entry.insert(target_key);
-            }
-        }
+    let edges = stowage
+        .read_csv_objs::<T>(T::ENTITY_C, T::ENTITY_ATT)
+        .flat_map(|rec| rec.iter_edges())
+        .map(|ends| ends.map(|e| oa_id_parse(&e)));
+    let keep_all_targets = filter_targets | (max < usize::MAX);
+    let sets = [&source_set_o, &target_set_o];
+    let source_map = source_targets(edges, sets, keep_all_targets, min);
+    //sources with no edge left are dropped too, so every candidate gets a record
+    let source_candidates = candidate_ids(stowage, source_type, source_set_o);
+    let (taken_sources, drops) = judge_sources(&source_map, source_candidates, min, max);
+    let mut source_drops = DropWriter::new(stowage, dir_id, source_type);
+    for (k, reason, measured, threshold) in drops {
+        source_drops.add(k, reason, measured, threshold);
     }
-    let mut taken_sources = Vec::new();
+    source_drops.finish()?;
     let mut taken_targets: HashSet<u64> = HashSet::new();
-    for (k, v) in source_map.iter() {
-        if (v.len() >= T::MIN) && (v.len() <= T::MAX) {
-            taken_sources.push(*k);
-            if T::FILTER_TARGETS {
-                taken_targets.extend(v);
+    if filter_targets {
+        for k in taken_sources.iter() {
+            taken_targets.extend(source_map[k].iter());
+        }
+    }
+    //a step over edges within one entity only keeps one list of it
+    if filter_targets & (source_type != target_type) {
+        let mut target_drops = DropWriter::new(stowage, dir_id, target_type);
+        for k in candidate_ids(stowage, target_type, target_set_o) {
+            if !taken_targets.contains(&k) {
+                target_drops.add(k, DropReason::NotLinked, 0, 0);
             }
         }
+        target_drops.finish()?;
     }
 
-    if T::FILTER_TARGETS {
-        stowage.write_filter(step_id, target_type, &mut taken_targets.into_iter())?;
+    if filter_targets {
+        stowage.write_filter(dir_id, target_type, &mut taken_targets.into_iter())?;
     }
-    stowage.write_filter(step_id, source_type, &mut taken_sources.into_iter())?;
+    stowage.write_filter(dir_id, source_type, &mut taken_sources.into_iter())?;
     Ok(())
 }
+
+///targets of the sources on edges with both ends in their prefilter, only the first min
+///targets are kept unless all are needed
+fn source_targets<I>(
+    edges: I,
+    sets: [&Option<HashSet<BigId>>; 2],
+    keep_all_targets: bool,
+    min: usize,
+) -> HashMap<BigId, HashSet<BigId>>
+where
+    I: Iterator<Item = [BigId; 2]>,
+{
+    let mut source_map: HashMap<BigId, HashSet<BigId>> = HashMap::new();
+    for ends in edges {
+        let prefiltered_out = sets
+            .iter()
+            .zip(ends.iter())
+            .any(|(seto, key)| seto.as_ref().map(|set| !set.contains(key)).unwrap_or(false));
+        if prefiltered_out {
+            continue;
+        }
+        let [source_key, target_key] = ends;
+        let set_entry = source_map.entry(source_key).or_default();
+        if keep_all_targets | (set_entry.len() < min) {
+            set_entry.insert(target_key);
+        }
+    }
+    source_map
+}
+
+///sources kept by their number of targets, and the drops of the candidates not kept
+fn judge_sources<I>(
+    source_map: &HashMap<BigId, HashSet<BigId>>,
+    candidates: I,
+    min: usize,
+    max: usize,
+) -> (Vec<BigId>, Vec<DropRecord>)
+where
+    I: Iterator<Item = BigId>,
+{
+    let mut taken = Vec::new();
+    let mut drops = Vec::new();
+    for (k, v) in source_map.iter() {
+        if (v.len() >= min) && (v.len() <= max) {
+            taken.push(*k);
+        } else if v.len() < min {
+            drops.push((*k, DropReason::TooFewEdges, v.len() as u64, min as u64));
+        } else {
+            drops.push((*k, DropReason::TooManyEdges, v.len() as u64, max as u64));
+        }
+    }
+    for k in candidates.filter(|k| !source_map.contains_key(k)) {
+        drops.push((k, DropReason::TooFewEdges, 0, min as u64));
+    }
+    (taken, drops)
+}
+
+///ids an entity has going into a step, its last filter or all of its records
+fn candidate_ids<'a>(
+    stowage: &'a Stowage,
+    entity_type: &str,
+    last_filter: Option<HashSet<BigId>>,
+) -> Box<dyn Iterator<Item = BigId> + 'a> {
+    match last_filter {
+        Some(set) => Box::new(set.into_iter()),
+        None => Box::new(
+            stowage
+                .read_csv_objs::<IdStruct>(entity_type, MAIN_NAME)
+                .map(|e| e.get_parsed_id()),
+        ),
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_without_edges_are_recorded() {
+        //sources 1-4 linked to works 10-13, work 13 was dropped earlier
+        let edges = [[1, 10], [1, 11], [1, 12], [2, 10], [3, 13], [1, 10]];
+        let works: HashSet<BigId> = [10, 11, 12].into_iter().collect();
+        let sets = [&None, &Some(works)];
+        let source_map = source_targets(edges.into_iter(), sets, false, 2);
+        assert_eq!(source_map[&1].len(), 2);
+        assert!(!source_map.contains_key(&3));
+
+        //round 0 has no prefilter of sources, all of them are candidates
+        let (taken, mut drops) = judge_sources(&source_map, 1..=4, 2, usize::MAX);
+        assert_eq!(taken, vec![1]);
+        drops.sort_by_key(|e| e.0);
+        let drops: Vec<(BigId, u8, u64)> = drops.iter().map(|e| (e.0, e.1 as u8, e.2)).collect();
+        let few = DropReason::TooFewEdges as u8;
+        assert_eq!(drops, vec![(2, few, 1), (3, few, 0), (4, few, 0)]);
+
+        let source_map = source_targets(edges.into_iter(), sets, true, 2);
+        let (taken, drops) = judge_sources(&source_map, 1..=4, 0, 2);
+        assert_eq!(taken.len(), 1);
+        let too_many: Vec<(BigId, u8, u64)> = drops
+            .iter()
+            .filter(|e| e.2 > 0)
+            .map(|e| (e.0, e.1 as u8, e.2))
+            .collect();
+        assert_eq!(too_many, vec![(1, DropReason::TooManyEdges as u8, 3)]);
+    }
+}
@@ -14,6 +14,10 @@ pub struct Qs { }
 
 pub struct Sources { }
 
+pub struct Publishers { }
+
+pub struct Funders { }
+
 pub struct Topics { }
 
 pub struct Institutions { }
@@ -52,6 +56,18 @@ impl MappableEntity for Sources { type KeyType = u64; }
 
 impl NamespacedEntity for Sources { const NS: & str = "a1_entity_mapping"; }
 
+impl Entity for Publishers { type T = u16; const N: usize = 7112; const NAME: & str = "publishers"; }
+
+impl MappableEntity for Publishers { type KeyType = u64; }
+
+impl NamespacedEntity for Publishers { const NS: & str = "a1_entity_mapping"; }
+
+impl Entity for Funders { type T = u16; const N: usize = 32437; const NAME: & str = "funders"; }
+
+impl MappableEntity for Funders { type KeyType = u64; }
+
+impl NamespacedEntity for Funders { const NS: & str = "a1_entity_mapping"; }
+
 impl Entity for AreaFields { type T = u8; const N: usize = 2; const NAME: & str = "area-fields"; }
 
 impl MappableEntity for AreaFields { type KeyType = u64; }
@@ -42,16 +42,34 @@ pub struct SubfieldsNames { }
 
 pub struct WorkDois { }
 
-pub struct SourceYearQs { }
+pub struct SourceYearExternalQs { }
 
 pub struct AuthorshipAuthor { }
 
 pub struct SourcesNames { }
 
+pub struct PublishersNames { }
+
+pub struct PublishersNameExts { }
+
+pub struct SourcePublishers { }
+
+pub struct PublisherParents { }
+
+pub struct FundersNames { }
+
+pub struct FundersNameExts { }
+
+pub struct WorkFunders { }
+
 pub struct WorkTopics { }
 
 pub struct SubfieldAncestors { }
 
+pub struct AuthorOrcids { }
+
+pub struct AuthorAlternativeNames { }
+
 impl Entity for AuthorshipAuthor { type T = u32; const N: usize = 276436341; const NAME: & str = "authorship-author"; }
 
 impl MappableEntity for AuthorshipAuthor { type KeyType = usize; }
@@ -198,6 +216,72 @@ impl NamespacedEntity for SourcesNameExts { const NS: & str = "a2_init_atts"; }
 
 impl MarkedAttribute<crate::common::NameExtensionMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesNameExts; }
 
+impl Entity for PublishersNames { type T = String; const N: usize = 7113; const NAME: & str = "publishers-names"; }
+
+impl MappableEntity for PublishersNames { type KeyType = usize; }
+
+impl VariableSizeAttribute for PublishersNames { type SizeType = u8; }
+
+impl NamespacedEntity for PublishersNames { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::NameMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersNames; }
+
+impl Entity for PublishersNameExts { type T = String; const N: usize = 7113; const NAME: & str = "publishers-name-exts"; }
+
+impl MappableEntity for PublishersNameExts { type KeyType = usize; }
+
+impl VariableSizeAttribute for PublishersNameExts { type SizeType = u16; }
+
+impl NamespacedEntity for PublishersNameExts { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::NameExtensionMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersNameExts; }
+
+impl Entity for SourcePublishers { type T = u16; const N: usize = 39074; const NAME: & str = "source-publishers"; }
+
+impl MappableEntity for SourcePublishers { type KeyType = usize; }
+
+impl NamespacedEntity for SourcePublishers { const NS: & str = "a2_init_atts"; }
+
+impl Link for SourcePublishers { type Source = crate::gen::a1_entity_mapping::Sources; type Target = crate::gen::a1_entity_mapping::Publishers; }
+
+impl Entity for PublisherParents { type T = u16; const N: usize = 7113; const NAME: & str = "publisher-parents"; }
+
+impl MappableEntity for PublisherParents { type KeyType = usize; }
+
+impl NamespacedEntity for PublisherParents { const NS: & str = "a2_init_atts"; }
+
+impl Link for PublisherParents { type Source = crate::gen::a1_entity_mapping::Publishers; type Target = crate::gen::a1_entity_mapping::Publishers; }
+
+impl Entity for FundersNames { type T = String; const N: usize = 32438; const NAME: & str = "funders-names"; }
+
+impl MappableEntity for FundersNames { type KeyType = usize; }
+
+impl VariableSizeAttribute for FundersNames { type SizeType = u8; }
+
+impl NamespacedEntity for FundersNames { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::NameMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersNames; }
+
+impl Entity for FundersNameExts { type T = String; const N: usize = 32438; const NAME: & str = "funders-name-exts"; }
+
+impl MappableEntity for FundersNameExts { type KeyType = usize; }
+
+impl VariableSizeAttribute for FundersNameExts { type SizeType = u16; }
+
+impl NamespacedEntity for FundersNameExts { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::NameExtensionMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersNameExts; }
+
+impl Entity for WorkFunders { type T = Box<[u16]>; const N: usize = 72804468; const NAME: & str = "work-funders"; }
+
+impl MappableEntity for WorkFunders { type KeyType = usize; }
+
+impl VariableSizeAttribute for WorkFunders { type SizeType = u8; }
+
+impl NamespacedEntity for WorkFunders { const NS: & str = "a2_init_atts"; }
+
+impl Link for WorkFunders { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Funders; }
+
 impl Entity for AuthorsNameExts { type T = String; const N: usize = 3882892; const NAME: & str = "authors-name-exts"; }
 
 impl MappableEntity for AuthorsNameExts { type KeyType = usize; }
@@ -228,11 +312,11 @@ impl NamespacedEntity for SubfieldsNameExts { const NS: & str = "a2_init_atts";
 
 impl MarkedAttribute<crate::common::NameExtensionMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsNameExts; }
 
-impl Entity for SourceYearQs { type T = u8; const N: usize = 1688250; const NAME: & str = "source-year-qs"; }
+impl Entity for SourceYearExternalQs { type T = u8; const N: usize = 1688250; const NAME: & str = "source-year-external-qs"; }
 
-impl MappableEntity for SourceYearQs { type KeyType = (u16, u8); }
+impl MappableEntity for SourceYearExternalQs { type KeyType = (u16, u8); }
 
-impl NamespacedEntity for SourceYearQs { const NS: & str = "a2_init_atts"; }
+impl NamespacedEntity for SourceYearExternalQs { const NS: & str = "a2_init_atts"; }
 
 impl Entity for InstCountries { type T = u8; const N: usize = 29650; const NAME: & str = "inst-countries"; }
 
@@ -296,4 +380,24 @@ impl VariableSizeAttribute for WorkTopics { type SizeType = u8; }
 
 impl NamespacedEntity for WorkTopics { const NS: & str = "a2_init_atts"; }
 
-impl Link for WorkTopics { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Topics; }
\ No newline at end of file
+impl Link for WorkTopics { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Topics; }
+
+impl Entity for AuthorOrcids { type T = String; const N: usize = 3882893; const NAME: & str = "author-orcids"; }
+
+impl MappableEntity for AuthorOrcids { type KeyType = usize; }
+
+impl VariableSizeAttribute for AuthorOrcids { type SizeType = u8; }
+
+impl NamespacedEntity for AuthorOrcids { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::OrcidMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorOrcids; }
+
+impl Entity for AuthorAlternativeNames { type T = Box<[String]>; const N: usize = 3882893; const NAME: & str = "author-alternative-names"; }
+
+impl MappableEntity for AuthorAlternativeNames { type KeyType = usize; }
+
+impl VariableSizeAttribute for AuthorAlternativeNames { type SizeType = u16; }
+
+impl NamespacedEntity for AuthorAlternativeNames { const NS: & str = "a2_init_atts"; }
+
+impl MarkedAttribute<crate::common::AlternativeNamesMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorAlternativeNames; }
\ No newline at end of file
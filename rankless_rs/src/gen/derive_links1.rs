@@ -12,6 +12,12 @@ pub struct TopicWorks { }
 
 pub struct WorkSubfields { }
 
+pub struct WorkPublishers { }
+
+pub struct FunderWorks { }
+
+pub struct SourceYearQs { }
+
 impl Entity for WorksCiting { type T = Box<[u32]>; const N: usize = 72804468; const NAME: & str = "works-citing"; }
 
 impl MappableEntity for WorksCiting { type KeyType = usize; }
@@ -48,6 +54,18 @@ impl Link for SourceWorks { type Source = crate::gen::a1_entity_mapping::Sources
 
 impl MarkedAttribute<crate::common::MainWorkMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourceWorks; }
 
+impl Entity for FunderWorks { type T = Box<[u32]>; const N: usize = 32438; const NAME: & str = "funder-works"; }
+
+impl MappableEntity for FunderWorks { type KeyType = usize; }
+
+impl VariableSizeAttribute for FunderWorks { type SizeType = u32; }
+
+impl NamespacedEntity for FunderWorks { const NS: & str = "derive_links1"; }
+
+impl Link for FunderWorks { type Source = crate::gen::a1_entity_mapping::Funders; type Target = crate::gen::a1_entity_mapping::Works; }
+
+impl MarkedAttribute<crate::common::MainWorkMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FunderWorks; }
+
 impl Entity for WorkSubfields { type T = Box<[u8]>; const N: usize = 72804468; const NAME: & str = "work-subfields"; }
 
 impl MappableEntity for WorkSubfields { type KeyType = usize; }
@@ -76,4 +94,20 @@ impl VariableSizeAttribute for WorkInstitutions { type SizeType = u8; }
 
 impl NamespacedEntity for WorkInstitutions { const NS: & str = "derive_links1"; }
 
-impl Link for WorkInstitutions { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Institutions; }
\ No newline at end of file
+impl Link for WorkInstitutions { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Institutions; }
+
+impl Entity for WorkPublishers { type T = Box<[u16]>; const N: usize = 72804468; const NAME: & str = "work-publishers"; }
+
+impl MappableEntity for WorkPublishers { type KeyType = usize; }
+
+impl VariableSizeAttribute for WorkPublishers { type SizeType = u8; }
+
+impl NamespacedEntity for WorkPublishers { const NS: & str = "derive_links1"; }
+
+impl Link for WorkPublishers { type Source = crate::gen::a1_entity_mapping::Works; type Target = crate::gen::a1_entity_mapping::Publishers; }
+
+impl Entity for SourceYearQs { type T = u8; const N: usize = 1688250; const NAME: & str = "source-year-qs"; }
+
+impl MappableEntity for SourceYearQs { type KeyType = (u16, u8); }
+
+impl NamespacedEntity for SourceYearQs { const NS: & str = "derive_links1"; }
\ No newline at end of file
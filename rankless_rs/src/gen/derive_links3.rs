@@ -14,6 +14,10 @@ pub struct HitPapers { }
 
 pub struct CountryWorks { }
 
+pub struct PublisherWorks { }
+
+pub struct FundersWorkCount { }
+
 impl Entity for SourcesWorkCount { type T = u32; const N: usize = 39074; const NAME: & str = "sources-work-count"; }
 
 impl MappableEntity for SourcesWorkCount { type KeyType = usize; }
@@ -22,6 +26,14 @@ impl NamespacedEntity for SourcesWorkCount { const NS: & str = "derive_links3";
 
 impl MarkedAttribute<crate::common::WorkCountMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesWorkCount; }
 
+impl Entity for FundersWorkCount { type T = u32; const N: usize = 32438; const NAME: & str = "funders-work-count"; }
+
+impl MappableEntity for FundersWorkCount { type KeyType = usize; }
+
+impl NamespacedEntity for FundersWorkCount { const NS: & str = "derive_links3"; }
+
+impl MarkedAttribute<crate::common::WorkCountMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersWorkCount; }
+
 impl Entity for InstitutionsWorkCount { type T = u32; const N: usize = 29650; const NAME: & str = "institutions-work-count"; }
 
 impl MappableEntity for InstitutionsWorkCount { type KeyType = usize; }
@@ -66,6 +78,18 @@ impl Link for CountryWorks { type Source = crate::gen::a1_entity_mapping::Countr
 
 impl MarkedAttribute<crate::common::MainWorkMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountryWorks; }
 
+impl Entity for PublisherWorks { type T = Box<[u32]>; const N: usize = 7113; const NAME: & str = "publisher-works"; }
+
+impl MappableEntity for PublisherWorks { type KeyType = usize; }
+
+impl VariableSizeAttribute for PublisherWorks { type SizeType = u32; }
+
+impl NamespacedEntity for PublisherWorks { const NS: & str = "derive_links3"; }
+
+impl Link for PublisherWorks { type Source = crate::gen::a1_entity_mapping::Publishers; type Target = crate::gen::a1_entity_mapping::Works; }
+
+impl MarkedAttribute<crate::common::MainWorkMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublisherWorks; }
+
 impl Entity for HitPapers { type T = u16; const N: usize = 53606; const NAME: & str = "hit-papers"; }
 
 impl MappableEntity for HitPapers { type KeyType = u64; }
@@ -2,10 +2,20 @@ use dmove::{MarkedAttribute, Entity, MappableEntity, NamespacedEntity};
 
 pub struct CountriesWorkCount { }
 
+pub struct PublishersWorkCount { }
+
 impl Entity for CountriesWorkCount { type T = u32; const N: usize = 230; const NAME: & str = "countries-work-count"; }
 
 impl MappableEntity for CountriesWorkCount { type KeyType = usize; }
 
 impl NamespacedEntity for CountriesWorkCount { const NS: & str = "derive_links4"; }
 
-impl MarkedAttribute<crate::common::WorkCountMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesWorkCount; }
\ No newline at end of file
+impl MarkedAttribute<crate::common::WorkCountMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesWorkCount; }
+
+impl Entity for PublishersWorkCount { type T = u32; const N: usize = 7113; const NAME: & str = "publishers-work-count"; }
+
+impl MappableEntity for PublishersWorkCount { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersWorkCount { const NS: & str = "derive_links4"; }
+
+impl MarkedAttribute<crate::common::WorkCountMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersWorkCount; }
\ No newline at end of file
@@ -146,6 +146,58 @@ pub struct CountriesCitationsYearly { }
 
 pub struct CountriesRelInsts { }
 
+pub struct PublishersSemanticIds { }
+
+pub struct PublishersCiteCount { }
+
+pub struct PublishersCitSubfields { }
+
+pub struct PublishersRefSubfields { }
+
+pub struct PublishersPapersYearly { }
+
+pub struct PublishersCitationsYearly { }
+
+pub struct PublishersTopPaperSubfields { }
+
+pub struct PublishersTopCitingSubfields { }
+
+pub struct PublishersTopPaperTopics { }
+
+pub struct PublishersTopPaperAuthors { }
+
+pub struct PublishersTopJournals { }
+
+pub struct PublishersTopAffCountries { }
+
+pub struct PublishersRelInsts { }
+
+pub struct FundersSemanticIds { }
+
+pub struct FundersCiteCount { }
+
+pub struct FundersCitSubfields { }
+
+pub struct FundersRefSubfields { }
+
+pub struct FundersPapersYearly { }
+
+pub struct FundersCitationsYearly { }
+
+pub struct FundersTopPaperSubfields { }
+
+pub struct FundersTopCitingSubfields { }
+
+pub struct FundersTopPaperTopics { }
+
+pub struct FundersTopPaperAuthors { }
+
+pub struct FundersTopJournals { }
+
+pub struct FundersTopAffCountries { }
+
+pub struct FundersRelInsts { }
+
 pub struct InstitutionsRelInsts { }
 
 pub struct AuthorsCitSubfields { }
@@ -418,6 +470,218 @@ impl NamespacedEntity for CountriesRelInsts { const NS: & str = "derive_links5";
 
 impl MarkedAttribute<crate::common::InstRelMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesRelInsts; }
 
+impl Entity for PublishersSemanticIds { type T = String; const N: usize = 7113; const NAME: & str = "publishers-semantic-ids"; }
+
+impl MappableEntity for PublishersSemanticIds { type KeyType = usize; }
+
+impl VariableSizeAttribute for PublishersSemanticIds { type SizeType = u8; }
+
+impl NamespacedEntity for PublishersSemanticIds { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::SemanticIdMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersSemanticIds; }
+
+impl Entity for PublishersCiteCount { type T = u32; const N: usize = 7113; const NAME: & str = "publishers-cite-count"; }
+
+impl MappableEntity for PublishersCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersCiteCount { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::CiteCountMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersCiteCount; }
+
+impl Entity for PublishersCitSubfields { type T = [u32; 253]; const N: usize = 7113; const NAME: & str = "publishers-cit-subfields"; }
+
+impl MappableEntity for PublishersCitSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersCitSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsArrayMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersCitSubfields; }
+
+impl Entity for PublishersRefSubfields { type T = [u32; 253]; const N: usize = 7113; const NAME: & str = "publishers-ref-subfields"; }
+
+impl MappableEntity for PublishersRefSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersRefSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsArrayMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersRefSubfields; }
+
+impl Entity for PublishersPapersYearly { type T = [u32; 11]; const N: usize = 7113; const NAME: & str = "publishers-papers-yearly"; }
+
+impl MappableEntity for PublishersPapersYearly { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersPapersYearly { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::YearlyPapersMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersPapersYearly; }
+
+impl Entity for PublishersCitationsYearly { type T = [u32; 11]; const N: usize = 7113; const NAME: & str = "publishers-citations-yearly"; }
+
+impl MappableEntity for PublishersCitationsYearly { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersCitationsYearly { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::YearlyCitationsMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersCitationsYearly; }
+
+impl Entity for PublishersTopPaperSubfields { type T = [(u32, u8); 3]; const N: usize = 7113; const NAME: & str = "publishers-top-paper-subfields"; }
+
+impl MappableEntity for PublishersTopPaperSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopPaperSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3PaperSfMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopPaperSubfields; }
+
+impl Entity for PublishersTopCitingSubfields { type T = [(u32, u8); 3]; const N: usize = 7113; const NAME: & str = "publishers-top-citing-subfields"; }
+
+impl MappableEntity for PublishersTopCitingSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopCitingSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3CitingSfMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopCitingSubfields; }
+
+impl Entity for PublishersTopPaperTopics { type T = [(u32, u16); 3]; const N: usize = 7113; const NAME: & str = "publishers-top-paper-topics"; }
+
+impl MappableEntity for PublishersTopPaperTopics { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopPaperTopics { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3PaperTopicMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopPaperTopics; }
+
+impl Entity for PublishersTopPaperAuthors { type T = [(u32, u32); 5]; const N: usize = 7113; const NAME: & str = "publishers-top-paper-authors"; }
+
+impl MappableEntity for PublishersTopPaperAuthors { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopPaperAuthors { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3AuthorMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopPaperAuthors; }
+
+impl Entity for PublishersTopJournals { type T = [(u32, u16); 3]; const N: usize = 7113; const NAME: & str = "publishers-top-journals"; }
+
+impl MappableEntity for PublishersTopJournals { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopJournals { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3JournalMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopJournals; }
+
+impl Entity for PublishersTopAffCountries { type T = [(u32, u8); 3]; const N: usize = 7113; const NAME: & str = "publishers-top-aff-countries"; }
+
+impl MappableEntity for PublishersTopAffCountries { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopAffCountries { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3AffCountryMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopAffCountries; }
+
+impl Entity for PublishersRelInsts { type T = [crate::steps::derive_links5::InstRelation; 8]; const N: usize = 7113; const NAME: & str = "publishers-rel-insts"; }
+
+impl MappableEntity for PublishersRelInsts { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersRelInsts { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::InstRelMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersRelInsts; }
+
+impl Entity for FundersSemanticIds { type T = String; const N: usize = 32438; const NAME: & str = "funders-semantic-ids"; }
+
+impl MappableEntity for FundersSemanticIds { type KeyType = usize; }
+
+impl VariableSizeAttribute for FundersSemanticIds { type SizeType = u8; }
+
+impl NamespacedEntity for FundersSemanticIds { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::SemanticIdMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersSemanticIds; }
+
+impl Entity for FundersCiteCount { type T = u32; const N: usize = 32438; const NAME: & str = "funders-cite-count"; }
+
+impl MappableEntity for FundersCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for FundersCiteCount { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::CiteCountMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersCiteCount; }
+
+impl Entity for FundersCitSubfields { type T = [u32; 253]; const N: usize = 32438; const NAME: & str = "funders-cit-subfields"; }
+
+impl MappableEntity for FundersCitSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for FundersCitSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsArrayMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersCitSubfields; }
+
+impl Entity for FundersRefSubfields { type T = [u32; 253]; const N: usize = 32438; const NAME: & str = "funders-ref-subfields"; }
+
+impl MappableEntity for FundersRefSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for FundersRefSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsArrayMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersRefSubfields; }
+
+impl Entity for FundersPapersYearly { type T = [u32; 11]; const N: usize = 32438; const NAME: & str = "funders-papers-yearly"; }
+
+impl MappableEntity for FundersPapersYearly { type KeyType = usize; }
+
+impl NamespacedEntity for FundersPapersYearly { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::YearlyPapersMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersPapersYearly; }
+
+impl Entity for FundersCitationsYearly { type T = [u32; 11]; const N: usize = 32438; const NAME: & str = "funders-citations-yearly"; }
+
+impl MappableEntity for FundersCitationsYearly { type KeyType = usize; }
+
+impl NamespacedEntity for FundersCitationsYearly { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::YearlyCitationsMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersCitationsYearly; }
+
+impl Entity for FundersTopPaperSubfields { type T = [(u32, u8); 3]; const N: usize = 32438; const NAME: & str = "funders-top-paper-subfields"; }
+
+impl MappableEntity for FundersTopPaperSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopPaperSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3PaperSfMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopPaperSubfields; }
+
+impl Entity for FundersTopCitingSubfields { type T = [(u32, u8); 3]; const N: usize = 32438; const NAME: & str = "funders-top-citing-subfields"; }
+
+impl MappableEntity for FundersTopCitingSubfields { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopCitingSubfields { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3CitingSfMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopCitingSubfields; }
+
+impl Entity for FundersTopPaperTopics { type T = [(u32, u16); 3]; const N: usize = 32438; const NAME: & str = "funders-top-paper-topics"; }
+
+impl MappableEntity for FundersTopPaperTopics { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopPaperTopics { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3PaperTopicMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopPaperTopics; }
+
+impl Entity for FundersTopPaperAuthors { type T = [(u32, u32); 5]; const N: usize = 32438; const NAME: & str = "funders-top-paper-authors"; }
+
+impl MappableEntity for FundersTopPaperAuthors { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopPaperAuthors { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3AuthorMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopPaperAuthors; }
+
+impl Entity for FundersTopJournals { type T = [(u32, u16); 3]; const N: usize = 32438; const NAME: & str = "funders-top-journals"; }
+
+impl MappableEntity for FundersTopJournals { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopJournals { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3JournalMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopJournals; }
+
+impl Entity for FundersTopAffCountries { type T = [(u32, u8); 3]; const N: usize = 32438; const NAME: & str = "funders-top-aff-countries"; }
+
+impl MappableEntity for FundersTopAffCountries { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopAffCountries { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::Top3AffCountryMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopAffCountries; }
+
+impl Entity for FundersRelInsts { type T = [crate::steps::derive_links5::InstRelation; 8]; const N: usize = 32438; const NAME: & str = "funders-rel-insts"; }
+
+impl MappableEntity for FundersRelInsts { type KeyType = usize; }
+
+impl NamespacedEntity for FundersRelInsts { const NS: & str = "derive_links5"; }
+
+impl MarkedAttribute<crate::common::InstRelMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersRelInsts; }
+
 impl Entity for InstitutionsCiteCount { type T = u32; const N: usize = 29650; const NAME: & str = "institutions-cite-count"; }
 
 impl MappableEntity for InstitutionsCiteCount { type KeyType = usize; }
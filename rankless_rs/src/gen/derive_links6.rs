@@ -0,0 +1,311 @@
+use dmove::{MarkedAttribute, Entity, MappableEntity, NamespacedEntity};
+
+pub struct WorkNormalizedImpacts { }
+
+pub struct Work2yCitingCounts { }
+
+pub struct Work5yCitingCounts { }
+
+pub struct AuthorsNormalizedImpact { }
+
+pub struct AuthorsTopDecileShare { }
+
+pub struct Authors2yCiteCount { }
+
+pub struct Authors5yCiteCount { }
+
+pub struct InstitutionsNormalizedImpact { }
+
+pub struct InstitutionsTopDecileShare { }
+
+pub struct Institutions2yCiteCount { }
+
+pub struct Institutions5yCiteCount { }
+
+pub struct SourcesNormalizedImpact { }
+
+pub struct SourcesTopDecileShare { }
+
+pub struct Sources2yCiteCount { }
+
+pub struct Sources5yCiteCount { }
+
+pub struct SubfieldsNormalizedImpact { }
+
+pub struct SubfieldsTopDecileShare { }
+
+pub struct Subfields2yCiteCount { }
+
+pub struct Subfields5yCiteCount { }
+
+pub struct CountriesNormalizedImpact { }
+
+pub struct CountriesTopDecileShare { }
+
+pub struct Countries2yCiteCount { }
+
+pub struct Countries5yCiteCount { }
+
+pub struct PublishersNormalizedImpact { }
+
+pub struct PublishersTopDecileShare { }
+
+pub struct Publishers2yCiteCount { }
+
+pub struct Publishers5yCiteCount { }
+
+pub struct FundersNormalizedImpact { }
+
+pub struct FundersTopDecileShare { }
+
+pub struct Funders2yCiteCount { }
+
+pub struct Funders5yCiteCount { }
+
+impl Entity for WorkNormalizedImpacts { type T = f64; const N: usize = 72804468; const NAME: & str = "work-normalized-impacts"; }
+
+impl MappableEntity for WorkNormalizedImpacts { type KeyType = usize; }
+
+impl NamespacedEntity for WorkNormalizedImpacts { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Works { type AttributeEntity = WorkNormalizedImpacts; }
+
+impl Entity for Work2yCitingCounts { type T = u32; const N: usize = 72804468; const NAME: & str = "work-2y-citing-counts"; }
+
+impl MappableEntity for Work2yCitingCounts { type KeyType = usize; }
+
+impl NamespacedEntity for Work2yCitingCounts { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Works { type AttributeEntity = Work2yCitingCounts; }
+
+impl Entity for Work5yCitingCounts { type T = u32; const N: usize = 72804468; const NAME: & str = "work-5y-citing-counts"; }
+
+impl MappableEntity for Work5yCitingCounts { type KeyType = usize; }
+
+impl NamespacedEntity for Work5yCitingCounts { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Works { type AttributeEntity = Work5yCitingCounts; }
+
+impl Entity for AuthorsNormalizedImpact { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-normalized-impact"; }
+
+impl MappableEntity for AuthorsNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsNormalizedImpact; }
+
+impl Entity for AuthorsTopDecileShare { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-top-decile-share"; }
+
+impl MappableEntity for AuthorsTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsTopDecileShare; }
+
+impl Entity for Authors2yCiteCount { type T = u32; const N: usize = 3882893; const NAME: & str = "authors-2y-cite-count"; }
+
+impl MappableEntity for Authors2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Authors2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = Authors2yCiteCount; }
+
+impl Entity for Authors5yCiteCount { type T = u32; const N: usize = 3882893; const NAME: & str = "authors-5y-cite-count"; }
+
+impl MappableEntity for Authors5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Authors5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = Authors5yCiteCount; }
+
+impl Entity for InstitutionsNormalizedImpact { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-normalized-impact"; }
+
+impl MappableEntity for InstitutionsNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsNormalizedImpact; }
+
+impl Entity for InstitutionsTopDecileShare { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-top-decile-share"; }
+
+impl MappableEntity for InstitutionsTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsTopDecileShare; }
+
+impl Entity for Institutions2yCiteCount { type T = u32; const N: usize = 29650; const NAME: & str = "institutions-2y-cite-count"; }
+
+impl MappableEntity for Institutions2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Institutions2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = Institutions2yCiteCount; }
+
+impl Entity for Institutions5yCiteCount { type T = u32; const N: usize = 29650; const NAME: & str = "institutions-5y-cite-count"; }
+
+impl MappableEntity for Institutions5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Institutions5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = Institutions5yCiteCount; }
+
+impl Entity for SourcesNormalizedImpact { type T = f64; const N: usize = 39074; const NAME: & str = "sources-normalized-impact"; }
+
+impl MappableEntity for SourcesNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesNormalizedImpact; }
+
+impl Entity for SourcesTopDecileShare { type T = f64; const N: usize = 39074; const NAME: & str = "sources-top-decile-share"; }
+
+impl MappableEntity for SourcesTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesTopDecileShare; }
+
+impl Entity for Sources2yCiteCount { type T = u32; const N: usize = 39074; const NAME: & str = "sources-2y-cite-count"; }
+
+impl MappableEntity for Sources2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Sources2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = Sources2yCiteCount; }
+
+impl Entity for Sources5yCiteCount { type T = u32; const N: usize = 39074; const NAME: & str = "sources-5y-cite-count"; }
+
+impl MappableEntity for Sources5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Sources5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = Sources5yCiteCount; }
+
+impl Entity for SubfieldsNormalizedImpact { type T = f64; const N: usize = 254; const NAME: & str = "subfields-normalized-impact"; }
+
+impl MappableEntity for SubfieldsNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsNormalizedImpact; }
+
+impl Entity for SubfieldsTopDecileShare { type T = f64; const N: usize = 254; const NAME: & str = "subfields-top-decile-share"; }
+
+impl MappableEntity for SubfieldsTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsTopDecileShare; }
+
+impl Entity for Subfields2yCiteCount { type T = u32; const N: usize = 254; const NAME: & str = "subfields-2y-cite-count"; }
+
+impl MappableEntity for Subfields2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Subfields2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = Subfields2yCiteCount; }
+
+impl Entity for Subfields5yCiteCount { type T = u32; const N: usize = 254; const NAME: & str = "subfields-5y-cite-count"; }
+
+impl MappableEntity for Subfields5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Subfields5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = Subfields5yCiteCount; }
+
+impl Entity for CountriesNormalizedImpact { type T = f64; const N: usize = 230; const NAME: & str = "countries-normalized-impact"; }
+
+impl MappableEntity for CountriesNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesNormalizedImpact; }
+
+impl Entity for CountriesTopDecileShare { type T = f64; const N: usize = 230; const NAME: & str = "countries-top-decile-share"; }
+
+impl MappableEntity for CountriesTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesTopDecileShare; }
+
+impl Entity for Countries2yCiteCount { type T = u32; const N: usize = 230; const NAME: & str = "countries-2y-cite-count"; }
+
+impl MappableEntity for Countries2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Countries2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = Countries2yCiteCount; }
+
+impl Entity for Countries5yCiteCount { type T = u32; const N: usize = 230; const NAME: & str = "countries-5y-cite-count"; }
+
+impl MappableEntity for Countries5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Countries5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = Countries5yCiteCount; }
+
+impl Entity for PublishersNormalizedImpact { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-normalized-impact"; }
+
+impl MappableEntity for PublishersNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersNormalizedImpact; }
+
+impl Entity for PublishersTopDecileShare { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-top-decile-share"; }
+
+impl MappableEntity for PublishersTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersTopDecileShare; }
+
+impl Entity for Publishers2yCiteCount { type T = u32; const N: usize = 7113; const NAME: & str = "publishers-2y-cite-count"; }
+
+impl MappableEntity for Publishers2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Publishers2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = Publishers2yCiteCount; }
+
+impl Entity for Publishers5yCiteCount { type T = u32; const N: usize = 7113; const NAME: & str = "publishers-5y-cite-count"; }
+
+impl MappableEntity for Publishers5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Publishers5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = Publishers5yCiteCount; }
+
+impl Entity for FundersNormalizedImpact { type T = f64; const N: usize = 32438; const NAME: & str = "funders-normalized-impact"; }
+
+impl MappableEntity for FundersNormalizedImpact { type KeyType = usize; }
+
+impl NamespacedEntity for FundersNormalizedImpact { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::NormalizedImpactMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersNormalizedImpact; }
+
+impl Entity for FundersTopDecileShare { type T = f64; const N: usize = 32438; const NAME: & str = "funders-top-decile-share"; }
+
+impl MappableEntity for FundersTopDecileShare { type KeyType = usize; }
+
+impl NamespacedEntity for FundersTopDecileShare { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TopDecileShareMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersTopDecileShare; }
+
+impl Entity for Funders2yCiteCount { type T = u32; const N: usize = 32438; const NAME: & str = "funders-2y-cite-count"; }
+
+impl MappableEntity for Funders2yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Funders2yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::TwoYearCiteCountMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = Funders2yCiteCount; }
+
+impl Entity for Funders5yCiteCount { type T = u32; const N: usize = 32438; const NAME: & str = "funders-5y-cite-count"; }
+
+impl MappableEntity for Funders5yCiteCount { type KeyType = usize; }
+
+impl NamespacedEntity for Funders5yCiteCount { const NS: & str = "derive_links6"; }
+
+impl MarkedAttribute<crate::common::FiveYearCiteCountMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = Funders5yCiteCount; }
\ No newline at end of file
@@ -0,0 +1,81 @@
+use dmove::{MarkedAttribute, Entity, MappableEntity, NamespacedEntity};
+
+pub struct WorkPageranks { }
+
+pub struct AuthorsPagerank { }
+
+pub struct InstitutionsPagerank { }
+
+pub struct SourcesPagerank { }
+
+pub struct SubfieldsPagerank { }
+
+pub struct CountriesPagerank { }
+
+pub struct PublishersPagerank { }
+
+pub struct FundersPagerank { }
+
+impl Entity for WorkPageranks { type T = f64; const N: usize = 72804468; const NAME: & str = "work-pageranks"; }
+
+impl MappableEntity for WorkPageranks { type KeyType = usize; }
+
+impl NamespacedEntity for WorkPageranks { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Works { type AttributeEntity = WorkPageranks; }
+
+impl Entity for AuthorsPagerank { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-pagerank"; }
+
+impl MappableEntity for AuthorsPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsPagerank; }
+
+impl Entity for InstitutionsPagerank { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-pagerank"; }
+
+impl MappableEntity for InstitutionsPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsPagerank; }
+
+impl Entity for SourcesPagerank { type T = f64; const N: usize = 39074; const NAME: & str = "sources-pagerank"; }
+
+impl MappableEntity for SourcesPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesPagerank; }
+
+impl Entity for SubfieldsPagerank { type T = f64; const N: usize = 254; const NAME: & str = "subfields-pagerank"; }
+
+impl MappableEntity for SubfieldsPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsPagerank; }
+
+impl Entity for CountriesPagerank { type T = f64; const N: usize = 230; const NAME: & str = "countries-pagerank"; }
+
+impl MappableEntity for CountriesPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesPagerank; }
+
+impl Entity for PublishersPagerank { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-pagerank"; }
+
+impl MappableEntity for PublishersPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersPagerank; }
+
+impl Entity for FundersPagerank { type T = f64; const N: usize = 32438; const NAME: & str = "funders-pagerank"; }
+
+impl MappableEntity for FundersPagerank { type KeyType = usize; }
+
+impl NamespacedEntity for FundersPagerank { const NS: & str = "derive_links7"; }
+
+impl MarkedAttribute<crate::common::PageRankMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersPagerank; }
\ No newline at end of file
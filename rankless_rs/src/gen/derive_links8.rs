@@ -0,0 +1,133 @@
+use dmove::{MarkedAttribute, Entity, Link, VariableSizeAttribute, MappableEntity, NamespacedEntity};
+
+pub struct AuthorsCollabDegree { }
+
+pub struct AuthorsWeightedCollabDegree { }
+
+pub struct AuthorsClustering { }
+
+pub struct AuthorsCollabComponent { }
+
+pub struct AuthorsCollaborators { }
+
+pub struct AuthorsCollabWeights { }
+
+pub struct InstitutionsCollabDegree { }
+
+pub struct InstitutionsWeightedCollabDegree { }
+
+pub struct InstitutionsClustering { }
+
+pub struct InstitutionsCollabComponent { }
+
+pub struct InstitutionsCollaborators { }
+
+pub struct InstitutionsCollabWeights { }
+
+impl Entity for AuthorsCollabDegree { type T = u16; const N: usize = 3882893; const NAME: & str = "authors-collab-degree"; }
+
+impl MappableEntity for AuthorsCollabDegree { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsCollabDegree { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabDegreeMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCollabDegree; }
+
+impl Entity for AuthorsWeightedCollabDegree { type T = u32; const N: usize = 3882893; const NAME: & str = "authors-weighted-collab-degree"; }
+
+impl MappableEntity for AuthorsWeightedCollabDegree { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsWeightedCollabDegree { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::WeightedCollabDegreeMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsWeightedCollabDegree; }
+
+impl Entity for AuthorsClustering { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-clustering"; }
+
+impl MappableEntity for AuthorsClustering { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsClustering { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::ClusteringMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsClustering; }
+
+impl Entity for AuthorsCollabComponent { type T = u32; const N: usize = 3882893; const NAME: & str = "authors-collab-component"; }
+
+impl MappableEntity for AuthorsCollabComponent { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsCollabComponent { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabComponentMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCollabComponent; }
+
+impl Entity for AuthorsCollaborators { type T = Box<[u32]>; const N: usize = 3882893; const NAME: & str = "authors-collaborators"; }
+
+impl MappableEntity for AuthorsCollaborators { type KeyType = usize; }
+
+impl VariableSizeAttribute for AuthorsCollaborators { type SizeType = u16; }
+
+impl NamespacedEntity for AuthorsCollaborators { const NS: & str = "derive_links8"; }
+
+impl Link for AuthorsCollaborators { type Source = crate::gen::a1_entity_mapping::Authors; type Target = crate::gen::a1_entity_mapping::Authors; }
+
+impl MarkedAttribute<crate::common::CollaboratorsMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCollaborators; }
+
+impl Entity for AuthorsCollabWeights { type T = Box<[u32]>; const N: usize = 3882893; const NAME: & str = "authors-collab-weights"; }
+
+impl MappableEntity for AuthorsCollabWeights { type KeyType = usize; }
+
+impl VariableSizeAttribute for AuthorsCollabWeights { type SizeType = u16; }
+
+impl NamespacedEntity for AuthorsCollabWeights { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabWeightsMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCollabWeights; }
+
+impl Entity for InstitutionsCollabDegree { type T = u16; const N: usize = 29650; const NAME: & str = "institutions-collab-degree"; }
+
+impl MappableEntity for InstitutionsCollabDegree { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsCollabDegree { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabDegreeMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCollabDegree; }
+
+impl Entity for InstitutionsWeightedCollabDegree { type T = u32; const N: usize = 29650; const NAME: & str = "institutions-weighted-collab-degree"; }
+
+impl MappableEntity for InstitutionsWeightedCollabDegree { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsWeightedCollabDegree { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::WeightedCollabDegreeMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsWeightedCollabDegree; }
+
+impl Entity for InstitutionsClustering { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-clustering"; }
+
+impl MappableEntity for InstitutionsClustering { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsClustering { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::ClusteringMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsClustering; }
+
+impl Entity for InstitutionsCollabComponent { type T = u16; const N: usize = 29650; const NAME: & str = "institutions-collab-component"; }
+
+impl MappableEntity for InstitutionsCollabComponent { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsCollabComponent { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabComponentMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCollabComponent; }
+
+impl Entity for InstitutionsCollaborators { type T = Box<[u16]>; const N: usize = 29650; const NAME: & str = "institutions-collaborators"; }
+
+impl MappableEntity for InstitutionsCollaborators { type KeyType = usize; }
+
+impl VariableSizeAttribute for InstitutionsCollaborators { type SizeType = u16; }
+
+impl NamespacedEntity for InstitutionsCollaborators { const NS: & str = "derive_links8"; }
+
+impl Link for InstitutionsCollaborators { type Source = crate::gen::a1_entity_mapping::Institutions; type Target = crate::gen::a1_entity_mapping::Institutions; }
+
+impl MarkedAttribute<crate::common::CollaboratorsMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCollaborators; }
+
+impl Entity for InstitutionsCollabWeights { type T = Box<[u32]>; const N: usize = 29650; const NAME: & str = "institutions-collab-weights"; }
+
+impl MappableEntity for InstitutionsCollabWeights { type KeyType = usize; }
+
+impl VariableSizeAttribute for InstitutionsCollabWeights { type SizeType = u16; }
+
+impl NamespacedEntity for InstitutionsCollabWeights { const NS: & str = "derive_links8"; }
+
+impl MarkedAttribute<crate::common::CollabWeightsMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCollabWeights; }
\ No newline at end of file
@@ -0,0 +1,321 @@
+use dmove::{MarkedAttribute, Entity, MappableEntity, NamespacedEntity};
+
+pub struct AuthorsRefSfConc { }
+
+pub struct AuthorsRefSfDiversity { }
+
+pub struct AuthorsCitSfConc { }
+
+pub struct AuthorsCitSfDiversity { }
+
+pub struct InstitutionsRefSfConc { }
+
+pub struct InstitutionsRefSfDiversity { }
+
+pub struct InstitutionsCitSfConc { }
+
+pub struct InstitutionsCitSfDiversity { }
+
+pub struct SourcesRefSfConc { }
+
+pub struct SourcesRefSfDiversity { }
+
+pub struct SourcesCitSfConc { }
+
+pub struct SourcesCitSfDiversity { }
+
+pub struct SubfieldsRefSfConc { }
+
+pub struct SubfieldsRefSfDiversity { }
+
+pub struct SubfieldsCitSfConc { }
+
+pub struct SubfieldsCitSfDiversity { }
+
+pub struct CountriesRefSfConc { }
+
+pub struct CountriesRefSfDiversity { }
+
+pub struct CountriesCitSfConc { }
+
+pub struct CountriesCitSfDiversity { }
+
+pub struct PublishersRefSfConc { }
+
+pub struct PublishersRefSfDiversity { }
+
+pub struct PublishersCitSfConc { }
+
+pub struct PublishersCitSfDiversity { }
+
+pub struct FundersRefSfConc { }
+
+pub struct FundersRefSfDiversity { }
+
+pub struct FundersCitSfConc { }
+
+pub struct FundersCitSfDiversity { }
+
+pub struct TopicsRefSfConc { }
+
+pub struct TopicsRefSfDiversity { }
+
+pub struct TopicsCitSfConc { }
+
+pub struct TopicsCitSfDiversity { }
+
+impl Entity for AuthorsRefSfConc { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-ref-sf-conc"; }
+
+impl MappableEntity for AuthorsRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsRefSfConc; }
+
+impl Entity for AuthorsRefSfDiversity { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-ref-sf-diversity"; }
+
+impl MappableEntity for AuthorsRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsRefSfDiversity; }
+
+impl Entity for AuthorsCitSfConc { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-cit-sf-conc"; }
+
+impl MappableEntity for AuthorsCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCitSfConc; }
+
+impl Entity for AuthorsCitSfDiversity { type T = f64; const N: usize = 3882893; const NAME: & str = "authors-cit-sf-diversity"; }
+
+impl MappableEntity for AuthorsCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for AuthorsCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Authors { type AttributeEntity = AuthorsCitSfDiversity; }
+
+impl Entity for InstitutionsRefSfConc { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-ref-sf-conc"; }
+
+impl MappableEntity for InstitutionsRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsRefSfConc; }
+
+impl Entity for InstitutionsRefSfDiversity { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-ref-sf-diversity"; }
+
+impl MappableEntity for InstitutionsRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsRefSfDiversity; }
+
+impl Entity for InstitutionsCitSfConc { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-cit-sf-conc"; }
+
+impl MappableEntity for InstitutionsCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCitSfConc; }
+
+impl Entity for InstitutionsCitSfDiversity { type T = f64; const N: usize = 29650; const NAME: & str = "institutions-cit-sf-diversity"; }
+
+impl MappableEntity for InstitutionsCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for InstitutionsCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Institutions { type AttributeEntity = InstitutionsCitSfDiversity; }
+
+impl Entity for SourcesRefSfConc { type T = f64; const N: usize = 39074; const NAME: & str = "sources-ref-sf-conc"; }
+
+impl MappableEntity for SourcesRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesRefSfConc; }
+
+impl Entity for SourcesRefSfDiversity { type T = f64; const N: usize = 39074; const NAME: & str = "sources-ref-sf-diversity"; }
+
+impl MappableEntity for SourcesRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesRefSfDiversity; }
+
+impl Entity for SourcesCitSfConc { type T = f64; const N: usize = 39074; const NAME: & str = "sources-cit-sf-conc"; }
+
+impl MappableEntity for SourcesCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesCitSfConc; }
+
+impl Entity for SourcesCitSfDiversity { type T = f64; const N: usize = 39074; const NAME: & str = "sources-cit-sf-diversity"; }
+
+impl MappableEntity for SourcesCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for SourcesCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Sources { type AttributeEntity = SourcesCitSfDiversity; }
+
+impl Entity for SubfieldsRefSfConc { type T = f64; const N: usize = 254; const NAME: & str = "subfields-ref-sf-conc"; }
+
+impl MappableEntity for SubfieldsRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsRefSfConc; }
+
+impl Entity for SubfieldsRefSfDiversity { type T = f64; const N: usize = 254; const NAME: & str = "subfields-ref-sf-diversity"; }
+
+impl MappableEntity for SubfieldsRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsRefSfDiversity; }
+
+impl Entity for SubfieldsCitSfConc { type T = f64; const N: usize = 254; const NAME: & str = "subfields-cit-sf-conc"; }
+
+impl MappableEntity for SubfieldsCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsCitSfConc; }
+
+impl Entity for SubfieldsCitSfDiversity { type T = f64; const N: usize = 254; const NAME: & str = "subfields-cit-sf-diversity"; }
+
+impl MappableEntity for SubfieldsCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for SubfieldsCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Subfields { type AttributeEntity = SubfieldsCitSfDiversity; }
+
+impl Entity for CountriesRefSfConc { type T = f64; const N: usize = 230; const NAME: & str = "countries-ref-sf-conc"; }
+
+impl MappableEntity for CountriesRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesRefSfConc; }
+
+impl Entity for CountriesRefSfDiversity { type T = f64; const N: usize = 230; const NAME: & str = "countries-ref-sf-diversity"; }
+
+impl MappableEntity for CountriesRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesRefSfDiversity; }
+
+impl Entity for CountriesCitSfConc { type T = f64; const N: usize = 230; const NAME: & str = "countries-cit-sf-conc"; }
+
+impl MappableEntity for CountriesCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesCitSfConc; }
+
+impl Entity for CountriesCitSfDiversity { type T = f64; const N: usize = 230; const NAME: & str = "countries-cit-sf-diversity"; }
+
+impl MappableEntity for CountriesCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for CountriesCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Countries { type AttributeEntity = CountriesCitSfDiversity; }
+
+impl Entity for PublishersRefSfConc { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-ref-sf-conc"; }
+
+impl MappableEntity for PublishersRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersRefSfConc; }
+
+impl Entity for PublishersRefSfDiversity { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-ref-sf-diversity"; }
+
+impl MappableEntity for PublishersRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersRefSfDiversity; }
+
+impl Entity for PublishersCitSfConc { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-cit-sf-conc"; }
+
+impl MappableEntity for PublishersCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersCitSfConc; }
+
+impl Entity for PublishersCitSfDiversity { type T = f64; const N: usize = 7113; const NAME: & str = "publishers-cit-sf-diversity"; }
+
+impl MappableEntity for PublishersCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for PublishersCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Publishers { type AttributeEntity = PublishersCitSfDiversity; }
+
+impl Entity for FundersRefSfConc { type T = f64; const N: usize = 32438; const NAME: & str = "funders-ref-sf-conc"; }
+
+impl MappableEntity for FundersRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for FundersRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersRefSfConc; }
+
+impl Entity for FundersRefSfDiversity { type T = f64; const N: usize = 32438; const NAME: & str = "funders-ref-sf-diversity"; }
+
+impl MappableEntity for FundersRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for FundersRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersRefSfDiversity; }
+
+impl Entity for FundersCitSfConc { type T = f64; const N: usize = 32438; const NAME: & str = "funders-cit-sf-conc"; }
+
+impl MappableEntity for FundersCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for FundersCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersCitSfConc; }
+
+impl Entity for FundersCitSfDiversity { type T = f64; const N: usize = 32438; const NAME: & str = "funders-cit-sf-diversity"; }
+
+impl MappableEntity for FundersCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for FundersCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Funders { type AttributeEntity = FundersCitSfDiversity; }
+
+impl Entity for TopicsRefSfConc { type T = f64; const N: usize = 4518; const NAME: & str = "topics-ref-sf-conc"; }
+
+impl MappableEntity for TopicsRefSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for TopicsRefSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Topics { type AttributeEntity = TopicsRefSfConc; }
+
+impl Entity for TopicsRefSfDiversity { type T = f64; const N: usize = 4518; const NAME: & str = "topics-ref-sf-diversity"; }
+
+impl MappableEntity for TopicsRefSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for TopicsRefSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::RefSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Topics { type AttributeEntity = TopicsRefSfDiversity; }
+
+impl Entity for TopicsCitSfConc { type T = f64; const N: usize = 4518; const NAME: & str = "topics-cit-sf-conc"; }
+
+impl MappableEntity for TopicsCitSfConc { type KeyType = usize; }
+
+impl NamespacedEntity for TopicsCitSfConc { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsConcentrationMarker> for crate::gen::a1_entity_mapping::Topics { type AttributeEntity = TopicsCitSfConc; }
+
+impl Entity for TopicsCitSfDiversity { type T = f64; const N: usize = 4518; const NAME: & str = "topics-cit-sf-diversity"; }
+
+impl MappableEntity for TopicsCitSfDiversity { type KeyType = usize; }
+
+impl NamespacedEntity for TopicsCitSfDiversity { const NS: & str = "derive_links9"; }
+
+impl MarkedAttribute<crate::common::CitSubfieldsDiversityMarker> for crate::gen::a1_entity_mapping::Topics { type AttributeEntity = TopicsCitSfDiversity; }
\ No newline at end of file
@@ -4,4 +4,8 @@ pub mod derive_links1;
 pub mod derive_links2;
 pub mod derive_links3;
 pub mod derive_links4;
-pub mod derive_links5;
\ No newline at end of file
+pub mod derive_links5;
+pub mod derive_links6;
+pub mod derive_links7;
+pub mod derive_links8;
+pub mod derive_links9;
\ No newline at end of file
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashSet,
+    fs::{read_to_string, write, File},
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use csv::ReaderBuilder;
+use dmove::{
+    camel_case, BigId, DowncastingBuilder, Entity, FixAttBuilder, NamespacedEntity, UnsignedNumber,
+    VarAttBuilder,
+};
+
+use crate::{
+    common::{code_path, get_gz_buf, MainEntity, QuickestNumbered, Stowage, NET},
+    gen::a1_entity_mapping::{
+        Authors, Funders, Institutions, Publishers, Sources, Subfields, Topics, Works,
+    },
+};
+
+//generated modules of imports start with this, so the pipeline keeps them
+const EXT_PREFIX: &str = "ext_";
+const ID_COL: &str = "id";
+pub(crate) const MAX_REPORTED: usize = 20;
+
+enum ColumnType {
+    Int,
+    Float,
+    Str,
+}
+
+///values of the rows of the csv, with the OpenAlex id they belong to
+struct ImportedRows {
+    headers: Vec<String>,
+    rows: Vec<(BigId, Vec<String>)>,
+}
+
+///imports the value columns of a csv of OpenAlex ids as attributes of the entity,
+///the csv is in a directory named after the entity and its name names the attributes
+pub fn main(mut stowage: Stowage, csv_path: &str) -> io::Result<()> {
+    let path = Path::new(csv_path);
+    let entity = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|e| e.to_str())
+        .expect("csv in a directory named after the entity")
+        .to_string();
+    let stem = path
+        .file_name()
+        .and_then(|e| e.to_str())
+        .and_then(|e| e.split('.').next())
+        .unwrap()
+        .to_string();
+    check_name(&stem, "file name")?;
+    let prefix = id_prefix(&entity)
+        .ok_or_else(|| invalid(format!("no OpenAlex ids to import to for {entity}")))?;
+    //the ids are checked before anything is staged
+    let imported = ImportedRows::read(path, prefix)?;
+    let ns = format!("{EXT_PREFIX}{}", stem.replace('-', "_"));
+    stowage.set_namespace(&ns);
+    match entity.as_str() {
+        Works::NAME => import::<Works>(&stowage, imported, &stem),
+        Authors::NAME => import::<Authors>(&stowage, imported, &stem),
+        Institutions::NAME => import::<Institutions>(&stowage, imported, &stem),
+        Sources::NAME => import::<Sources>(&stowage, imported, &stem),
+        Publishers::NAME => import::<Publishers>(&stowage, imported, &stem),
+        Funders::NAME => import::<Funders>(&stowage, imported, &stem),
+        Topics::NAME => import::<Topics>(&stowage, imported, &stem),
+        Subfields::NAME => import::<Subfields>(&stowage, imported, &stem),
+        _ => unreachable!("entities without ids are rejected by id_prefix"),
+    }?;
+    stowage.write_code()?;
+    add_gen_module(&ns)
+}
+
+fn import<E>(stowage: &Stowage, imported: ImportedRows, stem: &str) -> io::Result<()>
+where
+    E: MainEntity + NamespacedEntity,
+    NET<E>: UnsignedNumber,
+{
+    let id_map = stowage.get_entity_interface::<E, QuickestNumbered>();
+    let rows = imported.map_rows(|oa_id| id_map.0.get(&oa_id).map(|i| i.to_usize()));
+    for (ci, header) in imported.headers.iter().enumerate() {
+        let name = format!("{}-{stem}-{}", E::NAME, slug(header));
+        let values = column_values(&rows, ci, E::N);
+        let (marker, type_name) = match column_type(&values) {
+            ColumnType::Int => (
+                stowage.declare_iter_generated::<DowncastingBuilder, _, _, E>(
+                    values
+                        .iter()
+                        .map(|e| e.map(|v| v.parse::<usize>().unwrap()).unwrap_or(0)),
+                    &name,
+                ),
+                "integer",
+            ),
+            ColumnType::Float => (
+                stowage.declare_iter_generated::<FixAttBuilder, _, f64, E>(
+                    values
+                        .iter()
+                        .map(|e| e.map(|v| v.parse::<f64>().unwrap()).unwrap_or(0.0)),
+                    &name,
+                ),
+                "float",
+            ),
+            ColumnType::Str => (
+                stowage.declare_iter_generated::<VarAttBuilder, _, _, E>(
+                    values.iter().map(|e| e.unwrap_or("").to_string()),
+                    &name,
+                ),
+                "string",
+            ),
+        };
+        println!("imported {header} as {type_name} {name}, marked by {marker}");
+    }
+    Ok(())
+}
+
+///one value per entity index, attributes are as long as the entity
+fn column_values<'a>(
+    rows: &[(usize, &'a Vec<String>)],
+    ci: usize,
+    n: usize,
+) -> Vec<Option<&'a str>> {
+    let mut values = vec![None; n];
+    for (ind, row) in rows.iter() {
+        let v = row[ci].trim();
+        if !v.is_empty() {
+            values[*ind] = Some(v);
+        }
+    }
+    values
+}
+
+impl ImportedRows {
+    fn read(path: &Path, prefix: Option<char>) -> io::Result<Self> {
+        let buf: Box<dyn Read> = if path.extension().is_some_and(|e| e == "gz") {
+            Box::new(get_gz_buf(path)?)
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+        let mut reader = ReaderBuilder::new().from_reader(buf);
+        let all_headers: Vec<String> = reader.headers()?.iter().map(|e| e.to_string()).collect();
+        let id_i = all_headers
+            .iter()
+            .position(|e| e == ID_COL)
+            .ok_or_else(|| invalid(format!("no {ID_COL} column of OpenAlex ids")))?;
+        let headers: Vec<String> = all_headers
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| *i != id_i)
+            .map(|(_, e)| e)
+            .collect();
+        check_headers(&headers)?;
+        let mut rows = Vec::new();
+        let mut malformed = Vec::new();
+        for rec in reader.records() {
+            let rec = rec?;
+            let oa_id = &rec[id_i];
+            match parse_oa_id(oa_id, prefix) {
+                Some(id) => rows.push((
+                    id,
+                    rec.iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != id_i)
+                        .map(|(_, v)| v.to_string())
+                        .collect(),
+                )),
+                None => malformed.push(oa_id.to_string()),
+            }
+        }
+        if !malformed.is_empty() {
+            let shown = &malformed[..malformed.len().min(MAX_REPORTED)];
+            return Err(invalid(format!(
+                "{} ids are not OpenAlex ids of the entity, like {shown:?}",
+                malformed.len()
+            )));
+        }
+        Ok(Self { headers, rows })
+    }
+
+    ///rows at the index of the entity they belong to, the first row of an id is kept
+    fn map_rows<F>(&self, map_id: F) -> Vec<(usize, &Vec<String>)>
+    where
+        F: Fn(BigId) -> Option<usize>,
+    {
+        let mut out = Vec::new();
+        let (mut seen, mut unmatched, mut duplicates) = (HashSet::new(), Vec::new(), Vec::new());
+        for (id, row) in self.rows.iter() {
+            match map_id(*id) {
+                Some(ind) if seen.insert(ind) => out.push((ind, row)),
+                Some(_) => duplicates.push(*id),
+                None => unmatched.push(*id),
+            }
+        }
+        println!(
+            "matched {} rows, {} ids unmatched, {} duplicate ids",
+            out.len(),
+            unmatched.len(),
+            duplicates.len()
+        );
+        for id in unmatched.iter().take(MAX_REPORTED) {
+            println!("unmatched id: {id}");
+        }
+        for id in duplicates.iter().take(MAX_REPORTED) {
+            println!("duplicate id: {id}");
+        }
+        out
+    }
+}
+
+///letter of the short OpenAlex ids of the entity, subfields have plain numbers
+fn id_prefix(entity: &str) -> Option<Option<char>> {
+    let out = match entity {
+        Works::NAME => 'W',
+        Authors::NAME => 'A',
+        Institutions::NAME => 'I',
+        Sources::NAME => 'S',
+        Publishers::NAME => 'P',
+        Funders::NAME => 'F',
+        Topics::NAME => 'T',
+        Subfields::NAME => return Some(None),
+        _ => return None,
+    };
+    Some(Some(out))
+}
+
+///narrowest type all the given values parse as
+fn column_type(values: &[Option<&str>]) -> ColumnType {
+    let given = || values.iter().flatten();
+    if given().all(|v| v.parse::<usize>().is_ok()) {
+        ColumnType::Int
+    } else if given().all(|v| v.parse::<f64>().is_ok()) {
+        ColumnType::Float
+    } else {
+        ColumnType::Str
+    }
+}
+
+///numeric part of a full or short OpenAlex id, like https://openalex.org/I27837315 or I27837315
+fn parse_oa_id(id: &str, prefix: Option<char>) -> Option<BigId> {
+    let short = id.trim().rsplit('/').next()?;
+    let num = match prefix {
+        Some(c) => short
+            .strip_prefix(c)
+            .or(short.strip_prefix(c.to_ascii_lowercase()))?,
+        None => short,
+    };
+    num.parse::<BigId>().ok()
+}
+
+fn slug(header: &str) -> String {
+    header
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|e| !e.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+///names end up in module and type names of the generated code
+fn check_name(name: &str, what: &str) -> io::Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(invalid(format!(
+            "{what} {name:?} has to start with a letter and have only letters, digits, - and _"
+        )));
+    }
+    Ok(())
+}
+
+///the type names drop digits, so columns only differing in them would collide
+fn check_headers(headers: &[String]) -> io::Result<()> {
+    let mut types = HashSet::new();
+    for header in headers {
+        check_name(&slug(header), "column")?;
+        if !types.insert(camel_case(&slug(header))) {
+            return Err(invalid(format!(
+                "column {header:?} has the same type name as an earlier one"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn add_gen_module(ns: &str) -> io::Result<()> {
+    let mod_path = code_path("mod");
+    let mod_line = format!("pub mod {ns};");
+    let mods = read_to_string(&mod_path)?;
+    if !mods.lines().any(|e| e == mod_line) {
+        write(&mod_path, format!("{mods}\n{mod_line}"))?;
+    }
+    Ok(())
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oa_ids() {
+        let inst = Some('I');
+        assert_eq!(
+            parse_oa_id("https://openalex.org/I27837315", inst),
+            Some(27837315)
+        );
+        assert_eq!(parse_oa_id(" I27837315 ", inst), Some(27837315));
+        assert_eq!(parse_oa_id("i27837315", inst), Some(27837315));
+        assert_eq!(parse_oa_id("W27837315", inst), None);
+        assert_eq!(parse_oa_id("27837315", inst), None);
+        assert_eq!(parse_oa_id("I", inst), None);
+        assert_eq!(
+            parse_oa_id("https://openalex.org/subfields/1702", None),
+            Some(1702)
+        );
+        assert_eq!(parse_oa_id("S1702", None), None);
+    }
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slug("Share of OA (%)"), "share-of-oa");
+        assert_eq!(slug("h_index"), "h-index");
+        assert_eq!(slug("2020 score"), "2020-score");
+        assert_eq!(slug("%"), "");
+        assert!(check_name(&slug("Share of OA (%)"), "column").is_ok());
+        assert!(check_name(&slug("2020 score"), "column").is_err());
+        assert!(check_name(&slug("%"), "column").is_err());
+        assert!(check_name("uni rankings", "file name").is_err());
+        assert!(check_headers(&["score 2020".to_string(), "score 2021".to_string()]).is_err());
+    }
+
+    #[test]
+    fn values_per_entity() {
+        let (a, b) = (
+            vec!["x".to_string(), " ".to_string()],
+            vec!["y".to_string(), "2".to_string()],
+        );
+        let rows = vec![(1, &a), (3, &b)];
+        assert_eq!(
+            column_values(&rows, 0, 4),
+            vec![None, Some("x"), None, Some("y")]
+        );
+        assert_eq!(
+            column_values(&rows, 1, 4),
+            vec![None, None, None, Some("2")]
+        );
+    }
+
+    #[test]
+    fn column_types() {
+        assert!(matches!(
+            column_type(&[Some("1"), None, Some("20")]),
+            ColumnType::Int
+        ));
+        assert!(matches!(
+            column_type(&[Some("1"), Some("0.5")]),
+            ColumnType::Float
+        ));
+        assert!(matches!(column_type(&[Some("-1")]), ColumnType::Float));
+        assert!(matches!(
+            column_type(&[Some("1"), Some("n/a")]),
+            ColumnType::Str
+        ));
+        assert!(matches!(column_type(&[None, None]), ColumnType::Int));
+    }
+}
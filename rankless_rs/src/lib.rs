@@ -4,18 +4,22 @@ use std::io;
 
 pub mod agg_tree;
 pub mod common;
+pub mod config;
 mod csv_writers;
-pub mod env_consts;
 mod filter;
 pub mod gen;
+mod import;
 mod oa_structs;
+mod record_store;
 mod semantic_ids;
 pub mod steps;
 
 pub use common::{
-    CiteCountMarker, NameExtensionMarker, NameMarker, QuickestBox, QuickestNumbered, QuickestVBox,
-    ReadFixIter, ReadIter, SemanticIdMarker, Stowage, WorkCountMarker,
+    AlternativeNamesMarker, CiteCountMarker, NameExtensionMarker, NameMarker, OrcidMarker,
+    QuickestBox, QuickestNumbered, QuickestVBox, ReadFixIter, ReadIter, SemanticIdMarker, Stowage,
+    WorkCountMarker,
 };
+pub use semantic_ids::SemRegistry;
 
 macro_rules! mods_as_comms {
     ($($mod_name:ident),*) => {
@@ -29,18 +33,54 @@ macro_rules! mods_as_comms {
             )*
             Ok(())
         }
+
+        pub fn step_namespaces() -> Vec<&'static str> {
+            vec![$(stringify!($mod_name)),*]
+        }
     };
 }
 
+///namespaces of the steps and the imported attributes, the data of a full build
+pub fn data_namespaces() -> Vec<&'static str> {
+    let mut out = step_namespaces();
+    out.extend(env!("EXT_NAMESPACES").split(',').filter(|e| !e.is_empty()));
+    out
+}
+
 pub fn runner(comm: &str, root_str: &str, in_root_o: Option<String>) -> io::Result<()> {
     let stowage = Stowage::new(root_str);
-    if comm == "to-csv" {
+    if comm == "to-csv" || comm == "to-csv-update" {
         if let Some(in_root_str) = in_root_o {
-            csv_writers::write_csvs(&in_root_str, &stowage)?;
+            csv_writers::write_csvs(&in_root_str, &stowage, comm == "to-csv-update")?;
+            stowage.unlink_jsonl()?;
         }
+    } else if comm == "link-jsonl" {
+        let in_root_str = in_root_o.expect("snapshot data dir to read");
+        return stowage.link_jsonl(&in_root_str);
     } else if comm == "filter" {
         return filter::main(stowage);
+    } else if comm == "pack" {
+        let out_path = in_root_o.expect("bundle path to write");
+        return stowage.pack_bundle(&data_namespaces(), &out_path);
+    } else if comm == "unpack" {
+        let bundle_path = in_root_o.expect("bundle path to read");
+        return stowage.unpack_bundle(&bundle_path);
+    } else if comm == "restore-ns" {
+        let ns = in_root_o.expect("namespace to restore");
+        return stowage.restore_namespace(&ns);
+    } else if comm == "filter-history" {
+        let oa_id = in_root_o.expect("OpenAlex id to look up");
+        return filter::history(&stowage, &oa_id);
+    } else if comm == "check-config" {
+        return stowage.check_config(&data_namespaces());
+    } else if comm == "import-att" {
+        let csv_path = in_root_o.expect("csv of OpenAlex ids and values to import");
+        return import::main(stowage, &csv_path);
+    }
+    let namespaces = step_namespaces();
+    if let Some(i) = namespaces.iter().position(|ns| *ns == comm) {
+        stowage.check_config(&namespaces[..i])?;
     }
     subrun(comm, stowage)
 }
-mods_as_comms!(a1_entity_mapping, a2_init_atts, derive_links1, derive_links2, derive_links3, derive_links4, derive_links5);
+mods_as_comms!(a1_entity_mapping, a2_init_atts, derive_links1, derive_links2, derive_links3, derive_links4, derive_links5, derive_links6, derive_links7, derive_links8, derive_links9);
@@ -37,6 +37,7 @@ pub trait Named {
 add_id_traits!(
     Author,
     Concept,
+    Funder,
     Institution,
     Publisher,
     Source,
@@ -105,7 +106,8 @@ pub struct Author {
     id: String,
     orcid: Option<String>,
     display_name: Option<String>,
-    // display_name_alternatives: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_json_array")]
+    display_name_alternatives: Option<String>,
     works_count: Option<u32>,
     cited_by_count: Option<u32>,
     updated_date: Option<String>,
@@ -224,6 +226,21 @@ pub struct Publisher {
     updated_date: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Funder {
+    id: String,
+    display_name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_json_array")]
+    alternate_titles: Option<String>,
+    country_code: Option<String>,
+    description: Option<String>,
+    homepage_url: Option<String>,
+    grants_count: Option<u32>,
+    works_count: Option<u32>,
+    cited_by_count: Option<u64>,
+    updated_date: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Source {
     id: String,
@@ -232,6 +249,7 @@ pub struct Source {
     issn: Option<String>,
     display_name: Option<String>,
     publisher: Option<String>,
+    host_organization: Option<String>,
     works_count: Option<u32>,
     cited_by_count: Option<u64>,
     is_oa: Option<bool>,
@@ -342,6 +360,14 @@ pub struct OpenAccess {
     oa_url: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Grant {
+    pub parent_id: Option<String>,
+    funder: Option<String>,
+    funder_display_name: Option<String>,
+    award_id: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ReferencedWork {
     pub parent_id: Option<String>,
@@ -469,7 +495,7 @@ pub mod post {
     use crate::{add_parent_parsed_id_traits, add_strict_parsed_id_traits};
 
     use super::{oa_id_parse, BigId, Deserialize, IdTrait, ParsedId};
-    use crate::common::field_id_parse;
+    use crate::common::{field_id_parse, ID_PREFIX};
 
     #[derive(Deserialize, Debug)]
     pub struct Authorship {
@@ -486,11 +512,18 @@ pub mod post {
         pub source_id: Option<String>,
     }
 
+    #[derive(Deserialize, Debug)]
+    pub struct Grant {
+        pub parent_id: Option<String>,
+        pub funder: Option<String>,
+    }
+
     #[derive(Deserialize, Debug)]
     pub struct Author {
         id: String,
-        // orcid: Option<String>,
-        // display_name: Option<String>,
+        pub orcid: Option<String>,
+        pub display_name: String,
+        pub display_name_alternatives: Option<String>,
         pub works_count: Option<u32>,
         pub cited_by_count: Option<u32>,
     }
@@ -514,6 +547,7 @@ pub mod post {
     #[derive(Deserialize, Debug)]
     pub struct Institution {
         id: String,
+        pub ror: Option<String>,
         pub display_name: String,
         pub country_code: Option<String>,
         pub display_name_acronyms: Option<String>,
@@ -523,13 +557,38 @@ pub mod post {
     #[derive(Deserialize, Debug)]
     pub struct Source {
         id: String,
+        pub issn_l: Option<String>,
         pub display_name: String,
         pub alternate_titles: Option<String>,
         pub abbreviated_title: Option<String>,
+        pub host_organization: Option<String>,
     }
 
-    add_id_traits!(Author, Institution, Source);
-    add_strict_parsed_id_traits!(Author, Topic, Institution, Source);
+    #[derive(Deserialize, Debug)]
+    pub struct Publisher {
+        id: String,
+        pub display_name: String,
+        pub alternate_titles: Option<String>,
+        pub parent_publisher: Option<String>,
+        // pub hierarchy_level: Option<u8>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub struct Funder {
+        id: String,
+        pub display_name: String,
+        pub alternate_titles: Option<String>,
+    }
+
+    ///a retired id of the snapshot merged id lists and the one it lives on as
+    #[derive(Deserialize, Debug)]
+    pub struct MergedId {
+        pub id: String,
+        pub merge_into_id: String,
+    }
+
+    add_id_traits!(Author, Institution, Source, Publisher, Funder);
+    add_strict_parsed_id_traits!(Author, Topic, Institution, Source, Publisher, Funder);
 
     impl ParsedId for SubField {
         fn get_parsed_id(&self) -> BigId {
@@ -537,7 +596,25 @@ pub mod post {
         }
     }
 
-    add_parent_parsed_id_traits!(Location, Authorship);
+    add_parent_parsed_id_traits!(Location, Authorship, Grant);
+
+    impl MergedId {
+        pub fn parsed_pair(&self) -> Option<(BigId, BigId)> {
+            Some((
+                short_id_parse(&self.id)?,
+                short_id_parse(&self.merge_into_id)?,
+            ))
+        }
+    }
+
+    //the lists use short ids like A5012345678, full urls are accepted too
+    fn short_id_parse(id: &str) -> Option<BigId> {
+        id.trim()
+            .trim_start_matches(ID_PREFIX)
+            .get(1..)?
+            .parse()
+            .ok()
+    }
 
     pub fn read_post_str_arr(in_str: &Option<String>) -> Vec<String> {
         serde_json::from_str::<Vec<String>>(&in_str.as_ref().unwrap_or(&"[]".to_string()))
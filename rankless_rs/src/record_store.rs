@@ -0,0 +1,302 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use csv::StringRecord;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{
+    de::{self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
+
+use crate::common::get_gz_buf;
+
+pub const STORE_EXTENSION: &str = "bin.gz";
+
+///a field of a stored row, typed as the row struct serialized it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum StoredField {
+    Null,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+///rows of one table from one snapshot partition, the file is created with the first row
+pub struct StoreTable {
+    path: PathBuf,
+    header: Vec<String>,
+    out: Option<GzEncoder<BufWriter<File>>>,
+}
+
+///rows of one shard of a stored table, after its header
+pub struct StoredRecords {
+    buf: Option<BufReader<GzDecoder<File>>>,
+}
+
+///a stored row, deserialized by field name like a csv row with headers
+pub struct StoredRow<'a> {
+    header: &'a StringRecord,
+    fields: Vec<StoredField>,
+}
+
+struct RowAccess<I> {
+    fields: I,
+    value: Option<StoredField>,
+}
+
+pub trait RowSink {
+    fn serialize<S: Serialize>(&mut self, row: S) -> io::Result<()>;
+}
+
+impl StoreTable {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            header: Vec::new(),
+            out: None,
+        }
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(w) = self.out {
+            w.finish()?.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl RowSink for StoreTable {
+    fn serialize<S: Serialize>(&mut self, row: S) -> io::Result<()> {
+        let mut fields = match serde_json::to_value(row)? {
+            Value::Object(m) => m,
+            v => return Err(io::Error::other(format!("not a row: {v}"))),
+        };
+        if self.out.is_none() {
+            create_dir_all(self.path.parent().unwrap())?;
+            let file = BufWriter::new(File::create(&self.path)?);
+            let mut w = GzEncoder::new(file, Compression::default());
+            self.header = fields.keys().cloned().collect();
+            bincode::serialize_into(&mut w, &self.header).map_err(io::Error::other)?;
+            self.out = Some(w);
+        }
+        let row: Vec<StoredField> = self
+            .header
+            .iter()
+            .map(|k| {
+                fields
+                    .remove(k)
+                    .map_or(StoredField::Null, StoredField::from)
+            })
+            .collect();
+        bincode::serialize_into(self.out.as_mut().unwrap(), &row).map_err(io::Error::other)
+    }
+}
+
+impl<W: Write> RowSink for csv::Writer<W> {
+    fn serialize<S: Serialize>(&mut self, row: S) -> io::Result<()> {
+        csv::Writer::serialize(self, row).map_err(io::Error::from)
+    }
+}
+
+impl From<Value> for StoredField {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Null => Self::Null,
+            Value::Bool(b) => Self::Bool(b),
+            Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+                (Some(u), _) => Self::UInt(u),
+                (None, Some(i)) => Self::Int(i),
+                _ => Self::Float(n.as_f64().unwrap()),
+            },
+            Value::String(s) => Self::Str(s),
+            //rows are flat, anything nested is kept as json like in the csvs
+            other => Self::Str(other.to_string()),
+        }
+    }
+}
+
+impl StoredRecords {
+    pub fn open(path: &Path) -> (StringRecord, Self) {
+        assert!(
+            path.to_str().unwrap().ends_with(STORE_EXTENSION),
+            "{path:?}"
+        );
+        let mut buf = get_gz_buf(path).unwrap_or_else(|_| panic!("{path:?}"));
+        let header: Vec<String> =
+            bincode::deserialize_from(&mut buf).unwrap_or_else(|e| panic!("{path:?}: {e}"));
+        (StringRecord::from(header), Self { buf: Some(buf) })
+    }
+
+    pub fn empty() -> Self {
+        Self { buf: None }
+    }
+}
+
+impl Iterator for StoredRecords {
+    type Item = Vec<StoredField>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match bincode::deserialize_from::<_, Vec<StoredField>>(self.buf.as_mut()?) {
+            Ok(fields) => Some(fields),
+            Err(e) => match *e {
+                bincode::ErrorKind::Io(ref ioe) if ioe.kind() == io::ErrorKind::UnexpectedEof => {
+                    None
+                }
+                _ => panic!("broken record store: {e}"),
+            },
+        }
+    }
+}
+
+impl<'a> StoredRow<'a> {
+    pub fn new(header: &'a StringRecord, fields: Vec<StoredField>) -> Self {
+        Self { header, fields }
+    }
+}
+
+impl<'de, 'a> Deserializer<'de> for StoredRow<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(RowAccess {
+            fields: self.header.iter().zip(self.fields),
+            value: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a, I> MapAccess<'de> for RowAccess<I>
+where
+    I: Iterator<Item = (&'a str, StoredField)>,
+{
+    type Error = de::value::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.fields.next() {
+            Some((k, v)) => {
+                self.value = Some(v);
+                seed.deserialize(k.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(self.value.take().expect("value after key"))
+    }
+}
+
+//missing fields read like the empty cells of the csvs: none, or an empty string
+impl<'de> Deserializer<'de> for StoredField {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::UInt(u) => visitor.visit_u64(u),
+            Self::Int(i) => visitor.visit_i64(i),
+            Self::Float(f) => visitor.visit_f64(f),
+            Self::Str(s) => visitor.visit_string(s),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_string(String::new()),
+            Self::Bool(b) => visitor.visit_string(b.to_string()),
+            Self::UInt(u) => visitor.visit_string(u.to_string()),
+            Self::Int(i) => visitor.visit_string(i.to_string()),
+            Self::Float(f) => visitor.visit_string(f.to_string()),
+            Self::Str(s) => visitor.visit_string(s),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Written {
+        id: String,
+        #[serde(rename = "type")]
+        kind: Option<String>,
+        name: Option<String>,
+        count: Option<u32>,
+        score: f64,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Read {
+        #[serde(rename = "type")]
+        kind: Option<String>,
+        name: String,
+        count: Option<u16>,
+        score: String,
+    }
+
+    #[test]
+    fn typed_rows() {
+        let dir = std::env::temp_dir().join(format!("rankless-store-{}", std::process::id()));
+        let path = dir.join("main").join(format!("00000.{STORE_EXTENSION}"));
+        let mut table = StoreTable::new(path.clone());
+        for (name, count) in [(Some("a"), Some(3)), (None, None)] {
+            let row = Written {
+                id: "x".to_string(),
+                kind: Some("article".to_string()),
+                name: name.map(|e| e.to_string()),
+                count,
+                score: 0.5,
+            };
+            table.serialize(&row).unwrap();
+        }
+        table.finish().unwrap();
+        let (header, records) = StoredRecords::open(&path);
+        let rows: Vec<Read> = records
+            .map(|fields| Read::deserialize(StoredRow::new(&header, fields)).unwrap())
+            .collect();
+        let read = |name: &str, count| Read {
+            kind: Some("article".to_string()),
+            name: name.to_string(),
+            count,
+            score: "0.5".to_string(),
+        };
+        assert_eq!(rows, vec![read("a", Some(3)), read("", None)]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
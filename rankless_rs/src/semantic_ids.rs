@@ -1,17 +1,23 @@
-use std::collections::BinaryHeap;
+use std::{
+    collections::{BTreeMap, BinaryHeap},
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::Path,
+};
 
 use dmove::{
-    CompactEntity, Entity, MarkedAttribute, NamespacedEntity, UnsignedNumber, VarAttBuilder, ET,
-    MAA,
+    bundle::open_data, BigId, CompactEntity, Entity, MarkedAttribute, NamespacedEntity,
+    UnsignedNumber, VarAttBuilder, ET, MAA,
 };
 use hashbrown::HashSet;
-use muwo_search::StackWordSet;
-use serde::de::DeserializeOwned;
+use muwo_search::{transliterate, StackWordSet};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
     common::{init_empty_slice, MainEntity, ParsedId, MAIN_NAME},
+    import::MAX_REPORTED,
     oa_structs::{
-        post::{read_post_str_arr, Institution, Source},
+        post::{read_post_str_arr, Author, Funder, Institution, Publisher, Source},
         FieldLike, NamedEntity,
     },
     QuickestNumbered, ReadFixIter, SemanticIdMarker, Stowage, WorkCountMarker,
@@ -25,6 +31,15 @@ pub trait AddSemId {
     fn get_names(&self) -> Vec<String>;
 }
 
+///semantic ids issued so far, kept across rebuilds so public urls stay put
+#[derive(Serialize, Deserialize, Default)]
+pub struct SemRegistry {
+    ///openalex id -> current semantic id, also of entities left out of this build
+    pub issued: BTreeMap<BigId, String>,
+    ///retired semantic id -> openalex id of the entity it pointed to
+    pub redirects: BTreeMap<String, BigId>,
+}
+
 trait DoIfNot<T> {
     fn ifnotin<F>(&mut self, e: T, f: F) -> bool
     where
@@ -46,14 +61,35 @@ impl Stowage {
                 id_ops[eid.to_usize()] = o.get_names();
             }
         }
+        let mut oa_ids = init_empty_slice::<E, BigId>();
+        for (oid, eid) in interface.0.iter() {
+            oa_ids[eid.to_usize()] = *oid;
+        }
+        let file_name = SemRegistry::file_name::<E>();
+        let mut registry = SemRegistry::read(&self.published_file(&file_name)).unwrap_or_default();
+        //issued and retired ids are never handed to another entity
+        let mut sem_set: HashSet<String> = registry
+            .issued
+            .values()
+            .chain(registry.redirects.keys())
+            .cloned()
+            .collect();
+        let suffs = get_suffs();
+        let mut ids = init_empty_slice::<E, String>();
+        for (eid, oid) in oa_ids.iter().enumerate() {
+            if let Some(prev) = registry.issued.get(oid) {
+                if sem_candidates(&id_ops[eid], &suffs).any(|e| &e == prev) {
+                    ids[eid] = prev.clone();
+                }
+            }
+        }
+
         let wcounts = self.get_entity_interface::<MAA<E, WorkCountMarker>, ReadFixIter>();
+        let mut collisions = Vec::new();
         let mut miss_heap = BinaryHeap::new();
-        let mut sem_set = HashSet::new();
         for (eid, wc) in wcounts.enumerate() {
             miss_heap.push((wc, eid));
         }
-        let suffs = get_suffs();
-        let mut ids = init_empty_slice::<E, String>();
         while let Some((_wc, eid)) = miss_heap.pop() {
             let id_opts_vec = &id_ops[eid];
             if id_opts_vec.len() == 0 {
@@ -63,17 +99,36 @@ impl Stowage {
                 }
                 continue;
             }
-            let for_suff = id_opts_vec.iter().last().unwrap();
-            for sid in id_ops[eid]
-                .clone()
-                .into_iter()
-                .chain(suffs.iter().map(|e| (for_suff.to_owned() + e)))
-            {
-                if sem_set.ifnotin(sid, |e| ids[eid] = e) {
-                    break;
+            if !ids[eid].is_empty() {
+                continue;
+            }
+            let oid = oa_ids[eid];
+            match free_sem_id(id_opts_vec, &suffs, &mut sem_set) {
+                Some(sid) => ids[eid] = sid,
+                None => {
+                    println!("no free semantic id: {}({eid})", E::NAME);
+                    continue;
                 }
             }
+            if !id_opts_vec.contains(&ids[eid]) {
+                collisions.push((oid, ids[eid].clone()));
+            }
+            if let Some(prev) = registry.issued.insert(oid, ids[eid].clone()) {
+                println!("{} {oid}: {prev} -> {}", E::NAME, ids[eid]);
+                registry.redirects.insert(prev, oid);
+            }
         }
+        println!(
+            "{}: {} semantic ids fell to suffixes",
+            E::NAME,
+            collisions.len()
+        );
+        for (oid, sid) in collisions.iter().take(MAX_REPORTED) {
+            println!("collided: {oid} -> {sid}");
+        }
+        registry
+            .write(&self.staged_file(&file_name))
+            .expect("writing semantic id registry");
         self.decsem::<E, _>(ids.iter().map(|e| e.to_owned()))
     }
 
@@ -91,13 +146,22 @@ impl Stowage {
 
 impl AddSemId for NamedEntity {
     fn get_names(&self) -> Vec<String> {
-        vec![semantify(&self.display_name)]
+        sem_names(vec![self.display_name.clone()], None)
+    }
+}
+
+impl AddSemId for Author {
+    fn get_names(&self) -> Vec<String> {
+        sem_names(
+            vec![self.display_name.clone()],
+            id_slug("orcid", &self.orcid),
+        )
     }
 }
 
 impl AddSemId for FieldLike {
     fn get_names(&self) -> Vec<String> {
-        vec![semantify(&self.display_name)]
+        sem_names(vec![self.display_name.clone()], None)
     }
 }
 
@@ -109,7 +173,25 @@ impl AddSemId for Source {
         }
         out.sort_by_key(|e| e.len());
         out.push(self.display_name.clone());
-        out.iter().map(semantify).collect()
+        sem_names(out, id_slug("issn", &self.issn_l))
+    }
+}
+
+impl AddSemId for Publisher {
+    fn get_names(&self) -> Vec<String> {
+        let mut out = read_post_str_arr(&self.alternate_titles);
+        out.sort_by_key(|e| e.len());
+        out.push(self.display_name.clone());
+        sem_names(out, None)
+    }
+}
+
+impl AddSemId for Funder {
+    fn get_names(&self) -> Vec<String> {
+        let mut out = read_post_str_arr(&self.alternate_titles);
+        out.sort_by_key(|e| e.len());
+        out.push(self.display_name.clone());
+        sem_names(out, None)
     }
 }
 
@@ -136,7 +218,36 @@ impl AddSemId for Institution {
             .to_string(),
         );
         out.sort_by_key(|e| e.len());
-        out.iter().map(semantify).collect()
+        sem_names(out, id_slug("ror", &self.ror))
+    }
+}
+
+impl SemRegistry {
+    pub fn file_name<E: Entity>() -> String {
+        Self::file_name_of(E::NAME)
+    }
+
+    pub fn file_name_of(entity: &str) -> String {
+        format!("{entity}-semantic-registry.json")
+    }
+
+    ///the registry a build was made with, empty for entities without one
+    pub fn load<E>(stowage: &Stowage) -> Self
+    where
+        E: MarkedAttribute<SemanticIdMarker>,
+        MAA<E, SemanticIdMarker>: NamespacedEntity,
+    {
+        let ns_path = stowage.path_from_ns(<MAA<E, SemanticIdMarker> as NamespacedEntity>::NS);
+        Self::read(&ns_path.join(Self::file_name::<E>())).unwrap_or_default()
+    }
+
+    fn read(path: &Path) -> Option<Self> {
+        let reader = BufReader::new(open_data(path).ok()?);
+        serde_json::from_reader(reader).ok()
+    }
+
+    fn write(&self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer(BufWriter::new(File::create(path)?), self).map_err(io::Error::from)
     }
 }
 
@@ -158,9 +269,119 @@ pub fn semantify(s: &String) -> String {
     StackWordSet::new(s).to_words().join("-")
 }
 
+///slugs of the names that transliterate well, last one being the full name
+fn sem_names(names: Vec<String>, fallback: Option<String>) -> Vec<String> {
+    let full_ok = names.last().is_some_and(|e| transliterates_well(e));
+    let mut out: Vec<String> = names
+        .iter()
+        .filter(|e| transliterates_well(e))
+        .map(semantify)
+        .filter(|e| !e.is_empty())
+        .collect();
+    if !full_ok {
+        out.extend(fallback);
+    }
+    out
+}
+
+//scripts deunicode has no table for come out as placeholders
+fn transliterates_well(name: &str) -> bool {
+    let (mut letters, mut lost) = (0, 0);
+    for c in name.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        if !transliterate(&c.to_string())
+            .bytes()
+            .any(|b| b.is_ascii_lowercase())
+        {
+            lost += 1;
+        }
+    }
+    letters > 0 && lost * 3 <= letters
+}
+
+fn id_slug(kind: &str, id: &Option<String>) -> Option<String> {
+    let short = id.as_ref()?.rsplit('/').next()?.trim().to_lowercase();
+    (!short.is_empty()).then(|| format!("{kind}-{short}"))
+}
+
+fn sem_candidates<'a>(
+    names: &'a [String],
+    suffs: &'a [String],
+) -> impl Iterator<Item = String> + 'a {
+    let for_suff = names.last().cloned().unwrap_or_default();
+    names
+        .iter()
+        .cloned()
+        .chain(suffs.iter().map(move |e| for_suff.clone() + e))
+}
+
+///first candidate not yet taken, which is then taken
+fn free_sem_id(
+    names: &[String],
+    suffs: &[String],
+    sem_set: &mut HashSet<String>,
+) -> Option<String> {
+    let mut out = None;
+    for sid in sem_candidates(names, suffs) {
+        if sem_set.ifnotin(sid, |e| out = Some(e)) {
+            break;
+        }
+    }
+    out
+}
+
 fn get_suffs() -> Vec<String> {
     "23456789abcdefghijklmno"
         .chars()
         .map(|e| format!("-{e}").to_string())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(v: &[&str]) -> Vec<String> {
+        v.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn transliterated_names() {
+        let ror = || Some("ror-057zh3y96".to_string());
+        assert!(transliterates_well("Université de Montréal"));
+        assert_eq!(
+            sem_names(names(&["UdeM", "Université de Montréal"]), ror()),
+            names(&["udem", "universite-de-montreal"])
+        );
+        assert!(transliterates_well("東京大学"));
+        assert_eq!(
+            sem_names(names(&["東京大学"]), ror()),
+            names(&["dong-jing-da-xue"])
+        );
+        assert!(!transliterates_well("𓀀𓀁𓀂"));
+        assert_eq!(
+            sem_names(names(&["UT", "𓀀𓀁𓀂"]), ror()),
+            names(&["ut", "ror-057zh3y96"])
+        );
+        assert_eq!(
+            id_slug("ror", &Some("https://ror.org/057ZH3Y96".to_string())),
+            ror()
+        );
+        assert_eq!(id_slug("orcid", &None), None);
+    }
+
+    #[test]
+    fn colliding_names() {
+        let suffs = get_suffs();
+        let mut sem_set = HashSet::new();
+        let acme = sem_names(names(&["Acme Inc."]), None);
+        let taken = free_sem_id(&acme, &suffs, &mut sem_set);
+        assert_eq!(taken.as_deref(), Some("acme-inc"));
+        let second = free_sem_id(&acme, &suffs, &mut sem_set);
+        assert_eq!(second.as_deref(), Some("acme-inc-2"));
+        let with_alt = sem_names(names(&["ACME", "Acme Inc."]), None);
+        let third = free_sem_id(&with_alt, &suffs, &mut sem_set);
+        assert_eq!(third.as_deref(), Some("acme"));
+        assert!(!acme.contains(second.as_ref().unwrap()));
+    }
+}
@@ -1,6 +1,11 @@
-use std::{io, sync::Arc, thread};
+use std::{
+    fs::{copy, File},
+    io::{self, BufReader, BufWriter, Write},
+    sync::Arc,
+    thread,
+};
 
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::{
@@ -9,10 +14,13 @@ use crate::{
         field_id_parse, oa_id_parse, short_string_to_u64, BackendSelector, MarkedBackendLoader,
         ObjIter, ParsedId, Stowage, MAIN_NAME,
     },
-    csv_writers::{authors, fields, institutions, sources, subfields, topics, works},
-    env_consts::{FINAL_YEAR, START_YEAR},
+    config,
+    csv_writers::{
+        authors, fields, funders, institutions, publishers, sources, subfields, topics, works,
+        IngestState,
+    },
     oa_structs::{
-        post::{Authorship, Institution},
+        post::{Authorship, Institution, MergedId},
         IdStruct,
     },
     NameMarker, QuickestVBox,
@@ -25,7 +33,24 @@ use dmove::{
 pub type RawYear = u16;
 pub type YBT = [RawYear; N_PERS];
 pub const N_PERS: usize = 8;
-pub const POSSIBLE_YEAR_FILTERS: YBT = [START_YEAR, 2010, 2015, 2020, 2021, 2022, 2023, 2024];
+
+//ingest states of the csvs the id maps were built from
+const INGEST_MARKS_FILE: &str = "ingest-marks.json";
+//retired author ids and the ones they were merged into
+pub const AUTHOR_MERGES_FILE: &str = "author-merges";
+//longest chain of merges followed
+const MAX_MERGE_HOPS: usize = 16;
+const MAPPED_ENTITIES: [&str; 9] = [
+    works::C,
+    institutions::C,
+    sources::C,
+    publishers::C,
+    funders::C,
+    topics::C,
+    authors::C,
+    fields::C,
+    subfields::C,
+];
 
 pub struct Years {}
 pub struct YearInterface {}
@@ -64,18 +89,25 @@ impl SourceArea {
 
 impl YearInterface {
     pub fn reverse(y: ET<Years>) -> RawYear {
-        y as RawYear + START_YEAR
+        y as RawYear + config::get().start_year
     }
 
     pub fn parse(raw: RawYear) -> ET<Years> {
-        (raw - START_YEAR) as ET<Years>
+        (raw - config::get().start_year) as ET<Years>
     }
 
     pub fn iter() -> std::ops::Range<u8> {
-        0..((FINAL_YEAR - START_YEAR + 1) as u8)
+        let conf = config::get();
+        let span = (conf.final_year - conf.start_year + 1) as usize;
+        assert!(span <= Years::N, "{span} years do not fit the year ids");
+        0..(span as u8)
     }
 }
 
+pub fn possible_year_filters() -> YBT {
+    config::get().year_filters
+}
+
 impl Iterator for ShipIterator {
     type Item = Authorship;
 
@@ -91,7 +123,9 @@ impl Iterator for ShipIterator {
 
 impl Entity for Years {
     type T = u8;
-    const N: usize = (FINAL_YEAR - START_YEAR) as usize;
+    //only an upper bound of the year ids, the span comes from the pipeline config
+    //nothing is sized by this, year sized data goes by YearInterface::iter
+    const N: usize = u8::MAX as usize;
     const NAME: &'static str = "years";
 }
 
@@ -145,27 +179,41 @@ pub fn main(stowage: Stowage) -> io::Result<()> {
     for sw in vec![fields::C, subfields::C] {
         let sc = starc.clone();
         threads.push(thread::spawn(move || {
-            ids_from_atts::<IdStruct, _>(&sc, sw, sw, |e| field_id_parse(&e.id.unwrap()));
+            ids_from_atts::<IdStruct, _>(&sc, sw, sw, &HashMap::new(), |e| {
+                field_id_parse(&e.id.unwrap())
+            });
         }));
     }
 
+    let author_merges = Arc::new(write_merges(&starc, authors::C, AUTHOR_MERGES_FILE)?);
     for en in vec![
         works::C,
         institutions::C,
         sources::C,
+        publishers::C,
+        funders::C,
         // concepts::C,
         topics::C,
         authors::C,
     ] {
         let sc = starc.clone();
+        //stale rows of merged away entities are not mapped
+        let retired = if en == authors::C {
+            author_merges.clone()
+        } else {
+            Default::default()
+        };
         threads.push(thread::spawn(move || {
-            ids_from_atts::<IdStruct, _>(&sc, en, en, |e| e.get_parsed_id());
+            ids_from_atts::<IdStruct, _>(&sc, en, en, &retired, |e| e.get_parsed_id());
         }));
     }
 
-    ids_from_atts::<SourceArea, _>(&starc, "area-fields", sources::C, |e| e.raw_area_id());
+    let no_merges = HashMap::new();
+    ids_from_atts::<SourceArea, _>(&starc, "area-fields", sources::C, &no_merges, |e| {
+        e.raw_area_id()
+    });
 
-    ids_from_atts::<Institution, _>(&starc, "countries", institutions::C, |e| {
+    ids_from_atts::<Institution, _>(&starc, "countries", institutions::C, &no_merges, |e| {
         short_string_to_u64(&e.country_code.unwrap_or("".to_string()))
     });
 
@@ -177,6 +225,7 @@ pub fn main(stowage: Stowage) -> io::Result<()> {
         .mu_bu()
         .add_scaled_entity(works::atts::authorships, ship_n, true);
     starc.mu_bu().add_scaled_entity("qs", 5, true);
+    write_marks(&starc)?;
     starc.write_code()?;
     Ok(())
 }
@@ -185,21 +234,86 @@ pub fn iter_authorships(stowage: &Stowage) -> ShipIterator {
     ShipIterator::new(stowage)
 }
 
-fn ids_from_atts<T, F>(stowage: &Stowage, out_name: &str, parent_entity: &str, closure: F)
-where
+fn ids_from_atts<T, F>(
+    stowage: &Stowage,
+    out_name: &str,
+    parent_entity: &str,
+    retired: &HashMap<BigId, BigId>,
+    closure: F,
+) where
     T: DeserializeOwned,
     F: Fn(T) -> BigId,
 {
+    let objs = match mapped_until(stowage, out_name, parent_entity) {
+        Some(first_shard) => {
+            //keeps the published numbering, new ids are appended after it
+            println!("\n{out_name} extending published ids from shard {first_shard}");
+            copy(
+                stowage.published_file(out_name),
+                stowage.staged_file(out_name),
+            )
+            .unwrap();
+            stowage.read_csv_objs_from::<T>(parent_entity, MAIN_NAME, first_shard)
+        }
+        None => stowage.read_csv_objs::<T>(parent_entity, MAIN_NAME),
+    };
     entities_from_iter(
         stowage,
         out_name,
-        stowage
-            .read_csv_objs::<T>(parent_entity, MAIN_NAME)
-            .map(closure),
+        objs.map(closure).filter(|e| !retired.contains_key(e)),
         stowage.get_last_filter(out_name),
     )
 }
 
+///first shard the published id map has not seen, if the csvs were only updated since it was built
+fn mapped_until(stowage: &Stowage, name: &str, entity: &str) -> Option<usize> {
+    let state = stowage.ingest_state(entity)?;
+    let file = File::open(stowage.published_file(INGEST_MARKS_FILE)).ok()?;
+    let marks: HashMap<String, IngestState> = serde_json::from_reader(BufReader::new(file)).ok()?;
+    let mark = marks.get(entity)?;
+    if mark.base != state.base || !stowage.published_file(name).is_file() {
+        return None;
+    }
+    Some(mark.next_shard)
+}
+
+///redirects of retired ids straight to the surviving ones, also staged for a2
+fn write_merges(stowage: &Stowage, entity: &str, name: &str) -> io::Result<HashMap<BigId, BigId>> {
+    let raw: HashMap<BigId, BigId> = stowage
+        .read_merged_ids::<MergedId>(entity)
+        .filter_map(|e| e.parsed_pair())
+        .filter(|(from, to)| from != to)
+        .collect();
+    let mut out = HashMap::new();
+    for (from, to) in raw.iter() {
+        let mut target = *to;
+        for _ in 0..MAX_MERGE_HOPS {
+            match raw.get(&target) {
+                Some(next) if next != from => target = *next,
+                _ => break,
+            }
+        }
+        out.insert(*from, target);
+    }
+    println!("\n{entity} {} merged ids", out.len());
+    let mut file = BufWriter::new(File::create(stowage.staged_file(name))?);
+    for (from, to) in out.iter() {
+        file.write_all(&from.to_be_bytes())?;
+        file.write_all(&to.to_be_bytes())?;
+    }
+    file.flush()?;
+    Ok(out)
+}
+
+fn write_marks(stowage: &Stowage) -> io::Result<()> {
+    let marks: HashMap<&str, IngestState> = MAPPED_ENTITIES
+        .iter()
+        .filter_map(|e| Some((*e, stowage.ingest_state(e)?)))
+        .collect();
+    let file = BufWriter::new(File::create(stowage.staged_file(INGEST_MARKS_FILE))?);
+    serde_json::to_writer_pretty(file, &marks).map_err(io::Error::from)
+}
+
 fn entities_from_iter<I>(stowage: &Stowage, name: &str, iter: I, filter: Option<HashSet<BigId>>)
 where
     I: Iterator<Item = BigId>,
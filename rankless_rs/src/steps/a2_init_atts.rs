@@ -1,30 +1,38 @@
 use crate::{
     common::{
-        field_id_parse, init_empty_slice, oa_id_parse, short_string_to_u64, BeS, DoiMarker,
-        MainEntity, NameExtensionMarker, NameMarker, ParsedId, QuickestNumbered, Stowage,
-        MAIN_NAME, NET,
+        field_id_parse, init_empty_slice, oa_id_parse, short_string_to_u64, AlternativeNamesMarker,
+        BeS, DoiMarker, MainEntity, NameExtensionMarker, NameMarker, OrcidMarker, ParsedId,
+        QuickestNumbered, Stowage, ID_PREFIX, MAIN_NAME, NET,
     },
     csv_writers::{institutions, works},
     gen::a1_entity_mapping::{
-        AreaFields, Authors, Authorships, Countries, Fields, Institutions, Sources, Subfields,
-        Topics, Works,
+        AreaFields, Authors, Authorships, Countries, Fields, Funders, Institutions, Publishers,
+        Sources, Subfields, Topics, Works,
     },
     oa_structs::{
-        post::{read_post_str_arr, Authorship, Institution, Location, Source, SubField, Topic},
+        post::{
+            read_post_str_arr, Author, Authorship, Funder, Grant, Institution, Location, Publisher,
+            Source, SubField, Topic,
+        },
         FieldLike, Geo, Named, NamedEntity, ReferencedWork, Work, WorkTopic,
     },
-    steps::a1_entity_mapping::{iter_authorships, Qs, SourceArea, YearInterface, Years},
+    steps::a1_entity_mapping::{
+        iter_authorships, Qs, SourceArea, YearInterface, Years, AUTHOR_MERGES_FILE,
+    },
 };
 use dmove::{
     para::Worker, BigId, DiscoMapEntityBuilder, Entity, EntityImmutableMapperBackend,
     FixAttBuilder, InitEmpty, LoadedIdMap, MappableEntity, MetaIntegrator, NamespacedEntity,
-    UnsignedNumber, VarAttBuilder, ET,
+    UnsignedNumber, VarAttBuilder, VarSizedAttributeElement, ET,
 };
+use hashbrown::HashMap;
 use levenshtein::levenshtein;
 use serde::{de::DeserializeOwned, Deserialize};
 use std::{
     cmp::min,
-    io,
+    collections::BTreeSet,
+    fs::File,
+    io::{self, BufReader, Read},
     marker::PhantomData,
     sync::{Arc, Mutex},
     usize,
@@ -34,6 +42,8 @@ use tqdm::Iter;
 const MIN_TOPIC_SCORE: f64 = 0.7;
 const MIN_RATE: f64 = 0.8;
 const MIN_LEN: usize = 10;
+const PUBLISHER_PREFIX: &str = "P";
+const ORCID_PREFIX: &str = "https://orcid.org/";
 
 #[derive(Deserialize)]
 struct SourceQ {
@@ -49,6 +59,7 @@ struct ShipRelWriter {
     winf: Arc<LoadedIdMap<ET<Works>>>,
     ainf: Arc<LoadedIdMap<ET<Authors>>>,
     iinf: Arc<LoadedIdMap<ET<Institutions>>>,
+    author_merges: HashMap<BigId, BigId>,
 }
 
 struct WorkAttWriter {
@@ -135,13 +146,14 @@ where
 }
 
 impl Stowage {
-    fn add_source_qs<SIF, YIF>(&mut self, sources_interface: &SIF, years_interface: &YIF)
+    ///quartiles from an external table, that override the ones derived from citations
+    fn add_external_source_qs<SIF, YIF>(&mut self, sources_interface: &SIF, years_interface: &YIF)
     where
         YIF: EntityImmutableMapperBackend<Years>,
         SIF: EntityImmutableMapperBackend<Sources>,
     {
         let source_q_kv_iter = self
-            .read_csv_objs::<SourceQ>(Sources::NAME, Qs::NAME)
+            .read_optional_csv_objs::<SourceQ>(Sources::NAME, Qs::NAME)
             .filter_map(|yq| {
                 let source_oa_id = yq.get_parsed_id();
                 if let Some(sid) = sources_interface.get_via_immut(&source_oa_id) {
@@ -157,7 +169,7 @@ impl Stowage {
         self.add_iter_owned::<DiscoMapEntityBuilder<
             (<Sources as Entity>::T, <Years as Entity>::T),
             <Qs as Entity>::T,
-        >, _, _>(source_q_kv_iter, Some("source-year-qs"));
+        >, _, _>(source_q_kv_iter, Some("source-year-external-qs"));
     }
 
     fn add_work_atts(&self, winf: Arc<LoadedIdMap<ET<Works>>>) -> LoadedIdMap<ET<Works>> {
@@ -268,7 +280,54 @@ impl Stowage {
         SIF: EntityImmutableMapperBackend<Source> + Sync,
         TIF: EntityImmutableMapperBackend<Target> + Sync,
     {
-        let obj_worker = GenObjAttWorker::<'_, Source, Target, Vec<NET<Target>>, SIF, TIF>::new(
+        self.collected_object_property::<Vec<NET<Target>>, CsvObj, Source, Target, SIF, TIF>(
+            source_interface,
+            target_interface,
+            fatt_name,
+            sub,
+        )
+    }
+
+    ///like multi_object_property, but every target is stored once per source
+    fn distinct_object_property<CsvObj, Source, Target, SIF, TIF>(
+        &mut self,
+        source_interface: &SIF,
+        target_interface: &TIF,
+        fatt_name: &str,
+        sub: &str,
+    ) -> io::Result<usize>
+    where
+        CsvObj: ObjAttGetter<Target> + ParsedId + DeserializeOwned + Send,
+        Source: MainEntity,
+        Target: MainEntity,
+        SIF: EntityImmutableMapperBackend<Source> + Sync,
+        TIF: EntityImmutableMapperBackend<Target> + Sync,
+    {
+        self.collected_object_property::<BTreeSet<NET<Target>>, CsvObj, Source, Target, SIF, TIF>(
+            source_interface,
+            target_interface,
+            fatt_name,
+            sub,
+        )
+    }
+
+    fn collected_object_property<Stored, CsvObj, Source, Target, SIF, TIF>(
+        &mut self,
+        source_interface: &SIF,
+        target_interface: &TIF,
+        fatt_name: &str,
+        sub: &str,
+    ) -> io::Result<usize>
+    where
+        Stored:
+            StorableMarker<NET<Target>, FinalType = Box<[NET<Target>]>> + InitEmpty + Send + Sync,
+        CsvObj: ObjAttGetter<Target> + ParsedId + DeserializeOwned + Send,
+        Source: MainEntity,
+        Target: MainEntity,
+        SIF: EntityImmutableMapperBackend<Source> + Sync,
+        TIF: EntityImmutableMapperBackend<Target> + Sync,
+    {
+        let obj_worker = GenObjAttWorker::<'_, Source, Target, Stored, SIF, TIF>::new(
             source_interface,
             target_interface,
         );
@@ -333,6 +392,7 @@ impl ShipRelWriter {
             iinf: stowage
                 .get_entity_interface::<Institutions, QuickestNumbered>()
                 .into(),
+            author_merges: read_author_merges(stowage),
         }
     }
 
@@ -389,7 +449,10 @@ impl Worker<(usize, Authorship)> for ShipRelWriter {
         };
         self.w2ships.lock().unwrap()[w_ind].push(ET::<Authorships>::from_usize(i));
 
-        let aid_o = self.ainf.0.get(&oa_id_parse(&ship.author_id.unwrap()));
+        let raw_aid = oa_id_parse(&ship.author_id.unwrap());
+        //references to merged away authors go to the surviving one
+        let oa_aid = self.author_merges.get(&raw_aid).unwrap_or(&raw_aid);
+        let aid_o = self.ainf.0.get(oa_aid);
         if let Some(aid) = aid_o {
             self.ship2a.lock().unwrap()[i] = *aid;
         }
@@ -451,11 +514,23 @@ impl<'a> StrWriter<'a> {
     ) where
         CsvObj: DeserializeOwned + ParsedId + AttGetter<String, Marker> + Send,
         E: MainEntity + NamespacedEntity,
+    {
+        self.write_var_att::<E, CsvObj, Marker, String>(interface, prop_name);
+    }
+
+    fn write_var_att<E, CsvObj, Marker, T>(
+        &mut self,
+        interface: &BeS<QuickestNumbered, E>,
+        prop_name: &str,
+    ) where
+        CsvObj: DeserializeOwned + ParsedId + AttGetter<T, Marker> + Send,
+        E: MainEntity + NamespacedEntity,
+        T: InitEmpty + VarSizedAttributeElement + Sync + Send,
     {
         if self.main == "" {
             self.set_path(E::NAME, MAIN_NAME);
         }
-        let winit = GenWorker::new(DataAttWorker::<E, String, _>::new(interface));
+        let winit = GenWorker::new(DataAttWorker::<E, T, _>::new(interface));
         self.stowage
             .property_writer::<_, VarAttBuilder, CsvObj, _, _, _, _, Marker, _>(
                 winit, prop_name, self.main, self.sub,
@@ -541,6 +616,16 @@ impl<T> StorableMarker<T> for Vec<T> {
     }
 }
 
+impl<T: Ord> StorableMarker<T> for BTreeSet<T> {
+    type FinalType = Box<[T]>;
+    fn update(&mut self, other: T) {
+        self.insert(other);
+    }
+    fn finalize(self) -> Self::FinalType {
+        self.into_iter().collect()
+    }
+}
+
 impl ParsedId for SourceQ {
     fn get_parsed_id(&self) -> BigId {
         self.id
@@ -563,6 +648,36 @@ impl AttGetter<String, NameExtensionMarker> for Source {
     }
 }
 
+impl AttGetter<String, NameExtensionMarker> for Publisher {
+    fn get_att(&self) -> Option<String> {
+        post_ext_name(&self.alternate_titles)
+    }
+}
+
+impl AttGetter<String, NameExtensionMarker> for Funder {
+    fn get_att(&self) -> Option<String> {
+        post_ext_name(&self.alternate_titles)
+    }
+}
+
+impl AttGetter<Box<[String]>, AlternativeNamesMarker> for Author {
+    fn get_att(&self) -> Option<Box<[String]>> {
+        Some(read_post_str_arr(&self.display_name_alternatives).into())
+    }
+}
+
+impl AttGetter<String, OrcidMarker> for Author {
+    fn get_att(&self) -> Option<String> {
+        let orcid = self.orcid.as_ref()?;
+        Some(
+            orcid
+                .strip_prefix(ORCID_PREFIX)
+                .unwrap_or(orcid)
+                .to_string(),
+        )
+    }
+}
+
 impl AttGetter<String, NameExtensionMarker> for Institution {
     fn get_att(&self) -> Option<String> {
         post_ext_name(&self.display_name_acronyms)
@@ -608,6 +723,18 @@ impl Named for Source {
     }
 }
 
+impl Named for Publisher {
+    fn get_name(&self) -> String {
+        self.display_name.trim().to_string()
+    }
+}
+
+impl Named for Funder {
+    fn get_name(&self) -> String {
+        self.display_name.trim().to_string()
+    }
+}
+
 impl ObjAttGetter<Fields> for SubField {
     fn get_obj_att(&self) -> Option<<Fields as MappableEntity>::KeyType> {
         Some(field_id_parse(&self.field))
@@ -660,6 +787,24 @@ impl ObjAttGetter<Sources> for Location {
     }
 }
 
+impl ObjAttGetter<Publishers> for Source {
+    fn get_obj_att(&self) -> Option<<Publishers as MappableEntity>::KeyType> {
+        publisher_id_parse(&self.host_organization)
+    }
+}
+
+impl ObjAttGetter<Publishers> for Publisher {
+    fn get_obj_att(&self) -> Option<<Publishers as MappableEntity>::KeyType> {
+        publisher_id_parse(&self.parent_publisher)
+    }
+}
+
+impl ObjAttGetter<Funders> for Grant {
+    fn get_obj_att(&self) -> Option<<Funders as MappableEntity>::KeyType> {
+        self.funder.as_ref().map(|fid| oa_id_parse(fid))
+    }
+}
+
 impl<T, E> Iterator for BoxRoller<T, E>
 where
     T: StorableMarker<E>,
@@ -810,16 +955,25 @@ pub fn main(mut stowage: Stowage) -> io::Result<()> {
     let subfields_interface = str_writer.write_name::<FieldLike, Subfields>();
     let insts_interface = write_inst_names(&stowage);
     let sources_interface = str_writer.write_name::<Source, Sources>();
-    str_writer.write_name::<NamedEntity, Authors>();
+    let publishers_interface = str_writer.write_name::<Publisher, Publishers>();
+    let funders_interface = str_writer.write_name::<Funder, Funders>();
+    let authors_interface = str_writer.write_name::<NamedEntity, Authors>();
     let topics_interface = str_writer.write_name::<NamedEntity, Topics>();
     str_writer.write_name_ext::<Institution, Institutions>(&insts_interface);
     str_writer.write_name_ext::<Source, Sources>(&sources_interface);
+    str_writer.write_name_ext::<Publisher, Publishers>(&publishers_interface);
+    str_writer.write_name_ext::<Funder, Funders>(&funders_interface);
+    str_writer.write_meta::<Authors, Author, OrcidMarker>(&authors_interface, "author-orcids");
+    str_writer.write_var_att::<Authors, Author, AlternativeNamesMarker, _>(
+        &authors_interface,
+        "author-alternative-names",
+    );
 
     stowage.add_empty_name_ext::<Authors>();
     stowage.add_empty_name_ext::<Countries>();
     stowage.add_empty_name_ext::<Subfields>();
 
-    stowage.add_source_qs(&sources_interface, &YearInterface {});
+    stowage.add_external_source_qs(&sources_interface, &YearInterface {});
     stowage.object_property::<Institution, Institutions, _, _, _>(
         &insts_interface,
         &countries_interface,
@@ -835,6 +989,16 @@ pub fn main(mut stowage: Stowage) -> io::Result<()> {
         &subfields_interface,
         "topic-subfields",
     )?;
+    stowage.object_property::<Source, Sources, _, _, _>(
+        &sources_interface,
+        &publishers_interface,
+        "source-publishers",
+    )?;
+    stowage.object_property::<Publisher, Publishers, _, _, _>(
+        &publishers_interface,
+        &publishers_interface,
+        "publisher-parents",
+    )?;
     let area_fields_interface = stowage.get_entity_interface::<AreaFields, QuickestNumbered>();
     stowage.multi_object_property::<SourceArea, Sources, _, _, _>(
         &sources_interface,
@@ -860,6 +1024,13 @@ pub fn main(mut stowage: Stowage) -> io::Result<()> {
         "work-topics",
         works::atts::topics,
     )?;
+    //several grants of a work can come from the same funder
+    stowage.distinct_object_property::<Grant, Works, _, _, _>(
+        &works_interface,
+        &funders_interface,
+        "work-funders",
+        works::atts::grants,
+    )?;
 
     stowage.write_code()?;
     Ok(())
@@ -924,6 +1095,25 @@ fn write_inst_names(stowage: &Stowage) -> LoadedIdMap<NET<Institutions>> {
     interface
 }
 
+///surviving author of every retired author id, as mapped in a1
+fn read_author_merges(stowage: &Stowage) -> HashMap<BigId, BigId> {
+    let path = stowage.path_from_ns(Authors::NS).join(AUTHOR_MERGES_FILE);
+    let mut out = HashMap::new();
+    let mut file = match File::open(path) {
+        Ok(f) => BufReader::new(f),
+        Err(_) => return out,
+    };
+    let mut br = [0; 2 * std::mem::size_of::<BigId>()];
+    while file.read_exact(&mut br).is_ok() {
+        let (from, to) = br.split_at(std::mem::size_of::<BigId>());
+        out.insert(
+            BigId::from_be_bytes(from.try_into().unwrap()),
+            BigId::from_be_bytes(to.try_into().unwrap()),
+        );
+    }
+    out
+}
+
 fn get_name_name<E: Entity>() -> String {
     format!("{}-names", E::NAME)
 }
@@ -936,6 +1126,34 @@ fn iter_mboxa<T>(ba: Mutex<Box<[T]>>) -> std::vec::IntoIter<T> {
     ba.into_inner().unwrap().into_vec().into_iter()
 }
 
+//host organizations can also be institutions, only publisher ids are kept
+fn publisher_id_parse(oa_id: &Option<String>) -> Option<BigId> {
+    let short = oa_id.as_ref()?.strip_prefix(ID_PREFIX)?;
+    if short.starts_with(PUBLISHER_PREFIX) {
+        return Some(oa_id_parse(oa_id.as_ref().unwrap()));
+    }
+    None
+}
+
 fn post_ext_name(in_str: &Option<String>) -> Option<String> {
     Some(read_post_str_arr(in_str).join(" "))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_targets() {
+        let mut links: Vec<u32> = Vec::init_empty();
+        let mut funders: BTreeSet<u32> = BTreeSet::init_empty();
+        for t in [3, 1, 3] {
+            StorableMarker::<u32>::update(&mut links, t);
+            StorableMarker::<u32>::update(&mut funders, t);
+        }
+        let links = StorableMarker::<u32>::finalize(links);
+        let funders = StorableMarker::<u32>::finalize(funders);
+        assert_eq!(&links[..], &[3, 1, 3]);
+        assert_eq!(&funders[..], &[1, 3]);
+    }
+}
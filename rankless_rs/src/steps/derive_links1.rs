@@ -1,35 +1,48 @@
 use std::io;
 
+use hashbrown::HashMap;
+use tqdm::Iter;
+
 use crate::{
     common::{
         init_empty_slice, BackendSelector, BeS, MainWorkMarker, MarkedBackendLoader, QuickAttPair,
-        QuickestBox, QuickestVBox, ReadIter, Stowage,
+        QuickMap, QuickestBox, QuickestVBox, ReadIter, Stowage,
     },
     gen::{
-        a1_entity_mapping::{Countries, Institutions, Works},
+        a1_entity_mapping::{Countries, Institutions, Publishers, Sources, Subfields, Works},
         a2_init_atts::{
-            AuthorshipAuthor, AuthorshipInstitutions, InstCountries, TopicSubfields,
-            WorkAuthorships, WorkReferences, WorkSources, WorkTopics, WorkYears,
+            AuthorshipAuthor, AuthorshipInstitutions, InstCountries, PublisherParents,
+            SourcePublishers, SourceYearExternalQs, TopicSubfields, WorkAuthorships, WorkFunders,
+            WorkReferences, WorkSources, WorkTopics, WorkYears,
         },
     },
     ReadFixIter,
 };
 
 use dmove::{
-    BackendLoading, ByteArrayInterface, ByteFixArrayInterface, CompactEntity, Entity,
-    EntityImmutableRefMapperBackend, Link, MappableEntity, NamespacedEntity, UnsignedNumber,
-    VarAttBuilder, VarSizedAttributeElement, VariableSizeAttribute, VattArrPair, ET,
+    BackendLoading, ByteArrayInterface, ByteFixArrayInterface, CompactEntity,
+    DiscoMapEntityBuilder, Entity, EntityImmutableRefMapperBackend, Link, MappableEntity,
+    NamespacedEntity, UnsignedNumber, VarAttBuilder, VarSizedAttributeElement,
+    VariableSizeAttribute, VattArrPair, ET,
 };
 
-use super::a1_entity_mapping::{YearInterface, N_PERS, POSSIBLE_YEAR_FILTERS};
+use super::a1_entity_mapping::{possible_year_filters, Qs, YearInterface, Years, N_PERS};
+
+//citations counted for the impact of a source in a year, as in the journal impact factor
+const Q_CITE_WINDOW: u8 = 2;
+//source-years with fewer papers get no quartile
+const MIN_Q_PAPERS: u32 = 10;
+
+type SourceYear = (ET<Sources>, ET<Years>);
 
 pub struct WorkPeriods {}
 pub struct CountryInsts {}
 
 impl WorkPeriods {
     pub fn from_year(year: u16) -> ET<Self> {
+        let year_filters = possible_year_filters();
         for i in (0..N_PERS).rev() {
-            if year >= POSSIBLE_YEAR_FILTERS[i] {
+            if year >= year_filters[i] {
                 return i as u8;
             }
         }
@@ -186,10 +199,137 @@ where
     collapse_links_meta::<Link1, Link2, QuickestVBox, _>(stowage, name, cloj)
 }
 
+///publishers of the sources of each work, together with all their parent publishers
+fn roll_up_publishers(stowage: &mut Stowage, name: &str) {
+    let w_sources = stowage.get_entity_interface::<WorkSources, ReadIter>();
+    let s_publishers = stowage.get_entity_interface::<SourcePublishers, QuickestBox>();
+    let p_parents = stowage.get_entity_interface::<PublisherParents, QuickestBox>();
+    let iter = w_sources.map(|sources| {
+        let mut ends: Vec<ET<Publishers>> = Vec::new();
+        for sid in sources.iter() {
+            let mut pid = s_publishers[sid.to_usize()];
+            //a parent seen already means the rest of the line is in too (or there is a cycle)
+            while (pid != 0) & !ends.contains(&pid) {
+                ends.push(pid);
+                pid = p_parents[pid.to_usize()];
+            }
+        }
+        ends.into_boxed_slice()
+    });
+    stowage.add_iter_owned::<VarAttBuilder, _, _>(iter, Some(name));
+    stowage.declare_link::<Works, Publishers>(name);
+}
+
+///quartile of each source in each year, by citations per paper within its main subfield
+fn add_source_qs(stowage: &mut Stowage, name: &str) {
+    let w_years = stowage.get_entity_interface::<WorkYears, QuickestBox>();
+    let w_sources = stowage.get_entity_interface::<WorkSources, QuickAttPair>();
+    let t_subfields = stowage.get_entity_interface::<TopicSubfields, QuickestBox>();
+    let w_topics = stowage.get_entity_interface::<WorkTopics, ReadIter>();
+
+    let mut papers = HashMap::<SourceYear, u32>::new();
+    let mut sf_counts = init_empty_slice::<Sources, HashMap<ET<Subfields>, u32>>();
+    for (wid, topics) in w_topics.enumerate().tqdm().desc(Some("source papers")) {
+        for sid in w_sources.get(&wid).unwrap().iter() {
+            *papers.entry((*sid, w_years[wid])).or_insert(0) += 1;
+            for tid in topics.iter() {
+                *sf_counts[sid.to_usize()]
+                    .entry(t_subfields[tid.to_usize()])
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut cites = HashMap::<SourceYear, u32>::new();
+    let w_refs = stowage.get_entity_interface::<WorkReferences, ReadIter>();
+    for (wid, refs) in w_refs.enumerate().tqdm().desc(Some("source citations")) {
+        let citing_year = w_years[wid];
+        for rid in refs.iter() {
+            let ref_year = w_years[rid.to_usize()];
+            if !in_q_window(citing_year, ref_year) {
+                continue;
+            }
+            for sid in w_sources.get(&rid.to_usize()).unwrap().iter() {
+                *cites.entry((*sid, ref_year)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let main_sfs: Vec<ET<Subfields>> = sf_counts.iter().map(main_subfield).collect();
+    let mut qs = derived_qs(papers, &cites, &main_sfs);
+
+    let external = stowage.get_entity_interface::<SourceYearExternalQs, QuickMap>();
+    println!(
+        "derived {} source-year quartiles, {} from the external table",
+        qs.len(),
+        external.len()
+    );
+    override_qs(&mut qs, external.into_iter());
+    stowage.add_iter_owned::<DiscoMapEntityBuilder<SourceYear, ET<Qs>>, _, _>(
+        qs.into_iter(),
+        Some(name),
+    );
+}
+
+//citations of a work count for its source until Q_CITE_WINDOW years after it
+fn in_q_window(citing_year: ET<Years>, ref_year: ET<Years>) -> bool {
+    (citing_year >= ref_year) && (citing_year - ref_year <= Q_CITE_WINDOW)
+}
+
+///the subfield most topics of the papers of a source are in, 0 without any
+fn main_subfield(counts: &HashMap<ET<Subfields>, u32>) -> ET<Subfields> {
+    counts
+        .iter()
+        .filter(|(sf, _)| **sf != 0)
+        .max_by_key(|(sf, c)| (**c, std::cmp::Reverse(**sf)))
+        .map(|(sf, _)| *sf)
+        .unwrap_or(0)
+}
+
+///quartile of the rank-th of n sources, the first being the most cited
+fn rank_quartile(rank: usize, n: usize) -> ET<Qs> {
+    (1 + rank * 4 / n) as ET<Qs>
+}
+
+///ranks the sources of each main subfield and year by citations per paper
+fn derived_qs(
+    papers: HashMap<SourceYear, u32>,
+    cites: &HashMap<SourceYear, u32>,
+    main_sfs: &[ET<Subfields>],
+) -> HashMap<SourceYear, ET<Qs>> {
+    let mut groups = HashMap::<(ET<Subfields>, ET<Years>), Vec<(f64, ET<Sources>)>>::new();
+    for ((sid, year), n) in papers.into_iter() {
+        let sf = main_sfs[sid.to_usize()];
+        if (n < MIN_Q_PAPERS) || (sf == 0) || (sid == 0) {
+            continue;
+        }
+        let impact = *cites.get(&(sid, year)).unwrap_or(&0) as f64 / n as f64;
+        groups.entry((sf, year)).or_default().push((impact, sid));
+    }
+    let mut qs = HashMap::<SourceYear, ET<Qs>>::new();
+    for ((_, year), mut group) in groups.into_iter() {
+        group.sort_by(|l, r| r.0.total_cmp(&l.0).then(l.1.cmp(&r.1)));
+        let n = group.len();
+        for (rank, (_, sid)) in group.into_iter().enumerate() {
+            qs.insert((sid, year), rank_quartile(rank, n));
+        }
+    }
+    qs
+}
+
+///quartiles of the external table win over derived ones, 0 there is no quartile
+fn override_qs<I>(qs: &mut HashMap<SourceYear, ET<Qs>>, external: I)
+where
+    I: Iterator<Item = (SourceYear, ET<Qs>)>,
+{
+    qs.extend(external.filter(|(_, q)| *q != 0));
+}
+
 pub fn main(mut stowage: Stowage) -> io::Result<()> {
     invert_read_multi_link_to_work::<WorkReferences>(&mut stowage, "works-citing");
     invert_read_multi_link_to_work::<WorkTopics>(&mut stowage, "topic-works");
     invert_read_multi_link_to_work::<WorkSources>(&mut stowage, "source-works");
+    invert_read_multi_link_to_work::<WorkFunders>(&mut stowage, "funder-works");
 
     collapse_links::<WorkTopics, TopicSubfields>(&mut stowage, "work-subfields");
     collapse_links::<WorkAuthorships, AuthorshipAuthor>(&mut stowage, "work-authors");
@@ -197,6 +337,8 @@ pub fn main(mut stowage: Stowage) -> io::Result<()> {
         &mut stowage,
         "work-institutions",
     );
+    roll_up_publishers(&mut stowage, "work-publishers");
+    add_source_qs(&mut stowage, "source-year-qs");
     stowage.write_code()?;
     Ok(())
 }
@@ -228,3 +370,69 @@ where
     stowage.add_iter_owned::<VarAttBuilder, _, _>(collapsed.into_iter(), Some(name));
     stowage.declare_link::<Link1::Source, Link2::Target>(name);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quartiles() {
+        assert_eq!(rank_quartile(0, 1), 1);
+        let of_6: Vec<ET<Qs>> = (0..6).map(|r| rank_quartile(r, 6)).collect();
+        assert_eq!(of_6, vec![1, 1, 2, 3, 3, 4]);
+        let of_8: Vec<ET<Qs>> = (0..8).map(|r| rank_quartile(r, 8)).collect();
+        assert_eq!(of_8, vec![1, 1, 2, 2, 3, 3, 4, 4]);
+    }
+
+    #[test]
+    fn main_subfields() {
+        assert_eq!(main_subfield(&HashMap::new()), 0);
+        assert_eq!(main_subfield(&HashMap::from([(0, 9), (3, 2), (2, 5)])), 2);
+        //ties go to the lower subfield id
+        assert_eq!(main_subfield(&HashMap::from([(4, 5), (2, 5), (3, 1)])), 2);
+    }
+
+    #[test]
+    fn cite_window() {
+        assert!(in_q_window(10, 10));
+        assert!(in_q_window(12, 10));
+        assert!(!in_q_window(13, 10));
+        assert!(!in_q_window(9, 10));
+    }
+
+    #[test]
+    fn source_quartiles() {
+        let year = 5;
+        let mut papers = HashMap::new();
+        let mut cites = HashMap::new();
+        //sources 1-5 in subfield 1, 2 and 3 tied, 5 with too few papers
+        for (sid, n, c) in [
+            (1, 10, 50),
+            (2, 10, 20),
+            (3, 20, 40),
+            (4, 10, 0),
+            (5, 9, 90),
+        ] {
+            papers.insert((sid, year), n);
+            cites.insert((sid, year), c);
+        }
+        //a lone source of subfield 2, and one without a main subfield
+        papers.insert((6, year), MIN_Q_PAPERS);
+        papers.insert((7, year), MIN_Q_PAPERS);
+        let main_sfs = [0, 1, 1, 1, 1, 1, 2, 0];
+        let mut qs = derived_qs(papers, &cites, &main_sfs);
+        let q_of = |qs: &HashMap<SourceYear, ET<Qs>>, sid| *qs.get(&(sid, year)).unwrap_or(&0);
+        assert_eq!(
+            (1..8).map(|sid| q_of(&qs, sid)).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 0, 1, 0]
+        );
+        override_qs(
+            &mut qs,
+            [((4, year), 1), ((5, year), 2), ((1, year), 0)].into_iter(),
+        );
+        assert_eq!(
+            (1..8).map(|sid| q_of(&qs, sid)).collect::<Vec<_>>(),
+            vec![1, 2, 3, 1, 2, 1, 0]
+        );
+    }
+}
@@ -6,8 +6,8 @@ use crate::{
     common::{QuickMap, Stowage},
     gen::{
         a1_entity_mapping::Works,
-        a2_init_atts::{InstCountries, SourceYearQs, WorkSources, WorkYears},
-        derive_links1::{WorkAuthors, WorkInstitutions, WorkSubfields, WorksCiting},
+        a2_init_atts::{InstCountries, WorkSources, WorkYears},
+        derive_links1::{SourceYearQs, WorkAuthors, WorkInstitutions, WorkSubfields, WorksCiting},
     },
     steps::derive_links1::{collapse_links, invert_read_multi_link_to_work},
     CiteCountMarker, QuickestBox, ReadIter,
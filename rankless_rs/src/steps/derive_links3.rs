@@ -7,7 +7,8 @@ use tqdm::Iter;
 use crate::{
     common::MainWorkMarker,
     gen::{
-        a1_entity_mapping::{Authors, Institutions, Sources, Subfields, Topics, Works},
+        a1_entity_mapping::{Authors, Funders, Institutions, Sources, Subfields, Topics, Works},
+        derive_links1::WorkPublishers,
         derive_links2::WorkCountries,
     },
     steps::derive_links1::invert_read_multi_link_to_work,
@@ -37,7 +38,9 @@ pub fn main(mut stowage: Stowage) -> std::io::Result<()> {
     work_count::<Authors>(&mut stowage);
     work_count::<Subfields>(&mut stowage);
     work_count::<Topics>(&mut stowage);
+    work_count::<Funders>(&mut stowage);
     invert_read_multi_link_to_work::<WorkCountries>(&mut stowage, "country-works");
+    invert_read_multi_link_to_work::<WorkPublishers>(&mut stowage, "publisher-works");
     let interface = stowage.get_entity_interface::<MAA<Works, CiteCountMarker>, ReadFixIter>();
     let hit_papers = interface.tqdm().enumerate().filter_map(|(i, e)| {
         if e.to_usize() >= MIN_FOR_HIT {
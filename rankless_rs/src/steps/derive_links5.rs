@@ -25,20 +25,23 @@ use crate::{
         Top3CitingSfMarker, Top3JournalMarker, Top3PaperSfMarker, Top3PaperTopicMarker, WorkLoader,
         YearlyCitationsMarker, YearlyPapersMarker,
     },
-    env_consts::{FINAL_YEAR, START_YEAR},
+    config,
     gen::{
-        a1_entity_mapping::{Authors, Countries, Institutions, Sources, Subfields, Topics, Works},
+        a1_entity_mapping::{
+            Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Topics,
+            Works,
+        },
         a2_init_atts::{
-            AuthorshipAuthor, AuthorshipInstitutions, CountriesNames, InstCountries, SourceYearQs,
+            AuthorshipAuthor, AuthorshipInstitutions, CountriesNames, InstCountries,
             WorkAuthorships, WorkSources, WorkTopics, WorkYears,
         },
-        derive_links1::{WorkInstitutions, WorkSubfields},
+        derive_links1::{SourceYearQs, WorkInstitutions, WorkSubfields},
         derive_links2::{WorkCountries, WorkTopSource},
     },
     make_interface_struct,
     oa_structs::{
-        post::{Institution, Source},
-        FieldLike, NamedEntity,
+        post::{Author, Funder, Institution, Publisher, Source},
+        FieldLike,
     },
     semantic_ids::{semantify, SemCsvObj},
     steps::a1_entity_mapping::Qs,
@@ -49,8 +52,6 @@ use super::a1_entity_mapping::Years;
 
 pub const N_RELS: usize = 8;
 pub const ERA_SIZE: usize = 11;
-pub const MAX_YEAR: usize = (FINAL_YEAR - START_YEAR) as usize;
-pub const MIN_YEAR: usize = MAX_YEAR - ERA_SIZE + 1;
 
 type YT = ET<Years>;
 type IT = ET<Institutions>;
@@ -151,6 +152,8 @@ impl Stowage {
         self.write_semantic_id::<Authors>();
         self.write_semantic_id::<Institutions>();
         self.write_semantic_id::<Sources>();
+        self.write_semantic_id::<Publishers>();
+        self.write_semantic_id::<Funders>();
         self.write_semantic_id::<Subfields>();
         let citer = self
             .get_entity_interface::<CountriesNames, ReadIter>()
@@ -609,7 +612,7 @@ impl InitEmpty for InstRelation {
 }
 
 impl SemCsvObj for Authors {
-    type CsvObj = NamedEntity;
+    type CsvObj = Author;
 }
 
 impl SemCsvObj for Subfields {
@@ -620,12 +623,24 @@ impl SemCsvObj for Sources {
     type CsvObj = Source;
 }
 
+impl SemCsvObj for Publishers {
+    type CsvObj = Publisher;
+}
+
+impl SemCsvObj for Funders {
+    type CsvObj = Funder;
+}
+
 impl SemCsvObj for Institutions {
     type CsvObj = Institution;
 }
 
 impl IRelAdder for Sources {}
 
+impl IRelAdder for Publishers {}
+
+impl IRelAdder for Funders {}
+
 impl IRelAdder for Subfields {}
 
 impl IRelAdder for Topics {}
@@ -666,6 +681,8 @@ pub fn main(stowage: Stowage) -> io::Result<()> {
     cdm.send(CiteDeriver::author_paths);
     cdm.send(CiteDeriver::cite_count::<Institutions>);
     cdm.send(CiteDeriver::cite_count::<Countries>);
+    cdm.send(CiteDeriver::cite_count::<Publishers>);
+    cdm.send(CiteDeriver::cite_count::<Funders>);
     cdm.send(CiteDeriver::cite_count::<Subfields>);
     cdm.send(CiteDeriver::cite_count::<Topics>);
     cdm.send(|dm| dm.stowage.write_all_sem_ids());
@@ -685,10 +702,17 @@ where
     );
 }
 
+pub fn max_year() -> usize {
+    let conf = config::get();
+    (conf.final_year - conf.start_year) as usize
+}
+
 fn inc_year(era_rec: &mut EraRec, year: YT) {
     let yi = year.to_usize();
-    if (yi >= MIN_YEAR) & (yi <= MAX_YEAR) {
-        era_rec[yi - MIN_YEAR] += 1
+    let max_year = max_year();
+    let min_year = max_year + 1 - ERA_SIZE;
+    if (yi >= min_year) & (yi <= max_year) {
+        era_rec[yi - min_year] += 1
     }
 }
 
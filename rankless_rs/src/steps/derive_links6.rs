@@ -0,0 +1,340 @@
+use std::io;
+
+use dmove::{
+    DowncastingBuilder, Entity, FixAttBuilder, Link, MarkedAttribute, NamespacedEntity,
+    UnsignedNumber, VariableSizeAttribute, MAA,
+};
+use tqdm::Iter;
+
+use crate::{
+    common::{
+        FiveYearCiteCountMarker, MainWorkMarker, NormalizedImpactMarker, TopDecileShareMarker,
+        TwoYearCiteCountMarker,
+    },
+    gen::{
+        a1_entity_mapping::{
+            Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Works,
+        },
+        a2_init_atts::WorkYears,
+        derive_links1::{WorkSubfields, WorksCiting},
+        derive_links2::WorkCitingCounts,
+    },
+    QuickestBox, ReadIter, Stowage,
+};
+
+//share of the most cited papers of a subfield and year counted as top papers
+const TOP_SHARE: f64 = 0.1;
+const YEAR_SLOTS: usize = u8::MAX as usize + 1;
+///citations are counted in windows of this many years after publication
+pub const CITE_WINDOWS: [u8; 2] = [2, 5];
+
+///mean citations and top paper thresholds of every subfield and publication year
+struct Baselines {
+    means: Vec<f64>,
+    tops: Vec<u32>,
+}
+
+struct WorkImpacts {
+    //NaN for works without a subfield, that can not be normalized
+    impacts: Vec<f64>,
+    //share of the subfields of the work it is a top paper in
+    tops: Vec<f32>,
+}
+
+///citations within each of the CITE_WINDOWS
+struct WindowedCounts([Vec<u32>; 2]);
+
+impl Baselines {
+    fn new<I, S>(wsubfields: I, years: &[u8], cites: &[u32], n_subfields: usize) -> Self
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let mut cells: Vec<Vec<u32>> = vec![Vec::new(); n_subfields * YEAR_SLOTS];
+        for (w, sfs) in wsubfields.enumerate() {
+            for sf in sfs.as_ref().iter() {
+                cells[cell(*sf, years[w])].push(cites[w]);
+            }
+        }
+        let (mut means, mut tops) = (Vec::new(), Vec::new());
+        for mut cell_cites in cells.into_iter() {
+            cell_cites.sort();
+            let n = cell_cites.len();
+            let sum: u64 = cell_cites.iter().map(|e| *e as u64).sum();
+            means.push(sum as f64 / n.max(1) as f64);
+            let top_ind = ((n as f64) * (1.0 - TOP_SHARE)) as usize;
+            tops.push(*cell_cites.get(top_ind).unwrap_or(&u32::MAX));
+        }
+        Self { means, tops }
+    }
+
+    ///citations relative to the mean of the cells of the work, and the share of them it tops
+    fn normalize(&self, cites: u32, cells: &[usize]) -> (f64, f32) {
+        if cells.is_empty() {
+            return (f64::NAN, 0.0);
+        }
+        let expected = cells.iter().map(|e| self.means[*e]).sum::<f64>() / cells.len() as f64;
+        //a paper in a subfield and year nobody cited is as cited as expected
+        let impact = if expected > 0.0 {
+            cites as f64 / expected
+        } else {
+            1.0
+        };
+        let n_top = cells
+            .iter()
+            .filter(|e| cites > 0 && cites >= self.tops[**e])
+            .count();
+        (impact, n_top as f32 / cells.len() as f32)
+    }
+}
+
+impl WorkImpacts {
+    fn new(stowage: &Stowage) -> Self {
+        let years = stowage.get_entity_interface::<WorkYears, QuickestBox>();
+        let cites = stowage.get_entity_interface::<WorkCitingCounts, QuickestBox>();
+        let wsubfields = || stowage.get_entity_interface::<WorkSubfields, ReadIter>();
+        let baselines = Baselines::new(wsubfields().tqdm(), &years, &cites, Subfields::N + 1);
+        let (impacts, tops) = work_impacts(wsubfields().tqdm(), &years, &cites, &baselines);
+        Self { impacts, tops }
+    }
+
+    fn aggregate<E>(&self, stowage: &mut Stowage)
+    where
+        E: MarkedAttribute<MainWorkMarker>,
+        MAA<E, MainWorkMarker>: Entity<T = Box<[<Works as Entity>::T]>>
+            + Link<Target = Works>
+            + NamespacedEntity
+            + VariableSizeAttribute,
+    {
+        let interface = stowage.get_entity_interface::<MAA<E, MainWorkMarker>, ReadIter>();
+        let (mut means, mut shares) = (Vec::new(), Vec::new());
+        for works in interface {
+            let (mean, share) = mean_impact_and_share(
+                works.iter().map(|e| e.to_usize()),
+                &self.impacts,
+                &self.tops,
+            );
+            means.push(mean);
+            shares.push(share);
+        }
+        stowage.declare_iter::<FixAttBuilder, _, f64, E, NormalizedImpactMarker>(
+            means.into_iter(),
+            &format!("{}-normalized-impact", E::NAME),
+        );
+        stowage.declare_iter::<FixAttBuilder, _, f64, E, TopDecileShareMarker>(
+            shares.into_iter(),
+            &format!("{}-top-decile-share", E::NAME),
+        );
+    }
+}
+
+impl WindowedCounts {
+    fn new(stowage: &Stowage) -> Self {
+        let years = stowage.get_entity_interface::<WorkYears, QuickestBox>();
+        let wciting = stowage.get_entity_interface::<WorksCiting, ReadIter>();
+        let mut out = [Vec::new(), Vec::new()];
+        for (w, citing) in wciting.enumerate().tqdm() {
+            for (counts, window) in out.iter_mut().zip(CITE_WINDOWS) {
+                let citing_years = citing.iter().map(|c| years[c.to_usize()]);
+                counts.push(cites_within(years[w], citing_years, window));
+            }
+        }
+        Self(out)
+    }
+
+    fn declare(&self, stowage: &mut Stowage) {
+        let [w2, w5] = &self.0;
+        stowage.declare_iter::<DowncastingBuilder, _, _, Works, TwoYearCiteCountMarker>(
+            w2.iter().map(|e| *e as usize),
+            "work-2y-citing-counts",
+        );
+        stowage.declare_iter::<DowncastingBuilder, _, _, Works, FiveYearCiteCountMarker>(
+            w5.iter().map(|e| *e as usize),
+            "work-5y-citing-counts",
+        );
+    }
+
+    fn aggregate<E>(&self, stowage: &mut Stowage)
+    where
+        E: MarkedAttribute<MainWorkMarker>,
+        MAA<E, MainWorkMarker>: Entity<T = Box<[<Works as Entity>::T]>>
+            + Link<Target = Works>
+            + NamespacedEntity
+            + VariableSizeAttribute,
+    {
+        let interface = stowage.get_entity_interface::<MAA<E, MainWorkMarker>, ReadIter>();
+        let mut window_sums = [Vec::new(), Vec::new()];
+        for works in interface {
+            for (wsums, wcounts) in window_sums.iter_mut().zip(self.0.iter()) {
+                wsums.push(works.iter().map(|e| wcounts[e.to_usize()] as usize).sum());
+            }
+        }
+        let [sums2, sums5] = window_sums;
+        stowage.declare_iter::<DowncastingBuilder, _, _, E, TwoYearCiteCountMarker>(
+            sums2.into_iter(),
+            &format!("{}-2y-cite-count", E::NAME),
+        );
+        stowage.declare_iter::<DowncastingBuilder, _, _, E, FiveYearCiteCountMarker>(
+            sums5.into_iter(),
+            &format!("{}-5y-cite-count", E::NAME),
+        );
+    }
+}
+
+pub fn main(mut stowage: Stowage) -> io::Result<()> {
+    let wimps = WorkImpacts::new(&stowage);
+    stowage.declare_iter::<FixAttBuilder, _, f64, Works, NormalizedImpactMarker>(
+        wimps
+            .impacts
+            .iter()
+            .map(|e| if e.is_nan() { 0.0 } else { *e }),
+        "work-normalized-impacts",
+    );
+    wimps.aggregate::<Authors>(&mut stowage);
+    wimps.aggregate::<Institutions>(&mut stowage);
+    wimps.aggregate::<Sources>(&mut stowage);
+    wimps.aggregate::<Subfields>(&mut stowage);
+    wimps.aggregate::<Countries>(&mut stowage);
+    wimps.aggregate::<Publishers>(&mut stowage);
+    wimps.aggregate::<Funders>(&mut stowage);
+    drop(wimps);
+
+    let windowed = WindowedCounts::new(&stowage);
+    windowed.declare(&mut stowage);
+    windowed.aggregate::<Authors>(&mut stowage);
+    windowed.aggregate::<Institutions>(&mut stowage);
+    windowed.aggregate::<Sources>(&mut stowage);
+    windowed.aggregate::<Subfields>(&mut stowage);
+    windowed.aggregate::<Countries>(&mut stowage);
+    windowed.aggregate::<Publishers>(&mut stowage);
+    windowed.aggregate::<Funders>(&mut stowage);
+    stowage.write_code()?;
+    Ok(())
+}
+
+fn work_impacts<I, S>(
+    wsubfields: I,
+    years: &[u8],
+    cites: &[u32],
+    baselines: &Baselines,
+) -> (Vec<f64>, Vec<f32>)
+where
+    I: Iterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    wsubfields
+        .enumerate()
+        .map(|(w, sfs)| {
+            let cells: Vec<usize> = sfs.as_ref().iter().map(|sf| cell(*sf, years[w])).collect();
+            baselines.normalize(cites[w], &cells)
+        })
+        .unzip()
+}
+
+///mean normalized impact and top paper share of the works that could be normalized
+fn mean_impact_and_share<I>(works: I, impacts: &[f64], tops: &[f32]) -> (f64, f64)
+where
+    I: Iterator<Item = usize>,
+{
+    let (mut n, mut impact_sum, mut top_sum) = (0, 0.0, 0.0);
+    for w in works {
+        if impacts[w].is_nan() {
+            continue;
+        }
+        n += 1;
+        impact_sum += impacts[w];
+        top_sum += tops[w] as f64;
+    }
+    (impact_sum / n.max(1) as f64, top_sum / n.max(1) as f64)
+}
+
+///citations from the publication year up to window years after it
+fn cites_within<I>(year: u8, citing_years: I, window: u8) -> u32
+where
+    I: Iterator<Item = u8>,
+{
+    let last_year = year.saturating_add(window);
+    citing_years.filter(|y| *y <= last_year).count() as u32
+}
+
+fn cell(subfield: u8, year: u8) -> usize {
+    subfield as usize * YEAR_SLOTS + year as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBFIELDS: usize = 3;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    //work 0 is the null work, without subfields or year
+    fn toy() -> (Vec<Vec<u8>>, Vec<u8>, Vec<u32>) {
+        let mut wsubfields = vec![vec![]];
+        let mut years = vec![0];
+        //ten works of subfield 1 in year 4, tied at the 90th percentile
+        let mut cites = vec![0];
+        for c in [0, 1, 1, 2, 2, 2, 3, 8, 8, 8] {
+            wsubfields.push(vec![1]);
+            years.push(4);
+            cites.push(c);
+        }
+        //one work in two subfields, the other alone in its cell
+        wsubfields.push(vec![1, 2]);
+        years.push(4);
+        cites.push(14);
+        (wsubfields, years, cites)
+    }
+
+    #[test]
+    fn baselines() {
+        let (wsubfields, years, cites) = toy();
+        let bl = Baselines::new(wsubfields.iter(), &years, &cites, SUBFIELDS);
+        assert!(close(bl.means[cell(1, 4)], 49.0 / 11.0));
+        assert_eq!(bl.tops[cell(1, 4)], 8);
+        assert_eq!(bl.means[cell(2, 4)], 14.0);
+        assert_eq!(bl.tops[cell(2, 4)], 14);
+        //the null work is in no cell
+        assert_eq!(bl.means[cell(0, 0)], 0.0);
+        assert_eq!(bl.tops[cell(0, 0)], u32::MAX);
+    }
+
+    #[test]
+    fn impacts_and_shares() {
+        let (wsubfields, years, cites) = toy();
+        let bl = Baselines::new(wsubfields.iter(), &years, &cites, SUBFIELDS);
+        let (impacts, tops) = work_impacts(wsubfields.iter(), &years, &cites, &bl);
+        assert!(impacts[0].is_nan());
+        assert_eq!(tops[0], 0.0);
+        assert!(close(impacts[2], 11.0 / 49.0));
+        //every work tied at the threshold is a top paper
+        assert_eq!(&tops[1..11], &[0., 0., 0., 0., 0., 0., 0., 1., 1., 1.]);
+        assert!(close(impacts[11], 14.0 / ((49.0 / 11.0 + 14.0) / 2.0)));
+        assert_eq!(tops[11], 1.0);
+
+        let (mean, share) = mean_impact_and_share([0, 1, 8].into_iter(), &impacts, &tops);
+        assert!(close(mean, (0.0 + 88.0 / 49.0) / 2.0));
+        assert_eq!(share, 0.5);
+        assert_eq!(
+            mean_impact_and_share([0].into_iter(), &impacts, &tops),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn window_boundaries() {
+        for window in CITE_WINDOWS {
+            let y = 10;
+            assert_eq!(cites_within(y, [y + window].into_iter(), window), 1);
+            assert_eq!(cites_within(y, [y + window + 1].into_iter(), window), 0);
+            assert_eq!(
+                cites_within(y, [y, y + 1, y + window + 3].into_iter(), window),
+                2
+            );
+        }
+        assert_eq!(cites_within(u8::MAX - 1, [u8::MAX].into_iter(), 5), 1);
+    }
+}
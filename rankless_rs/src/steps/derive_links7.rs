@@ -0,0 +1,200 @@
+use std::{io, sync::Mutex};
+
+use dmove::{
+    para::Worker, Entity, FixAttBuilder, Link, MarkedAttribute, NamespacedEntity, UnsignedNumber,
+    VariableSizeAttribute, MAA,
+};
+use tqdm::Iter;
+
+use crate::{
+    common::{MainWorkMarker, PageRankMarker},
+    gen::{
+        a1_entity_mapping::{
+            Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Works,
+        },
+        derive_links1::WorksCiting,
+    },
+    ReadIter, Stowage,
+};
+
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 40;
+//summed absolute change of the ranks, that sum to 1
+const TOLERANCE: f64 = 1e-7;
+const CHUNK_SIZE: usize = 10_000;
+
+type CitingChunk = (usize, Vec<Box<[u32]>>);
+
+///one pull step of the ranks over the citing lists, that are read from disk in chunks
+struct RankPull<'a> {
+    //rank of a work divided by the number of works it cites
+    shares: &'a [f64],
+    //rank every work gets, from teleporting and from works citing nothing
+    base: f64,
+    ranks: Mutex<Vec<f64>>,
+}
+
+impl Worker<CitingChunk> for RankPull<'_> {
+    fn proc(&self, input: CitingChunk) {
+        let (start, chunk) = input;
+        let pulled: Vec<f64> = chunk
+            .iter()
+            .map(|citing| {
+                let s: f64 = citing.iter().map(|c| self.shares[c.to_usize()]).sum();
+                self.base + DAMPING * s
+            })
+            .collect();
+        self.ranks.lock().unwrap()[start..(start + pulled.len())].copy_from_slice(&pulled);
+    }
+}
+
+pub fn main(mut stowage: Stowage) -> io::Result<()> {
+    let ranks = page_ranks(&stowage);
+    stowage.declare_iter::<FixAttBuilder, _, f64, Works, PageRankMarker>(
+        ranks.iter().copied(),
+        "work-pageranks",
+    );
+    aggregate::<Authors>(&mut stowage, &ranks);
+    aggregate::<Institutions>(&mut stowage, &ranks);
+    aggregate::<Sources>(&mut stowage, &ranks);
+    aggregate::<Subfields>(&mut stowage, &ranks);
+    aggregate::<Countries>(&mut stowage, &ranks);
+    aggregate::<Publishers>(&mut stowage, &ranks);
+    aggregate::<Funders>(&mut stowage, &ranks);
+    stowage.write_code()?;
+    Ok(())
+}
+
+///pagerank of the works, scaled so that the mean work has 1
+fn page_ranks(stowage: &Stowage) -> Vec<f64> {
+    let citing = stowage.get_entity_interface::<WorksCiting, ReadIter>();
+    let out_degrees = out_degrees(<WorksCiting as Entity>::N, citing.tqdm());
+    iterate_ranks(&out_degrees, || citing_chunks(stowage))
+}
+
+///number of works each work cites, from the lists of works citing them
+fn out_degrees<I>(n: usize, citing: I) -> Vec<u32>
+where
+    I: Iterator<Item = Box<[u32]>>,
+{
+    let mut out = vec![0_u32; n];
+    for cs in citing {
+        for c in cs.iter() {
+            out[c.to_usize()] += 1;
+        }
+    }
+    out
+}
+
+///power iteration over the citing lists, that are read again in every round
+fn iterate_ranks<F, I>(out_degrees: &[u32], chunks: F) -> Vec<f64>
+where
+    F: Fn() -> I,
+    I: Iterator<Item = CitingChunk>,
+{
+    let n = out_degrees.len();
+    let nf = n as f64;
+    let mut ranks = vec![1.0 / nf; n];
+    for i in 0..MAX_ITERATIONS {
+        let mut dangling = 0.0;
+        let shares: Vec<f64> = ranks
+            .iter()
+            .zip(out_degrees.iter())
+            .map(|(r, d)| {
+                if *d == 0 {
+                    dangling += r;
+                    0.0
+                } else {
+                    r / *d as f64
+                }
+            })
+            .collect();
+        let pull = RankPull {
+            shares: &shares,
+            base: (1.0 - DAMPING + DAMPING * dangling) / nf,
+            ranks: Mutex::new(vec![0.0; n]),
+        }
+        .para(chunks());
+        let new_ranks = pull.ranks.into_inner().unwrap();
+        let delta: f64 = new_ranks
+            .iter()
+            .zip(ranks.iter())
+            .map(|(l, r)| (l - r).abs())
+            .sum();
+        ranks = new_ranks;
+        println!("pagerank iteration {i}: change {delta}");
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+    ranks.into_iter().map(|e| e * nf).collect()
+}
+
+fn citing_chunks(stowage: &Stowage) -> impl Iterator<Item = CitingChunk> {
+    let mut interface = stowage.get_entity_interface::<WorksCiting, ReadIter>();
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let chunk: Vec<Box<[u32]>> = interface.by_ref().take(CHUNK_SIZE).collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        let out = (start, chunk);
+        start += CHUNK_SIZE;
+        Some(out)
+    })
+}
+
+fn aggregate<E>(stowage: &mut Stowage, ranks: &[f64])
+where
+    E: MarkedAttribute<MainWorkMarker>,
+    MAA<E, MainWorkMarker>: Entity<T = Box<[<Works as Entity>::T]>>
+        + Link<Target = Works>
+        + NamespacedEntity
+        + VariableSizeAttribute,
+{
+    let interface = stowage.get_entity_interface::<MAA<E, MainWorkMarker>, ReadIter>();
+    let sums: Vec<f64> = interface
+        .map(|works| works.iter().map(|w| ranks[w.to_usize()]).sum())
+        .collect();
+    stowage.declare_iter::<FixAttBuilder, _, f64, E, PageRankMarker>(
+        sums.into_iter(),
+        &format!("{}-pagerank", E::NAME),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranks_of(citing: Vec<Vec<u32>>) -> Vec<f64> {
+        let citing: Vec<Box<[u32]>> = citing.into_iter().map(|e| e.into()).collect();
+        let out_degrees = out_degrees(citing.len(), citing.iter().cloned());
+        iterate_ranks(&out_degrees, || {
+            //chunks of one work, that the workers pull in any order
+            citing
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(i, e)| (i, vec![e]))
+        })
+    }
+
+    #[test]
+    fn cycle() {
+        for r in ranks_of(vec![vec![2], vec![0], vec![1]]) {
+            assert!((r - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn star() {
+        //1, 2 and 3 cite 0, that cites nothing, so 0 has r0 = 3.55 r of the others,
+        //and r0 + 3 r = 1 before the scaling by 4
+        let ranks = ranks_of(vec![vec![1, 2, 3], vec![], vec![], vec![]]);
+        let r = 4.0 / 6.55;
+        assert!((ranks[0] - 3.55 * r).abs() < 1e-6, "{ranks:?}");
+        for leaf in &ranks[1..] {
+            assert!((leaf - r).abs() < 1e-6, "{ranks:?}");
+        }
+    }
+}
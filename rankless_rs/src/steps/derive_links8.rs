@@ -0,0 +1,243 @@
+use std::io;
+
+use dmove::{
+    CompactEntity, DowncastingBuilder, Entity, FixAttBuilder, Link, MarkedAttribute,
+    NamespacedEntity, UnsignedNumber, VarAttBuilder, VariableSizeAttribute, ET, MAA,
+};
+use hashbrown::HashMap;
+use tqdm::Iter;
+
+use crate::{
+    common::{
+        ClusteringMarker, CollabComponentMarker, CollabDegreeMarker, CollabWeightsMarker,
+        CollaboratorsMarker, MainWorkMarker, QuickAttPair, WeightedCollabDegreeMarker,
+    },
+    gen::{
+        a1_entity_mapping::{Authors, Institutions, Works},
+        derive_links1::{WorkAuthors, WorkInstitutions},
+    },
+    ReadIter, Stowage,
+};
+
+//works with larger teams are left out, they would link everyone on them
+const MAX_TEAM: usize = 50;
+
+///weighted collaboration graph of an entity, neighbours sorted by id
+struct CollabGraph<T> {
+    neighbours: Vec<Box<[T]>>,
+    //number of shared works with each neighbour
+    weights: Vec<Box<[u32]>>,
+}
+
+impl<T> CollabGraph<T>
+where
+    T: UnsignedNumber + Ord,
+{
+    fn new<E, L>(stowage: &Stowage) -> Self
+    where
+        E: MarkedAttribute<MainWorkMarker> + Entity<T = T>,
+        MAA<E, MainWorkMarker>:
+            Entity<T = Box<[ET<Works>]>> + NamespacedEntity + VariableSizeAttribute,
+        L: Link<Source = Works, Target = E>
+            + Entity<T = Box<[T]>>
+            + NamespacedEntity
+            + CompactEntity
+            + VariableSizeAttribute,
+    {
+        let members = stowage.get_entity_interface::<L, QuickAttPair>();
+        let ework_iter = stowage.get_entity_interface::<MAA<E, MainWorkMarker>, ReadIter>();
+        let (mut neighbours, mut weights) = (Vec::new(), Vec::new());
+        for (i, works) in ework_iter.enumerate().tqdm().desc(Some(E::NAME)) {
+            let mut counts = HashMap::<T, u32>::new();
+            for wid in works.iter() {
+                let team = members.get(&wid.to_usize()).unwrap();
+                if team.len() > MAX_TEAM {
+                    continue;
+                }
+                let mut seen = Vec::new();
+                for m in team.iter() {
+                    let mu = m.to_usize();
+                    if (mu == 0) || (mu == i) || seen.contains(m) {
+                        continue;
+                    }
+                    seen.push(*m);
+                    *counts.entry(*m).or_insert(0) += 1;
+                }
+            }
+            let mut pairs: Vec<(T, u32)> = counts.into_iter().collect();
+            pairs.sort();
+            neighbours.push(pairs.iter().map(|e| e.0).collect());
+            weights.push(pairs.iter().map(|e| e.1).collect());
+        }
+        Self {
+            neighbours,
+            weights,
+        }
+    }
+
+    ///share of the pairs of neighbours that are neighbours themselves
+    fn clustering(&self) -> Vec<f64> {
+        self.neighbours
+            .iter()
+            .tqdm()
+            .map(|ns| {
+                let k = ns.len();
+                if k < 2 {
+                    return 0.0;
+                }
+                let links: usize = ns
+                    .iter()
+                    .map(|n| sorted_overlap(ns, &self.neighbours[n.to_usize()]))
+                    .sum();
+                links as f64 / (k * (k - 1)) as f64
+            })
+            .collect()
+    }
+
+    ///0 for entities without collaborators, the rest numbered from 1 by decreasing size
+    fn components(&self) -> Vec<u32> {
+        let mut parents: Vec<usize> = (0..self.neighbours.len()).collect();
+        for (i, ns) in self.neighbours.iter().enumerate() {
+            for n in ns.iter() {
+                let (ri, rn) = (
+                    find_root(&mut parents, i),
+                    find_root(&mut parents, n.to_usize()),
+                );
+                if ri != rn {
+                    parents[ri.max(rn)] = ri.min(rn);
+                }
+            }
+        }
+        let mut sizes = HashMap::<usize, usize>::new();
+        for i in 0..parents.len() {
+            if !self.neighbours[i].is_empty() {
+                *sizes.entry(find_root(&mut parents, i)).or_insert(0) += 1;
+            }
+        }
+        let mut by_size: Vec<(usize, usize)> = sizes.into_iter().collect();
+        by_size.sort_by_key(|(root, size)| (usize::MAX - size, *root));
+        let comp_ids: HashMap<usize, u32> = by_size
+            .into_iter()
+            .enumerate()
+            .map(|(ci, (root, _))| (root, ci as u32 + 1))
+            .collect();
+        (0..parents.len())
+            .map(|i| *comp_ids.get(&find_root(&mut parents, i)).unwrap_or(&0))
+            .collect()
+    }
+
+    fn write<E>(self, stowage: &mut Stowage)
+    where
+        E: Entity<T = T>,
+    {
+        let name = E::NAME;
+        stowage.declare_iter::<DowncastingBuilder, _, _, E, CollabDegreeMarker>(
+            self.neighbours.iter().map(|e| e.len()),
+            &format!("{name}-collab-degree"),
+        );
+        stowage.declare_iter::<DowncastingBuilder, _, _, E, WeightedCollabDegreeMarker>(
+            self.weights
+                .iter()
+                .map(|e| e.iter().map(|w| *w as usize).sum()),
+            &format!("{name}-weighted-collab-degree"),
+        );
+        stowage.declare_iter::<FixAttBuilder, _, f64, E, ClusteringMarker>(
+            self.clustering().into_iter(),
+            &format!("{name}-clustering"),
+        );
+        stowage.declare_iter::<DowncastingBuilder, _, _, E, CollabComponentMarker>(
+            self.components().into_iter().map(|e| e as usize),
+            &format!("{name}-collab-component"),
+        );
+        let links_name = format!("{name}-collaborators");
+        stowage
+            .add_iter_owned::<VarAttBuilder, _, _>(self.neighbours.into_iter(), Some(&links_name));
+        stowage.declare_link::<E, E>(&links_name);
+        stowage.declare::<E, CollaboratorsMarker>(&links_name);
+        stowage.declare_iter::<VarAttBuilder, _, _, E, CollabWeightsMarker>(
+            self.weights.into_iter(),
+            &format!("{name}-collab-weights"),
+        );
+    }
+}
+
+pub fn main(mut stowage: Stowage) -> io::Result<()> {
+    CollabGraph::new::<Authors, WorkAuthors>(&stowage).write::<Authors>(&mut stowage);
+    CollabGraph::new::<Institutions, WorkInstitutions>(&stowage)
+        .write::<Institutions>(&mut stowage);
+    stowage.write_code()?;
+    Ok(())
+}
+
+fn sorted_overlap<T: Ord>(l: &[T], r: &[T]) -> usize {
+    let (mut li, mut ri, mut n) = (0, 0, 0);
+    while (li < l.len()) && (ri < r.len()) {
+        match l[li].cmp(&r[ri]) {
+            std::cmp::Ordering::Less => li += 1,
+            std::cmp::Ordering::Greater => ri += 1,
+            std::cmp::Ordering::Equal => {
+                n += 1;
+                li += 1;
+                ri += 1;
+            }
+        }
+    }
+    n
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //triangle 1-2-3 with 4 hanging on 3, two separate pairs, 7 alone
+    fn toy_graph() -> CollabGraph<u32> {
+        let neighbours: Vec<Box<[u32]>> = vec![
+            vec![].into(),
+            vec![2, 3].into(),
+            vec![1, 3].into(),
+            vec![1, 2, 4].into(),
+            vec![3].into(),
+            vec![6].into(),
+            vec![5].into(),
+            vec![].into(),
+            vec![9].into(),
+            vec![8].into(),
+        ];
+        let weights = neighbours.iter().map(|e| vec![1; e.len()].into()).collect();
+        CollabGraph {
+            neighbours,
+            weights,
+        }
+    }
+
+    #[test]
+    fn clustering() {
+        let cc = toy_graph().clustering();
+        let expected = [0.0, 1.0, 1.0, 1.0 / 3.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        assert_eq!(cc.len(), expected.len());
+        for (l, r) in cc.iter().zip(expected.iter()) {
+            assert!((l - r).abs() < 1e-12, "{cc:?}");
+        }
+    }
+
+    #[test]
+    fn components() {
+        assert_eq!(toy_graph().components(), vec![0, 1, 1, 1, 1, 2, 2, 0, 3, 3]);
+    }
+
+    #[test]
+    fn overlap() {
+        assert_eq!(sorted_overlap(&[1, 3, 5, 7], &[2, 3, 4, 7, 9]), 2);
+        assert_eq!(sorted_overlap::<u32>(&[], &[1, 2]), 0);
+        assert_eq!(sorted_overlap(&[4, 5], &[1, 2]), 0);
+        assert_eq!(sorted_overlap(&[1, 2, 3], &[1, 2, 3]), 3);
+    }
+}
@@ -0,0 +1,179 @@
+use std::io;
+
+use dmove::{
+    ByteFixArrayInterface, Entity, FixAttBuilder, MarkedAttribute, NamespacedEntity,
+    UnsignedNumber, ET, MAA,
+};
+use tqdm::Iter;
+
+use crate::{
+    common::{
+        CitSubfieldsArrayMarker, CitSubfieldsConcentrationMarker, CitSubfieldsDiversityMarker,
+        QuickAttPair, RefSubfieldsArrayMarker, RefSubfieldsConcentrationMarker,
+        RefSubfieldsDiversityMarker,
+    },
+    gen::{
+        a1_entity_mapping::{
+            Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Topics,
+        },
+        a2_init_atts::WorkReferences,
+        derive_links1::WorkSubfields,
+    },
+    ReadFixIter, ReadIter, Stowage,
+};
+
+//subfield ids index the distributions directly, slot 0 is the null subfield
+const SF_SLOTS: usize = Subfields::N;
+
+///distances of subfields, one minus the cosine similarity of how often they are cited together
+struct SubfieldDistances(Vec<[f64; SF_SLOTS]>);
+
+impl SubfieldDistances {
+    fn new(stowage: &Stowage) -> Self {
+        let mut cocited = vec![[0_u64; SF_SLOTS]; SF_SLOTS];
+        let w_subfields = stowage.get_entity_interface::<WorkSubfields, QuickAttPair>();
+        let w_refs = stowage.get_entity_interface::<WorkReferences, ReadIter>();
+        for refs in w_refs.tqdm().desc(Some("subfield co-citations")) {
+            let mut sfs: Vec<usize> = Vec::new();
+            for rid in refs.iter() {
+                for sf in w_subfields.get(&rid.to_usize()).unwrap().iter() {
+                    let sfu = sf.to_usize();
+                    if (sfu != 0) && !sfs.contains(&sfu) {
+                        sfs.push(sfu);
+                    }
+                }
+            }
+            for l in sfs.iter() {
+                for r in sfs.iter() {
+                    cocited[*l][*r] += 1;
+                }
+            }
+        }
+        let mut distances = vec![[1.0; SF_SLOTS]; SF_SLOTS];
+        for l in 0..SF_SLOTS {
+            for r in 0..SF_SLOTS {
+                let norm = ((cocited[l][l] * cocited[r][r]) as f64).sqrt();
+                if norm > 0.0 {
+                    distances[l][r] = 1.0 - cocited[l][r] as f64 / norm;
+                }
+            }
+            distances[l][l] = 0.0;
+        }
+        Self(distances)
+    }
+
+    ///herfindahl index and rao-stirling diversity of a subfield distribution
+    fn metrics<I>(&self, dist: I) -> (f64, f64)
+    where
+        I: IntoIterator,
+        I::Item: UnsignedNumber,
+    {
+        let counts: Vec<(usize, f64)> = dist
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| c.to_usize() > 0)
+            .map(|(sf, c)| (sf, c.to_usize() as f64))
+            .collect();
+        let total: f64 = counts.iter().map(|e| e.1).sum();
+        if total == 0.0 {
+            return (0.0, 0.0);
+        }
+        let shares: Vec<(usize, f64)> = counts.into_iter().map(|(sf, c)| (sf, c / total)).collect();
+        let hhi = shares.iter().map(|(_, p)| p * p).sum();
+        let mut rao_stirling = 0.0;
+        for (l, pl) in shares.iter() {
+            for (r, pr) in shares.iter() {
+                rao_stirling += self.0[*l][*r] * pl * pr;
+            }
+        }
+        (hhi, rao_stirling)
+    }
+
+    fn write_all<E>(&self, stowage: &mut Stowage)
+    where
+        E: MarkedAttribute<RefSubfieldsArrayMarker> + MarkedAttribute<CitSubfieldsArrayMarker>,
+        MAA<E, RefSubfieldsArrayMarker>: NamespacedEntity,
+        MAA<E, CitSubfieldsArrayMarker>: NamespacedEntity,
+        ET<MAA<E, RefSubfieldsArrayMarker>>:
+            ByteFixArrayInterface + IntoIterator<Item: UnsignedNumber>,
+        ET<MAA<E, CitSubfieldsArrayMarker>>:
+            ByteFixArrayInterface + IntoIterator<Item: UnsignedNumber>,
+    {
+        self.write::<E, RefSubfieldsArrayMarker, RefSubfieldsConcentrationMarker, RefSubfieldsDiversityMarker>(stowage, "ref");
+        self.write::<E, CitSubfieldsArrayMarker, CitSubfieldsConcentrationMarker, CitSubfieldsDiversityMarker>(stowage, "cit");
+    }
+
+    fn write<E, DistMark, ConcMark, DivMark>(&self, stowage: &mut Stowage, pref: &str)
+    where
+        E: MarkedAttribute<DistMark>,
+        MAA<E, DistMark>: NamespacedEntity,
+        ET<MAA<E, DistMark>>: ByteFixArrayInterface + IntoIterator<Item: UnsignedNumber>,
+    {
+        let (concs, divs): (Vec<f64>, Vec<f64>) = stowage
+            .get_entity_interface::<MAA<E, DistMark>, ReadFixIter>()
+            .map(|dist| self.metrics(dist))
+            .unzip();
+        let name = E::NAME;
+        stowage.declare_iter::<FixAttBuilder, _, f64, E, ConcMark>(
+            concs.into_iter(),
+            &format!("{name}-{pref}-sf-conc"),
+        );
+        stowage.declare_iter::<FixAttBuilder, _, f64, E, DivMark>(
+            divs.into_iter(),
+            &format!("{name}-{pref}-sf-diversity"),
+        );
+    }
+}
+
+pub fn main(mut stowage: Stowage) -> io::Result<()> {
+    let distances = SubfieldDistances::new(&stowage);
+    distances.write_all::<Authors>(&mut stowage);
+    distances.write_all::<Institutions>(&mut stowage);
+    distances.write_all::<Sources>(&mut stowage);
+    distances.write_all::<Subfields>(&mut stowage);
+    distances.write_all::<Countries>(&mut stowage);
+    distances.write_all::<Publishers>(&mut stowage);
+    distances.write_all::<Funders>(&mut stowage);
+    distances.write_all::<Topics>(&mut stowage);
+    stowage.write_code()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distances() -> SubfieldDistances {
+        let mut distances = vec![[1.0; SF_SLOTS]; SF_SLOTS];
+        for (i, row) in distances.iter_mut().enumerate() {
+            row[i] = 0.0;
+        }
+        distances[1][2] = 0.5;
+        distances[2][1] = 0.5;
+        SubfieldDistances(distances)
+    }
+
+    fn dist(counts: &[(usize, u32)]) -> [u32; SF_SLOTS] {
+        let mut out = [0; SF_SLOTS];
+        for (sf, c) in counts {
+            out[*sf] = *c;
+        }
+        out
+    }
+
+    #[test]
+    fn metrics() {
+        let d = distances();
+        //shares 1/4, 1/4, 1/2, the null slot is left out
+        let (hhi, rs) = d.metrics(dist(&[(0, 5), (1, 1), (2, 1), (3, 2)]));
+        assert!((hhi - 0.375).abs() < 1e-12);
+        //2 * (0.5 * 1/4 * 1/4 + 1/4 * 1/2 + 1/4 * 1/2)
+        assert!((rs - 0.5625).abs() < 1e-12);
+
+        let (hhi, rs) = d.metrics(dist(&[(SF_SLOTS - 1, 3)]));
+        assert_eq!((hhi, rs), (1.0, 0.0));
+        assert_eq!(d.metrics(dist(&[(0, 4)])), (0.0, 0.0));
+        assert_eq!(d.metrics(dist(&[])), (0.0, 0.0));
+    }
+}
@@ -8,7 +8,7 @@ use axum::{
     routing::get,
     Json, Router,
 };
-use dmove::{para::set_and_notify, Entity, NamespacedEntity, UnsignedNumber, ET};
+use dmove::{para::set_and_notify, Entity, NamespacedEntity, UnsignedNumber, VarBox, ET, MAA};
 use hashbrown::HashMap;
 use kd_tree::{KdPoint, KdTree};
 use rand::seq::SliceRandom;
@@ -29,16 +29,23 @@ use tower_http::{
 
 use muwo_search::SearchEngine;
 use rankless_rs::{
-    common::{MainEntity, NET},
-    gen::a1_entity_mapping::{Authors, Countries, Institutions, Sources, Subfields, Topics},
+    common::{CollabWeightsMarker, CollaboratorsMarker, MainEntity, NET},
+    data_namespaces,
+    gen::a1_entity_mapping::{
+        Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Topics,
+    },
     steps::{
         a1_entity_mapping::{Qs, RawYear, YearInterface, Years},
         derive_links5::{EraRec, InstRelation},
+        derive_links6::CITE_WINDOWS,
     },
-    Stowage,
+    OrcidMarker, ReadIter, SemRegistry, SemanticIdMarker, Stowage,
 };
 use rankless_trees::{
-    interfacing::{Getters, NodeInterfaces, RootInterfaceable, RootInterfaces},
+    interfacing::{
+        CollabInterfaceable, CollabInterfaces, Getters, NodeInterfaces, RootInterfaceable,
+        RootInterfaces, VarAtt,
+    },
     io::{TreeQ, TreeResponse, TreeRunManager},
     AttributeLabelUnion,
 };
@@ -46,6 +53,7 @@ use rankless_trees::{
 const PORT: u16 = 3038;
 const N_THREADS: usize = 16;
 const UPPER_LIMIT: u32 = u32::MAX;
+const TOP_COLLABORATORS: usize = 20;
 const ETYPE_ENC: [&str; 6] = [
     Institutions::NAME,
     Authors::NAME,
@@ -55,7 +63,15 @@ const ETYPE_ENC: [&str; 6] = [
     Topics::NAME,
 ];
 
-type InstTrm = TreeRunManager<(Institutions, Authors, Subfields, Countries, Sources)>;
+type InstTrm = TreeRunManager<(
+    Institutions,
+    Authors,
+    Subfields,
+    Countries,
+    Sources,
+    Publishers,
+    Funders,
+)>;
 type Coords = [f64; 2];
 
 #[derive(Deserialize)]
@@ -63,6 +79,12 @@ struct BasicQ {
     q: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct SliceQ {
+    window: Option<u8>,
+    pagerank: Option<bool>,
+}
+
 #[derive(Serialize)]
 struct ViewResult {
     #[serde(flatten)]
@@ -107,12 +129,15 @@ struct EntityDescription {
 struct NameState {
     engine: SearchEngine,
     responses: Box<[SearchResult]>,
+    //response indices ranked by citations within each of the CITE_WINDOWS, then by pagerank
+    alt_ranks: [Box<[usize]>; 3],
     exts: Box<[ResultExtension]>,
     prep_exts: Box<[PreAttResultExtension]>,
     means: Box<Coords>,
     vars: Box<Coords>,
     pub semantic_id_map: HashMap<String, SemVal>,
     pub oa_id_map: HashMap<usize, usize>,
+    pub redirect_map: HashMap<String, usize>,
     query_tree: KdTree<KDItem>,
 }
 
@@ -145,6 +170,52 @@ struct SearchResult {
     dm_id: usize,
     papers: u32,
     citations: u32,
+    #[serde(rename = "citations2y")]
+    citations_2y: u32,
+    #[serde(rename = "citations5y")]
+    citations_5y: u32,
+    #[serde(rename = "normalizedImpact")]
+    normalized_impact: f64,
+    #[serde(rename = "topDecileShare")]
+    top_decile_share: f64,
+    pagerank: f64,
+    #[serde(rename = "refConcentration")]
+    ref_concentration: f64,
+    #[serde(rename = "citConcentration")]
+    cit_concentration: f64,
+    #[serde(rename = "refDiversity")]
+    ref_diversity: f64,
+    #[serde(rename = "citDiversity")]
+    cit_diversity: f64,
+}
+
+#[derive(Serialize)]
+struct CollabResult {
+    degree: usize,
+    #[serde(rename = "weightedDegree")]
+    weighted_degree: usize,
+    clustering: f64,
+    component: usize,
+    collaborators: Vec<Collaborator>,
+}
+
+#[derive(Serialize)]
+struct Collaborator {
+    name: String,
+    #[serde(rename = "semanticId")]
+    semantic_id: String,
+    #[serde(rename = "sharedWorks")]
+    shared_works: u32,
+}
+
+struct CollabState<E>
+where
+    E: CollabInterfaceable + VarAtt<CollaboratorsMarker> + VarAtt<CollabWeightsMarker>,
+{
+    ifs: CollabInterfaces<E>,
+    collaborators: VarBox<<E as VarAtt<CollaboratorsMarker>>::VT>,
+    weights: VarBox<<E as VarAtt<CollabWeightsMarker>>::VT>,
+    semantic_id_map: HashMap<String, usize>,
 }
 
 #[derive(Serialize, Clone)]
@@ -195,7 +266,7 @@ macro_rules! i_fil {
     };
 }
 
-i_fil!(Countries, Subfields);
+i_fil!(Countries, Subfields, Publishers, Funders);
 
 impl PrepFilter for Authors {
     fn filter_sr(sr: &SearchResult, _gets: &Getters) -> bool {
@@ -267,6 +338,15 @@ impl SearchResult {
             semantic_id,
             papers: entif.wcounts[i].to_usize() as u32,
             citations: entif.ccounts[i].to_usize() as u32,
+            citations_2y: entif.ccounts_2y[i].to_usize() as u32,
+            citations_5y: entif.ccounts_5y[i].to_usize() as u32,
+            normalized_impact: entif.impact[i],
+            top_decile_share: entif.top_decile_share[i],
+            pagerank: entif.pagerank[i],
+            ref_concentration: entif.ref_sfc[i],
+            cit_concentration: entif.cit_sfc[i],
+            ref_diversity: entif.ref_sfd[i],
+            cit_diversity: entif.cit_sfd[i],
             dm_id: i,
         }
     }
@@ -379,6 +459,7 @@ impl NameState {
     fn new<E>(entif: &RootInterfaces<E>, gets: &Getters) -> Self
     where
         E: RootInterfaceable + PrepFilter,
+        MAA<E, SemanticIdMarker>: NamespacedEntity,
     {
         let responses = Self::get_resps(entif, gets);
         let engine = SearchEngine::new(responses.iter().map(|e| e.full_name.clone()));
@@ -420,13 +501,25 @@ impl NameState {
 
         let query_tree = tree_from_iter(kdt_base);
 
+        let alt_ranks = [
+            Self::alt_rank(&responses, |e| f64::from(e.citations_2y)),
+            Self::alt_rank(&responses, |e| f64::from(e.citations_5y)),
+            Self::alt_rank(&responses, |e| e.pagerank),
+        ];
+
         Self {
             engine: engine.into(),
+            alt_ranks,
             exts: ResultExtension::from_resps(&responses, entif),
             prep_exts: PreAttResultExtension::from_resps(&responses, entif),
             responses,
             semantic_id_map,
             oa_id_map,
+            redirect_map: SemRegistry::load::<E>(&gets.stowage)
+                .redirects
+                .into_iter()
+                .map(|(k, v)| (k, v as usize))
+                .collect(),
             query_tree,
             means: means.into(),
             vars: vars.into(),
@@ -458,6 +551,15 @@ impl NameState {
         responses.sort_by_key(|e| u32::MAX - e.citations);
         responses.into()
     }
+
+    fn alt_rank<F>(responses: &[SearchResult], f: F) -> Box<[usize]>
+    where
+        F: Fn(&SearchResult) -> f64,
+    {
+        let mut ranks: Vec<usize> = (0..responses.len()).collect();
+        ranks.sort_by(|l, r| f(&responses[*r]).total_cmp(&f(&responses[*l])));
+        ranks.into()
+    }
 }
 
 fn coord_dist(l: &Coords, r: &Coords) -> f64 {
@@ -531,6 +633,7 @@ macro_rules! multi_route {
                 .route(&format!("/slice/{}/:from/:to", <$T as Entity>::NAME), get(slice_get))
                 .route(&format!("/views/{}/:semantic_id", <$T as Entity>::NAME), get(view_get))
                 .route(&format!("/sem-id-via-oa/{}/:oa_id", <$T as Entity>::NAME), get(sem_id_get))
+                .route(&format!("/sem-redirect/{}/:semantic_id", <$T as Entity>::NAME), get(sem_redirect_get))
                 .with_state({
                     let nstate = ns_map.remove(<$T>::NAME).expect("NState thread panicked");
                     let entities = nstate.responses.iter().filter(|e| <$T as PrepFilter>::is_top(e)).map(|e| e.clone()).collect();
@@ -557,6 +660,7 @@ fn add_thread<E>(
     ei_ns_map: &mut HashMap<&'static str, JoinHandle<NameState>>,
 ) where
     E: RootInterfaceable + PrepFilter + MainEntity + NamespacedEntity,
+    MAA<E, SemanticIdMarker>: NamespacedEntity,
 {
     let gets_clone = Arc::clone(gets);
     let au_clone = Arc::clone(atts);
@@ -589,14 +693,40 @@ async fn main() {
         .gzip(true)
         .quality(CompressionLevel::Fastest);
 
-    let stowage = Stowage::new(&path);
+    let stowage = if std::path::Path::new(&path).is_file() {
+        Stowage::from_bundle(&path)
+    } else {
+        Stowage::new(&path)
+    };
+    stowage
+        .check_config(&data_namespaces())
+        .expect("data built with the current pipeline config");
+    let collab_api = Router::new()
+        .route(
+            &format!("/{}/:semantic_id", Authors::NAME),
+            get(collab_get::<Authors>),
+        )
+        .with_state(Arc::new(CollabState::<Authors>::new(&stowage)))
+        .merge(
+            Router::new()
+                .route(
+                    &format!("/{}/:semantic_id", Institutions::NAME),
+                    get(collab_get::<Institutions>),
+                )
+                .with_state(Arc::new(CollabState::<Institutions>::new(&stowage))),
+        );
+    let orcid_api = Router::new()
+        .route("/:orcid", get(orcid_get))
+        .with_state(Arc::new(orcid_map(&stowage)));
     let (response_api, tree_manager, entity_descriptions, tops) = multi_route!(
         stowage,
         Authors,
         Institutions,
         Sources,
         Subfields,
-        Countries
+        Countries,
+        Publishers,
+        Funders
     );
 
     let count_api = static_router(&entity_descriptions);
@@ -616,6 +746,8 @@ async fn main() {
         .nest("/counts", count_api)
         .nest("/specs", specs_api)
         .nest("/tops", tops_api)
+        .nest("/orcid", orcid_api)
+        .nest("/collaborators", collab_api)
         .layer(ServiceBuilder::new().layer(cors).layer(compression));
 
     let app = Router::new().nest("/v1", api);
@@ -630,6 +762,7 @@ async fn main() {
 
 async fn slice_get(
     Path(ends): Path<(usize, usize)>,
+    q: Query<SliceQ>,
     states: State<(Arc<NameState>, Arc<AttributeLabelUnion>)>,
 ) -> Response<Body> {
     const MAX_SLICE: usize = 1000;
@@ -639,7 +772,22 @@ async fn slice_get(
         max(start + 1, min(start + MAX_SLICE, ends.1)),
         state.responses.len(),
     );
-    Json(&state.responses[start..end]).into_response()
+    let rank_i = if q.pagerank.unwrap_or(false) {
+        Some(CITE_WINDOWS.len())
+    } else {
+        q.window
+            .and_then(|w| CITE_WINDOWS.iter().position(|e| *e == w))
+    };
+    match rank_i {
+        None => Json(&state.responses[start..end]).into_response(),
+        Some(ri) => {
+            let ranked: Vec<&SearchResult> = state.alt_ranks[ri][start..end]
+                .iter()
+                .map(|i| &state.responses[*i])
+                .collect();
+            Json(ranked).into_response()
+        }
+    }
 }
 
 async fn state_get(str_state: State<Arc<str>>) -> (HeaderMap, Response<Body>) {
@@ -723,6 +871,70 @@ async fn sem_id_get(
     Json([out])
 }
 
+///the current semantic id of an entity, also when it is asked for with a retired one
+async fn sem_redirect_get(
+    Path(semantic_id): Path<String>,
+    states: State<(Arc<NameState>, Arc<AttributeLabelUnion>)>,
+) -> Json<[Option<String>; 1]> {
+    let nstate = states.0 .0;
+    if nstate.semantic_id_map.contains_key(&semantic_id) {
+        return Json([Some(semantic_id)]);
+    }
+    let out = nstate
+        .redirect_map
+        .get(&semantic_id)
+        .and_then(|oa_id| nstate.oa_id_map.get(oa_id))
+        .map(|e| nstate.responses[*e].semantic_id.clone());
+    Json([out])
+}
+
+///semantic id of the author with the orcid
+async fn orcid_get(
+    Path(orcid): Path<String>,
+    state: State<Arc<HashMap<String, String>>>,
+) -> Json<[Option<String>; 1]> {
+    Json([state.get(&orcid.to_uppercase()).cloned()])
+}
+
+///collaboration stats of an entity, with its strongest collaborators
+async fn collab_get<E>(
+    Path(semantic_id): Path<String>,
+    state: State<Arc<CollabState<E>>>,
+) -> Json<Option<CollabResult>>
+where
+    E: CollabInterfaceable + VarAtt<CollaboratorsMarker> + VarAtt<CollabWeightsMarker>,
+    ET<E>: UnsignedNumber,
+    <E as VarAtt<CollaboratorsMarker>>::VT: AsRef<[ET<E>]>,
+    <E as VarAtt<CollabWeightsMarker>>::VT: AsRef<[u32]>,
+{
+    let out = state.semantic_id_map.get(&semantic_id).map(|e| {
+        let ifs = &state.ifs;
+        let mut collaborators: Vec<Collaborator> = state.collaborators.0[*e]
+            .as_ref()
+            .iter()
+            .zip(state.weights.0[*e].as_ref().iter())
+            .map(|(c, w)| {
+                let cu = c.to_usize();
+                Collaborator {
+                    name: ifs.names.0[cu].clone(),
+                    semantic_id: ifs.sem_ids.0[cu].clone(),
+                    shared_works: *w,
+                }
+            })
+            .collect();
+        collaborators.sort_by_key(|c| std::cmp::Reverse(c.shared_works));
+        collaborators.truncate(TOP_COLLABORATORS);
+        CollabResult {
+            degree: ifs.degrees[*e].to_usize(),
+            weighted_degree: ifs.weighted_degrees[*e].to_usize(),
+            clustering: ifs.clustering[*e],
+            component: ifs.components[*e].to_usize(),
+            collaborators,
+        }
+    });
+    Json(out)
+}
+
 async fn name_get(
     q: Query<BasicQ>,
     states: State<(Arc<NameState>, Arc<AttributeLabelUnion>)>,
@@ -740,6 +952,39 @@ async fn name_get(
     (cache_header(60), resp)
 }
 
+impl<E> CollabState<E>
+where
+    E: CollabInterfaceable + VarAtt<CollaboratorsMarker> + VarAtt<CollabWeightsMarker>,
+{
+    fn new(stowage: &Stowage) -> Self {
+        let ifs = CollabInterfaces::<E>::new(stowage);
+        let semantic_id_map = ifs
+            .sem_ids
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, sem_id)| !sem_id.is_empty())
+            .map(|(i, sem_id)| (sem_id.clone(), i))
+            .collect();
+        Self {
+            ifs,
+            collaborators: <E as VarAtt<CollaboratorsMarker>>::load(stowage),
+            weights: <E as VarAtt<CollabWeightsMarker>>::load(stowage),
+            semantic_id_map,
+        }
+    }
+}
+
+fn orcid_map(stowage: &Stowage) -> HashMap<String, String> {
+    let orcids = stowage.get_entity_interface::<MAA<Authors, OrcidMarker>, ReadIter>();
+    let sem_ids = stowage.get_entity_interface::<MAA<Authors, SemanticIdMarker>, ReadIter>();
+    orcids
+        .zip(sem_ids)
+        .filter(|(orcid, sem_id)| !orcid.is_empty() && !sem_id.is_empty())
+        .map(|(orcid, sem_id)| (orcid.to_uppercase(), sem_id))
+        .collect()
+}
+
 fn cache_header(mins: usize) -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -0,0 +1,419 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, Write},
+};
+
+use dmove::{
+    stats::GeneratedAttribute, BigId, Entity, IdMap, MarkedAttribute, NamespacedEntity, MAA,
+};
+use hashbrown::HashMap;
+use rankless_rs::{
+    common::{write_json_path, MainEntity, Stowage},
+    gen::a1_entity_mapping::{
+        Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Works,
+    },
+    CiteCountMarker, NameMarker, SemanticIdMarker, WorkCountMarker,
+};
+use serde::Serialize;
+
+const DIFF_DIR: &str = "diffs";
+const TOP_CHANGES: usize = 100;
+const SUMMARY_EXAMPLES: usize = 5;
+
+#[derive(Serialize)]
+pub struct EntityDiff {
+    pub entity: String,
+    pub old_count: usize,
+    pub new_count: usize,
+    pub added: Vec<BigId>,
+    pub removed: Vec<BigId>,
+    pub renamed: Vec<StringChange>,
+    pub semantic_id_changes: Vec<StringChange>,
+    pub work_count_changes: Vec<CountChange>,
+    pub cite_count_changes: Vec<CountChange>,
+}
+
+#[derive(Serialize)]
+pub struct StringChange {
+    pub id: BigId,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Serialize)]
+pub struct CountChange {
+    pub id: BigId,
+    pub old: usize,
+    pub new: usize,
+}
+
+//(namespace, name) of an attribute, as the compiled code calls it
+type AttName = (&'static str, &'static str);
+
+///attributes of an entity as one build has them, sized by its own files
+struct Side {
+    by_oa: HashMap<BigId, usize>,
+    names: Vec<String>,
+    sem_ids: Vec<String>,
+    wcounts: Vec<u64>,
+    ccounts: Vec<u64>,
+}
+
+///layouts of the attributes of a build, from the code generated with it
+struct Build<'a> {
+    stowage: &'a Stowage,
+    atts: HashMap<(String, String), GeneratedAttribute>,
+}
+
+pub fn main(old: Stowage, new: Stowage) -> io::Result<()> {
+    let (old, new) = (Build::new(&old), Build::new(&new));
+    let diffs = vec![
+        diff_entity::<Authors>(&old, &new),
+        diff_entity::<Institutions>(&old, &new),
+        diff_entity::<Sources>(&old, &new),
+        diff_entity::<Subfields>(&old, &new),
+        diff_entity::<Countries>(&old, &new),
+        diff_entity::<Publishers>(&old, &new),
+        diff_entity::<Funders>(&old, &new),
+        diff_works(&old, &new),
+    ];
+
+    let out_dir = new
+        .stowage
+        .paths
+        .entity_csvs
+        .parent()
+        .unwrap()
+        .join(DIFF_DIR);
+    create_dir_all(&out_dir)?;
+    let summary = diffs.iter().map(EntityDiff::summary).collect::<Vec<_>>();
+    let mut summary_file = File::create(out_dir.join("summary.txt"))?;
+    for s in summary.iter() {
+        println!("{s}");
+        writeln!(summary_file, "{s}")?;
+    }
+    write_json_path(&diffs, out_dir.join("diff.json.gz")).map_err(io::Error::from)
+}
+
+fn diff_entity<E>(old: &Build, new: &Build) -> EntityDiff
+where
+    E: MainEntity
+        + NamespacedEntity
+        + MarkedAttribute<NameMarker>
+        + MarkedAttribute<SemanticIdMarker>
+        + MarkedAttribute<WorkCountMarker>
+        + MarkedAttribute<CiteCountMarker>,
+    MAA<E, NameMarker>: NamespacedEntity,
+    MAA<E, SemanticIdMarker>: NamespacedEntity,
+    MAA<E, WorkCountMarker>: NamespacedEntity,
+    MAA<E, CiteCountMarker>: NamespacedEntity,
+{
+    let atts = [
+        Some(att_name::<E, NameMarker>()),
+        Some(att_name::<E, SemanticIdMarker>()),
+        Some(att_name::<E, WorkCountMarker>()),
+        Some(att_name::<E, CiteCountMarker>()),
+    ];
+    diff_sides(E::NAME, old.side::<E>(atts), new.side::<E>(atts))
+}
+
+fn diff_works(old: &Build, new: &Build) -> EntityDiff {
+    let atts = [
+        Some(att_name::<Works, NameMarker>()),
+        None,
+        None,
+        Some(att_name::<Works, CiteCountMarker>()),
+    ];
+    diff_sides(
+        Works::NAME,
+        old.side::<Works>(atts),
+        new.side::<Works>(atts),
+    )
+}
+
+fn diff_sides(entity: &str, old_side: Side, new_side: Side) -> EntityDiff {
+    let mut added = Vec::new();
+    let mut renamed = Vec::new();
+    let mut semantic_id_changes = Vec::new();
+    let mut work_count_changes = Vec::new();
+    let mut cite_count_changes = Vec::new();
+
+    for (oa_id, new_i) in new_side.by_oa.iter() {
+        let old_i = match old_side.by_oa.get(oa_id) {
+            Some(i) => *i,
+            None => {
+                added.push(*oa_id);
+                continue;
+            }
+        };
+        let pair = (old_i, *new_i);
+        if let Some(c) = string_change(*oa_id, &old_side.names, &new_side.names, pair) {
+            renamed.push(c);
+        }
+        if let Some(c) = string_change(*oa_id, &old_side.sem_ids, &new_side.sem_ids, pair) {
+            semantic_id_changes.push(c);
+        }
+        if let Some(c) = count_change(*oa_id, &old_side.wcounts, &new_side.wcounts, pair) {
+            work_count_changes.push(c);
+        }
+        if let Some(c) = count_change(*oa_id, &old_side.ccounts, &new_side.ccounts, pair) {
+            cite_count_changes.push(c);
+        }
+    }
+    let mut removed: Vec<BigId> = old_side
+        .by_oa
+        .keys()
+        .filter(|k| !new_side.by_oa.contains_key(*k))
+        .copied()
+        .collect();
+
+    added.sort();
+    removed.sort();
+    renamed.sort_by_key(|e| e.id);
+    semantic_id_changes.sort_by_key(|e| e.id);
+
+    EntityDiff {
+        entity: entity.to_string(),
+        old_count: old_side.by_oa.len(),
+        new_count: new_side.by_oa.len(),
+        added,
+        removed,
+        renamed,
+        semantic_id_changes,
+        work_count_changes: top_changes(work_count_changes),
+        cite_count_changes: top_changes(cite_count_changes),
+    }
+}
+
+impl<'a> Build<'a> {
+    fn new(stowage: &'a Stowage) -> Self {
+        let atts = stowage
+            .generated_attributes()
+            .into_iter()
+            .map(|(ns, att)| ((ns.to_string(), att.name.clone()), att))
+            .collect();
+        Self { stowage, atts }
+    }
+
+    ///[names, semantic ids, work counts, cite counts], the ones given and present in the build
+    fn side<E>(&self, atts: [Option<AttName>; 4]) -> Side
+    where
+        E: MainEntity + NamespacedEntity,
+    {
+        let id_path = self.stowage.path_from_ns(E::NS).join(E::NAME);
+        let by_oa = IdMap::new(id_path)
+            .to_map::<u64>()
+            .0
+            .into_iter()
+            .filter(|(oa, _)| *oa > 0)
+            .map(|(oa, i)| (oa, i as usize))
+            .collect();
+        let [names, sem_ids, wcounts, ccounts] = atts.map(|e| e.and_then(|k| self.find(k)));
+        Side {
+            by_oa,
+            names: self.strings(names),
+            sem_ids: self.strings(sem_ids),
+            wcounts: self.numbers(wcounts),
+            ccounts: self.numbers(ccounts),
+        }
+    }
+
+    fn find(&self, k: AttName) -> Option<(&'static str, &GeneratedAttribute)> {
+        let found = self.atts.get(&(k.0.to_string(), k.1.to_string()));
+        if found.is_none() {
+            println!(
+                "{} not in {:?}, not compared",
+                k.1, self.stowage.paths.entity_csvs
+            );
+        }
+        found.map(|att| (k.0, att))
+    }
+
+    fn strings(&self, att: Option<(&str, &GeneratedAttribute)>) -> Vec<String> {
+        att.map(|(ns, att)| att.strings(&self.stowage.path_from_ns(ns)))
+            .unwrap_or_default()
+    }
+
+    fn numbers(&self, att: Option<(&str, &GeneratedAttribute)>) -> Vec<u64> {
+        let mut out = Vec::new();
+        if let Some((ns, att)) = att {
+            att.for_each_number(&self.stowage.path_from_ns(ns), |v| out.push(v));
+        }
+        out
+    }
+}
+
+impl EntityDiff {
+    pub fn summary(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "== {} ({} -> {})",
+                self.entity, self.old_count, self.new_count
+            ),
+            format!(
+                "added: {}, removed: {}, renamed: {}, semantic id changes: {}",
+                self.added.len(),
+                self.removed.len(),
+                self.renamed.len(),
+                self.semantic_id_changes.len()
+            ),
+        ];
+        for (k, changes) in [
+            ("work count", &self.work_count_changes),
+            ("cite count", &self.cite_count_changes),
+        ] {
+            lines.push(format!("largest {k} changes:"));
+            for c in changes.iter().take(SUMMARY_EXAMPLES) {
+                lines.push(format!("  {}: {} -> {}", c.id, c.old, c.new));
+            }
+        }
+        for c in self.semantic_id_changes.iter().take(SUMMARY_EXAMPLES) {
+            lines.push(format!("  semantic id {}: {} -> {}", c.id, c.old, c.new));
+        }
+        lines.join("\n")
+    }
+}
+
+impl CountChange {
+    fn abs_diff(&self) -> usize {
+        self.old.abs_diff(self.new)
+    }
+}
+
+fn att_name<E, Mark>() -> AttName
+where
+    E: MarkedAttribute<Mark>,
+    MAA<E, Mark>: NamespacedEntity,
+{
+    (MAA::<E, Mark>::NS, MAA::<E, Mark>::NAME)
+}
+
+fn string_change(
+    id: BigId,
+    old: &[String],
+    new: &[String],
+    (old_i, new_i): (usize, usize),
+) -> Option<StringChange> {
+    match (old.get(old_i), new.get(new_i)) {
+        (Some(o), Some(n)) if o != n => Some(StringChange {
+            id,
+            old: o.clone(),
+            new: n.clone(),
+        }),
+        _ => None,
+    }
+}
+
+fn count_change(
+    id: BigId,
+    old: &[u64],
+    new: &[u64],
+    (old_i, new_i): (usize, usize),
+) -> Option<CountChange> {
+    Some(CountChange {
+        id,
+        old: *old.get(old_i)? as usize,
+        new: *new.get(new_i)? as usize,
+    })
+}
+
+fn top_changes(mut changes: Vec<CountChange>) -> Vec<CountChange> {
+    changes.retain(|e| e.old != e.new);
+    changes.sort_by_key(|e| (usize::MAX - e.abs_diff(), e.id));
+    changes.truncate(TOP_CHANGES);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankless_rs::{
+        common::NS_CODE_FILE,
+        gen::{a2_init_atts::WorksNames, derive_links2::WorkCitingCounts},
+    };
+    use std::fs::{remove_dir_all, write};
+
+    //works given as (openalex id, title, citations), cite counts stored with the given width
+    fn fake_build(root: &std::path::Path, works: &[(BigId, &str, u64)], width: usize) -> Stowage {
+        let stowage = Stowage::new(root.to_str().unwrap());
+        let id_dir = stowage.path_from_ns(Works::NS);
+        let names_dir = stowage.path_from_ns(WorksNames::NS).join(WorksNames::NAME);
+        let counts_dir = stowage.path_from_ns(WorkCitingCounts::NS);
+        for d in [&id_dir, &names_dir, &counts_dir] {
+            create_dir_all(d).unwrap();
+        }
+        let mut records: Vec<(BigId, u64)> = works
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (w.0, i as u64 + 1))
+            .collect();
+        records.sort();
+        let id_bytes: Vec<u8> = records
+            .iter()
+            .flat_map(|(k, v)| [k.to_be_bytes(), v.to_be_bytes()].concat())
+            .collect();
+        write(id_dir.join(Works::NAME), id_bytes).unwrap();
+
+        let mut titles = vec![""];
+        titles.extend(works.iter().map(|w| w.1));
+        let sizes: Vec<u8> = titles.iter().map(|t| t.len() as u8).collect();
+        write(names_dir.join("sizes"), sizes).unwrap();
+        write(names_dir.join("targets"), titles.concat()).unwrap();
+        let name_code = format!(
+            "impl Entity for WorksNames {{ type T = String; const N: usize = {}; const NAME: & str = \"works-names\"; }}\n\nimpl VariableSizeAttribute for WorksNames {{ type SizeType = u8; }}",
+            titles.len()
+        );
+        write(
+            stowage.path_from_ns(WorksNames::NS).join(NS_CODE_FILE),
+            name_code,
+        )
+        .unwrap();
+
+        let mut counts = vec![0];
+        counts.extend(works.iter().map(|w| w.2));
+        let count_bytes: Vec<u8> = counts
+            .iter()
+            .flat_map(|c| c.to_be_bytes()[8 - width..].to_vec())
+            .collect();
+        write(counts_dir.join(WorkCitingCounts::NAME), count_bytes).unwrap();
+        let count_code = format!(
+            "impl Entity for WorkCitingCounts {{ type T = u{}; const N: usize = {}; const NAME: & str = \"work-citing-counts\"; }}",
+            width * 8,
+            counts.len()
+        );
+        write(counts_dir.join(NS_CODE_FILE), count_code).unwrap();
+        stowage
+    }
+
+    #[test]
+    fn works_across_layouts() {
+        let tmp = std::env::temp_dir().join(format!("rankless-diff-{}", std::process::id()));
+        let old = fake_build(
+            &tmp.join("old"),
+            &[(11, "a", 3), (12, "b", 200), (13, "c", 1)],
+            1,
+        );
+        let new = fake_build(
+            &tmp.join("new"),
+            &[(14, "d", 0), (12, "b", 70000), (11, "a2", 3)],
+            4,
+        );
+        let diff = diff_works(&Build::new(&old), &Build::new(&new));
+        assert_eq!((diff.old_count, diff.new_count), (3, 3));
+        assert_eq!((diff.added, diff.removed), (vec![14], vec![13]));
+        let renames: Vec<(BigId, &str, &str)> = diff
+            .renamed
+            .iter()
+            .map(|e| (e.id, e.old.as_str(), e.new.as_str()))
+            .collect();
+        assert_eq!(renames, vec![(11, "a", "a2")]);
+        let cites: Vec<(BigId, usize, usize)> = diff
+            .cite_count_changes
+            .iter()
+            .map(|e| (e.id, e.old, e.new))
+            .collect();
+        assert_eq!(cites, vec![(12, 200, 70000)]);
+        assert!(diff.work_count_changes.is_empty());
+        remove_dir_all(&tmp).unwrap();
+    }
+}
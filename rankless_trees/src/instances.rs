@@ -20,7 +20,9 @@ use muwo_search::{ordered_calls, sorted_iters_to_arr, ExtendableArr, OrderedMapp
 use rankless_rs::{
     agg_tree::{AggTreeBase, ReinstateFrom, Updater},
     common::{NumberedEntity, NET},
-    gen::a1_entity_mapping::{Authors, Countries, Institutions, Sources, Subfields, Works},
+    gen::a1_entity_mapping::{
+        Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Works,
+    },
 };
 
 use dmove::{Entity, InitEmpty, UnsignedNumber, ET};
@@ -552,6 +554,26 @@ mod source_trees {
     pub type Tree3<'a> = PostRefIterWrap<'a, Sources, CitingSourceCoSuByRef<'a>>;
 }
 
+#[derive_tree_getter(Publishers)]
+mod publisher_trees {
+
+    use super::*;
+
+    pub type Tree1<'a> = PostRefIterWrap<'a, Publishers, SubfieldCountryInstSourceByRef<'a>>;
+    pub type Tree2<'a> = PostRefIterWrap<'a, Publishers, FullRefCountryInstSubfieldByRef<'a>>;
+    pub type Tree3<'a> = PostRefIterWrap<'a, Publishers, CitingSourceCoSuByRef<'a>>;
+}
+
+#[derive_tree_getter(Funders)]
+mod funder_trees {
+
+    use super::*;
+
+    pub type Tree1<'a> = PostRefIterWrap<'a, Funders, FullRefCountryInstSubfieldByRef<'a>>;
+    pub type Tree2<'a> = PostRefIterWrap<'a, Funders, SubfieldCountryInstSubfieldByRef<'a>>;
+    pub type Tree3<'a> = PostRefIterWrap<'a, Funders, CitingSourceCoSuByRef<'a>>;
+}
+
 #[derive_tree_getter(Subfields)]
 mod subfield_trees {
     use crate::components::{FullRefSourceCountryInstByRef, PostRefIterWrap};
@@ -694,6 +716,7 @@ pub mod big_test_tree {
             big_prep: None,
             big_read: None,
             shallow: None,
+            window: None,
         };
 
         let tstate = TreeRunManager::<(BigTestEntity, BigTestEntity)>::fake();
@@ -723,7 +746,7 @@ mod tests {
     use dmove::{BigId, MappableEntity, NamespacedEntity};
     use rand::{rngs::StdRng, Rng, SeedableRng};
     use rankless_rs::steps::{
-        a1_entity_mapping::{RawYear, YearInterface, Years, POSSIBLE_YEAR_FILTERS},
+        a1_entity_mapping::{possible_year_filters, RawYear, YearInterface},
         derive_links1::WorkPeriods,
     };
     use std::{ops::Deref, sync::Arc};
@@ -818,7 +841,7 @@ mod tests {
                     rng.gen(),
                     rng.gen(),
                     rng.gen(),
-                    rng.gen::<WT>() % (Years::N as WT), //TODO: year test hack quickfix
+                    rng.gen::<WT>() % (YearInterface::iter().len() as WT),
                     rng.gen(),
                 );
                 vec.push(rec);
@@ -826,7 +849,7 @@ mod tests {
             vec
         }
         fn get_pid(v: &StackFr<Self::SB>) -> crate::components::PartitionId {
-            let y8 = (v.3 as usize % Years::N) as u8;
+            let y8 = (v.3 as usize % YearInterface::iter().len()) as u8;
             let y16 = YearInterface::reverse(y8);
             WorkPeriods::from_year(y16)
         }
@@ -840,6 +863,7 @@ mod tests {
             big_prep: None,
             big_read: None,
             shallow: None,
+            window: None,
         }
     }
 
@@ -902,6 +926,16 @@ mod tests {
         Arc::into_inner(tstate).unwrap().join();
     }
 
+    #[test]
+    fn unknown_window() {
+        let tstate = TreeRunManager::<(TestEntity, TestEntity)>::fake();
+        let mut qw = q(1);
+        qw.window = Some(3);
+        let r = tstate.get_resp(qw, &TestEntity::NAME.to_string(), &"0".to_string());
+        assert!(r.is_none());
+        Arc::into_inner(tstate).unwrap().join();
+    }
+
     fn val_res2(r: &TreeResponse) {
         assert_eq!(r.tree.node.source_count, 2);
         assert_eq!(r.tree.node.link_count, 3);
@@ -933,6 +967,7 @@ mod tests {
             big_prep,
             big_read,
             shallow: None,
+            window: None,
         };
         let id = "0".to_string();
         let resp = tstate.get_resp(gq(Some(true), None), &name, &id).unwrap();
@@ -942,7 +977,7 @@ mod tests {
         assert_eq!(resp.tree.node.top_cite_count, 0);
 
         let mut years: Vec<Option<RawYear>> =
-            POSSIBLE_YEAR_FILTERS.iter().map(|e| Some(*e)).collect();
+            possible_year_filters().iter().map(|e| Some(*e)).collect();
         years.insert(0, None);
         for year in years.into_iter() {
             let mut qy = gq(None, None);
@@ -6,18 +6,25 @@ use crate::{
 };
 use rankless_rs::{
     common::{
-        init_empty_slice, BeS, MainEntity, MainWorkMarker, MarkedBackendLoader, NumberedEntity,
-        QuickAttPair, QuickMap, QuickestBox, QuickestVBox, Stowage, Top3AffCountryMarker,
-        Top3AuthorMarker, Top3CitingSfMarker, Top3JournalMarker, Top3PaperSfMarker,
-        Top3PaperTopicMarker, WorkLoader, YearlyCitationsMarker, YearlyPapersMarker, NET,
+        init_empty_slice, BeS, CitSubfieldsConcentrationMarker, CitSubfieldsDiversityMarker,
+        ClusteringMarker, CollabComponentMarker, CollabDegreeMarker, FiveYearCiteCountMarker,
+        MainEntity, MainWorkMarker, MarkedBackendLoader, NormalizedImpactMarker, NumberedEntity,
+        PageRankMarker, QuickAttPair, QuickMap, QuickestBox, QuickestVBox,
+        RefSubfieldsConcentrationMarker, RefSubfieldsDiversityMarker, Stowage,
+        Top3AffCountryMarker, Top3AuthorMarker, Top3CitingSfMarker, Top3JournalMarker,
+        Top3PaperSfMarker, Top3PaperTopicMarker, TopDecileShareMarker, TwoYearCiteCountMarker,
+        WeightedCollabDegreeMarker, WorkLoader, YearlyCitationsMarker, YearlyPapersMarker, NET,
     },
     gen::{
-        a1_entity_mapping::{Authors, Countries, Institutions, Sources, Subfields, Topics, Works},
+        a1_entity_mapping::{
+            Authors, Countries, Funders, Institutions, Publishers, Sources, Subfields, Topics,
+            Works,
+        },
         a2_init_atts::{
-            AuthorshipAuthor, AuthorshipInstitutions, InstCountries, SourceYearQs, TopicSubfields,
+            AuthorshipAuthor, AuthorshipInstitutions, InstCountries, TopicSubfields,
             WorkAuthorships, WorkSources, WorkTopics, WorkYears, WorksNames,
         },
-        derive_links1::{WorkInstitutions, WorkSubfields},
+        derive_links1::{SourceYearQs, WorkInstitutions, WorkSubfields},
         derive_links2::{WorkCitingCounts, WorkCountries, WorkTopSource},
         derive_links5::SourceStats,
     },
@@ -176,8 +183,8 @@ macro_rules! make_ent_interfaces {
         {
             $(pub $f_key: VarBox<String>),*,
             $(pub $r_key: Box<[<T as NumAtt<$r_mark>>::Num]>),*,
+            $(pub $float_key: Box<[f64]>,)*
             $(pub $fix_key: Box<[<T as FixAtt<$fix_mark>>::FT]>),*
-            $(pub $float_key: Box<[f64]>),*
             $(, pub $oa_key: Box<[u64]>)*
         }
 
@@ -187,8 +194,8 @@ macro_rules! make_ent_interfaces {
                 Self {
                     $($f_key: <E as StringAtt<$f_mark>>::load(stowage)),*,
                     $($r_key: <E as NumAtt<$r_mark>>::load(stowage)),*,
+                    $($float_key: <E as FloatAtt<$float_mark>>::load(stowage),)*
                     $($fix_key:  <E as FixAtt<$fix_mark>>::load(stowage)),*
-                    $($float_key:  <E as FloatAtt<$float_mark>>::load(stowage))*
                     $(,$oa_key: reverse_id::<E>(stowage))*
                 }
             }
@@ -215,6 +222,8 @@ make_interfaces!(
     iworks > Institutions,
     aworks > Authors,
     soworks > Sources,
+    pworks > Publishers,
+    fuworks > Funders,
     sfworks > Subfields;
     year => WorkYears,
     top_source => WorkTopSource,
@@ -239,7 +248,8 @@ make_ent_interfaces!(
     RootInterfaces,
     RootInterfaceable,
     names => NameMarker, name_exts => NameExtensionMarker, sem_ids => SemanticIdMarker;
-    wcounts -> WorkCountMarker, ccounts -> CiteCountMarker;
+    wcounts -> WorkCountMarker, ccounts -> CiteCountMarker,
+    ccounts_2y -> TwoYearCiteCountMarker, ccounts_5y -> FiveYearCiteCountMarker;
     yearly_papers - YearlyPapersMarker | EraRec,
     yearly_cites - YearlyCitationsMarker | EraRec,
     top_journals - Top3JournalMarker | TopRec<Sources>,
@@ -247,11 +257,16 @@ make_ent_interfaces!(
     top_aff_countries - Top3AffCountryMarker | TopRec<Countries>,
     top_paper_topic - Top3PaperTopicMarker | TopRec<Topics>,
     top_citing_sfc - Top3CitingSfMarker | TopRec<Subfields>,
-    top_paper_sfc - Top3PaperSfMarker | TopRec<Subfields>;;
+    top_paper_sfc - Top3PaperSfMarker | TopRec<Subfields>;
+    impact : NormalizedImpactMarker,
+    top_decile_share : TopDecileShareMarker,
+    pagerank : PageRankMarker,
+    ref_sfc : RefSubfieldsConcentrationMarker,
+    cit_sfc : CitSubfieldsConcentrationMarker,
+    ref_sfd : RefSubfieldsDiversityMarker,
+    cit_sfd : CitSubfieldsDiversityMarker;
     oa_id; MainEntity, NamespacedEntity
     // inst_rels - InstRelMarker | [InstRelation; N_RELS];
-    // ref_sfc : RefSubfieldsConcentrationMarker,
-    // cit_sfc : CitSubfieldsConcentrationMarker
 
 );
 
@@ -262,10 +277,26 @@ make_ent_interfaces!(
     ccounts -> CiteCountMarker;;;;
 );
 
+//only loaded by the server's collaborator route, no tree reads these
+make_ent_interfaces!(
+    CollabInterfaces,
+    CollabInterfaceable,
+    names => NameMarker, sem_ids => SemanticIdMarker;
+    degrees -> CollabDegreeMarker,
+    weighted_degrees -> WeightedCollabDegreeMarker,
+    components -> CollabComponentMarker;;
+    clustering : ClusteringMarker;;
+);
+
 pub trait StringAtt<Mark>: MarkedAttribute<Mark> {
     fn load(stowage: &Stowage) -> VarBox<String>;
 }
 
+pub trait VarAtt<Mark>: MarkedAttribute<Mark> {
+    type VT;
+    fn load(stowage: &Stowage) -> VarBox<Self::VT>;
+}
+
 pub trait NumAtt<Mark>: MarkedAttribute<Mark> {
     type Num: UnsignedNumber;
     fn load(stowage: &Stowage) -> Box<[Self::Num]>;
@@ -375,6 +406,17 @@ where
     }
 }
 
+impl<T, Mark> VarAtt<Mark> for T
+where
+    T: MarkedAttribute<Mark>,
+    MAA<T, Mark>: CompactEntity + MarkedBackendLoader<QuickestVBox, BE = VarBox<ET<MAA<T, Mark>>>>,
+{
+    type VT = ET<MAA<Self, Mark>>;
+    fn load(stowage: &Stowage) -> VarBox<Self::VT> {
+        stowage.get_marked_interface::<Self, Mark, QuickestVBox>()
+    }
+}
+
 impl<T, Mark> NumAtt<Mark> for T
 where
     T: MarkedAttribute<Mark>,
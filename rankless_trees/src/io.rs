@@ -3,7 +3,7 @@ use std::{
     collections::VecDeque,
     fmt::{Debug, Display},
     marker::PhantomData,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, Condvar, Mutex},
     thread::JoinHandle,
@@ -15,11 +15,12 @@ use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 
 use rankless_rs::{
-    env_consts::START_YEAR,
+    config,
     gen::a1_entity_mapping::Works,
     steps::{
-        a1_entity_mapping::{POSSIBLE_YEAR_FILTERS, YBT},
+        a1_entity_mapping::{possible_year_filters, YBT},
         derive_links1::WorkPeriods,
+        derive_links6::CITE_WINDOWS,
     },
 };
 
@@ -61,6 +62,8 @@ pub struct CacheKey {
     pub etype: u8,
     pub eid: usize,
     pub tid: u8,
+    //0 for lifetime citations
+    pub window: u8,
 }
 
 pub struct FullTreeQuery {
@@ -96,6 +99,7 @@ pub struct TreeQ {
     pub big_prep: Option<bool>,
     pub big_read: Option<bool>,
     pub shallow: Option<u8>,
+    pub window: Option<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -220,8 +224,8 @@ impl Display for FullTreeQuery {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}({}:{}/{:?})",
-            self.name, self.ck.eid, self.ck.tid, self.q.year
+            "{}({}:{}/{:?}/{})",
+            self.name, self.ck.eid, self.ck.tid, self.q.year, self.ck.window
         )
     }
 }
@@ -273,7 +277,7 @@ impl TreeSpecs {
         Self {
             root_types,
             specs,
-            year_breaks: POSSIBLE_YEAR_FILTERS,
+            year_breaks: possible_year_filters(),
         }
     }
 
@@ -402,12 +406,17 @@ pub trait RunManagerSub {
         specs: &TreeSpecs,
     ) -> Option<FullTreeQuery> {
         let etype = specs.to_eid(root_type)?;
+        let window = match q.window {
+            Some(w) if !CITE_WINDOWS.contains(&w) => return None,
+            w => w.unwrap_or(0),
+        };
         let ck = CacheKey {
             etype,
             tid: q.tid.unwrap_or(0),
             eid,
+            window,
         };
-        let period = WorkPeriods::from_year(q.year.unwrap_or(START_YEAR));
+        let period = WorkPeriods::from_year(q.year.unwrap_or(config::get().start_year));
         let fq = FullTreeQuery {
             ck,
             q,
@@ -419,7 +428,7 @@ pub trait RunManagerSub {
 }
 
 // make this a derive trait for some struct
-impl_subs!(5);
+impl_subs!(7);
 impl_subs!(2);
 
 impl<T> TreeRunManager<T>
@@ -551,7 +560,7 @@ impl TreeBasisState {
     fn cache_dir(&self, fq: &FullTreeQuery) -> PathBuf {
         self.rt_cache_dir(&fq.name)
             .join(fq.ck.eid.to_string())
-            .join(fq.ck.tid.to_string())
+            .join(tid_dir_name(fq.ck.tid, fq.ck.window))
     }
 
     fn rt_cache_dir(&self, rt: &str) -> PathBuf {
@@ -575,8 +584,13 @@ impl TreeBasisState {
                     };
                     for tid_entry in std::fs::read_dir(&eid_path).unwrap() {
                         let tid_path = tid_entry.unwrap().path();
-                        if let Ok(tid) = fpparse(&tid_path) {
-                            let ck = CacheKey { eid, tid, etype };
+                        if let Some((tid, window)) = parse_tid_dir(&tid_path) {
+                            let ck = CacheKey {
+                                eid,
+                                tid,
+                                etype,
+                                window,
+                            };
                             let mut v = Vec::new();
                             for pid_entry in std::fs::read_dir(&tid_path).unwrap() {
                                 let pid_path = pid_entry.unwrap().path();
@@ -593,6 +607,22 @@ impl TreeBasisState {
     }
 }
 
+//windowed trees are cached next to the lifetime ones as <tid>-<window>y
+fn tid_dir_name(tid: u8, window: u8) -> String {
+    match window {
+        0 => tid.to_string(),
+        w => format!("{tid}-{w}y"),
+    }
+}
+
+fn parse_tid_dir(p: &Path) -> Option<(u8, u8)> {
+    let stem = p.file_stem()?.to_str()?;
+    match stem.split_once('-') {
+        None => Some((stem.parse().ok()?, 0)),
+        Some((tid, w)) => Some((tid.parse().ok()?, w.strip_suffix('y')?.parse().ok()?)),
+    }
+}
+
 fn fpparse<T: FromStr>(p: &PathBuf) -> Result<T, T::Err>
 where
     <T as FromStr>::Err: Debug,
@@ -1,9 +1,11 @@
 mod components;
+pub mod diff;
 mod ids;
 pub mod instances;
 pub mod interfacing;
 pub mod io;
 mod part_iterator;
 mod prune;
+pub mod subsample;
 
 pub use ids::AttributeLabelUnion;
@@ -1,3 +1,43 @@
-fn main() {
+use dmove::stats::StatKind;
+use rankless_rs::Stowage;
+
+fn main() -> std::io::Result<()> {
     // rankless_trees::instances::big_test_tree::get_big_tree(23);
+    let mut args = std::env::args().skip(1);
+    if let Some(comm) = args.next() {
+        if comm == "diff" {
+            let new_root = args.next().expect("new root");
+            let old_root = args.next().expect("old root");
+            let (old, new) = (Stowage::read_only(&old_root), Stowage::read_only(&new_root));
+            return rankless_trees::diff::main(old, new);
+        } else if comm == "subsample" {
+            let full_root = args.next().expect("full root");
+            let out_root = args.next().expect("out root");
+            let n = args.next().map(|e| e.parse().unwrap()).unwrap_or(20);
+            let (full, out) = (
+                Stowage::read_only(&full_root),
+                Stowage::read_only(&out_root),
+            );
+            return rankless_trees::subsample::main(full, out, n);
+        } else if comm == "stats" {
+            let stowage = Stowage::read_only(&args.next().expect("root"));
+            let name = args.next();
+            let atts = stowage.generated_attributes();
+            let Some(name) = name else {
+                for (ns, att) in atts.iter() {
+                    let kind = match att.kind {
+                        StatKind::Fix(_) => "values",
+                        StatKind::VarLen(_) => "lengths",
+                    };
+                    println!("{ns}: {} ({kind})", att.name);
+                }
+                return Ok(());
+            };
+            match atts.iter().find(|(_, att)| att.name == name) {
+                Some((ns, att)) => println!("{name}\n{}", stowage.attribute_stats(ns, att)),
+                None => println!("unknown attribute {name}"),
+            }
+        }
+    }
+    Ok(())
 }
@@ -23,7 +23,7 @@ use rankless_rs::{
     agg_tree::{HeapIterator, MinHeap, SortedRecord, Updater},
     common::{read_buf_path, write_buf_path, NumberedEntity, NET},
     steps::{
-        a1_entity_mapping::{YearInterface, POSSIBLE_YEAR_FILTERS},
+        a1_entity_mapping::{possible_year_filters, YearInterface},
         derive_links1::WorkPeriods,
     },
 };
@@ -59,7 +59,7 @@ pub trait PartitioningIterator<'a>:
             breakdowns,
             is_spec: Self::IS_SPEC,
             allow_spec: Self::IS_SPEC,
-            default_partition: POSSIBLE_YEAR_FILTERS[Self::DEFAULT_PARTITION as usize],
+            default_partition: possible_year_filters()[Self::DEFAULT_PARTITION as usize],
         }
     }
 
@@ -209,13 +209,15 @@ pub trait PartitioningIterator<'a>:
         state: &'a TreeBasisState,
     ) -> [SrHeap<'a, Self>; MAX_PARTITIONS]
     where
-        StackFr<Self::StackBasis>: Ord,
+        StackFr<Self::StackBasis>: Ord + GetRefWork,
     {
         let mut heaps = [(); MAX_PARTITIONS].map(|_| SrHeap::<'a, Self>::new());
         let now = std::time::Instant::now();
         let maker = Self::new(*et_id, &state.gets);
         for (pid, rec) in maker {
-            heaps[pid as usize].push(rec);
+            if in_window(&rec, fq.ck.window, &state.gets) {
+                heaps[pid as usize].push(rec);
+            }
         }
         println!("{fq}: got heaps in {}", now.elapsed().as_millis());
         heaps
@@ -269,6 +271,9 @@ pub trait PartitioningIterator<'a>:
             .collect();
         for e in piter {
             let frec = e.1;
+            if !in_window(&frec, fq.ck.window, &state.gets) {
+                continue;
+            }
             let rwid = frec.rwid();
             let y = state.gets.year(&rwid);
             writers[*y as usize]
@@ -283,6 +288,7 @@ pub trait PartitioningIterator<'a>:
 
 pub trait GetRefWork {
     fn rwid(&self) -> WT;
+    fn cwid(&self) -> WT;
 }
 
 impl Progress {
@@ -313,24 +319,41 @@ impl<T1> GetRefWork for (T1, WT, WT) {
     fn rwid(&self) -> WT {
         self.1
     }
+    fn cwid(&self) -> WT {
+        self.2
+    }
 }
 
 impl<T1, T2> GetRefWork for (T1, T2, WT, WT) {
     fn rwid(&self) -> WT {
         self.2
     }
+    fn cwid(&self) -> WT {
+        self.3
+    }
 }
 
 impl<T1, T2, T3> GetRefWork for (T1, T2, T3, WT, WT) {
     fn rwid(&self) -> WT {
         self.3
     }
+    fn cwid(&self) -> WT {
+        self.4
+    }
 }
 
 impl<T1, T2, T3, T4> GetRefWork for (T1, T2, T3, T4, WT, WT) {
     fn rwid(&self) -> WT {
         self.4
     }
+    fn cwid(&self) -> WT {
+        self.5
+    }
+}
+
+//windowed trees only keep citations that came at most window years after the cited work
+fn in_window<R: GetRefWork>(rec: &R, window: u8, gets: &Getters) -> bool {
+    (window == 0) || (*gets.year(&rec.cwid()) <= gets.year(&rec.rwid()).saturating_add(window))
 }
 
 fn read_big_calculate<'a, PI, CT, SR, F1>(fq: &FullTreeQuery, mut check_w: F1)
@@ -348,7 +371,8 @@ where
     let cache_root = tmp_part_cache_root(fq);
     let mut buf: [u8; MAX_BUFSIZE] = [0; MAX_BUFSIZE];
     let mut ser_tree_o = None;
-    let mut year_bp_iter = POSSIBLE_YEAR_FILTERS.iter().rev();
+    let year_filters = possible_year_filters();
+    let mut year_bp_iter = year_filters.iter().rev();
     let mut next_bp_o = year_bp_iter.next();
     let bufr = &mut buf[..StackFr::<PI::StackBasis>::S];
     for y in YearInterface::iter().rev() {
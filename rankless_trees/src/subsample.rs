@@ -0,0 +1,898 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use dmove::{
+    bundle::{open_data, DataFile},
+    ByteFixArrayInterface, Entity, GeneratedEntity, NamespacedEntity, ET, MAA,
+};
+use hashbrown::{HashMap, HashSet};
+use rankless_rs::{
+    common::{
+        CollabWeightsMarker, CollaboratorsMarker, InstRelMarker, Stowage, Top3AuthorMarker,
+        Top3JournalMarker, WorkCountMarker, NS_CODE_FILE,
+    },
+    config::CONFIG_FILE,
+    gen::{
+        a1_entity_mapping::{
+            Authors, Authorships, Funders, Institutions, Publishers, Sources, Works,
+        },
+        a2_init_atts::{SourceYearExternalQs, WorkReferences},
+        derive_links1::SourceYearQs,
+        derive_links2::{InstitutionWorks, WorkTopSource},
+        derive_links3::HitPapers,
+        derive_links5::{SourcePairsByPath, SourceStats},
+    },
+    steps::a1_entity_mapping::Years,
+    SemRegistry,
+};
+
+//entities cut down to the subset, the others are small and kept whole,
+//as records of fixed size refer to them by position
+const RENUMBERED: [&str; 7] = [
+    Works::NAME,
+    Authorships::NAME,
+    Authors::NAME,
+    Institutions::NAME,
+    Sources::NAME,
+    Publishers::NAME,
+    Funders::NAME,
+];
+//compact attributes declared without a link or a marker, with the entity indexing them
+const OWNERS: [(&str, &str); 2] = [
+    (WorkTopSource::NAME, Works::NAME),
+    (SourceStats::NAME, Sources::NAME),
+];
+//markers of var attributes with their elements in step with the ones of a link
+const ALIGNED: [(MarkerName, MarkerName); 1] = [(
+    Slots::of::<CollabWeightsMarker>,
+    Slots::of::<CollaboratorsMarker>,
+)];
+const ID_MAP_RECORD: usize = 16;
+
+type IdSet = HashSet<usize>;
+type MarkerName = fn() -> &'static str;
+
+///compact ids of an entity in the subset, numbered in the order of the full build
+struct IdMapping {
+    old_n: usize,
+    //None if the entity is kept whole
+    new: Option<HashMap<usize, usize>>,
+}
+
+///where ids of other entities sit in the fixed size records of an attribute
+#[derive(Clone, Copy)]
+enum Slots {
+    ///the record is one id
+    Whole,
+    ///(count, id) pairs of top lists
+    TopPairs,
+    ///institution relations, the id after their start and end years
+    InstRels,
+    ///a pair of ids leading the record, records with an id left out are dropped
+    LeadPair,
+    ///key of a sorted map, records with a key left out are dropped
+    Key,
+    ///id map keyed by compact ids, its values numbered again
+    IdMapKey,
+}
+
+///how the records of an attribute are rewritten
+struct Plan<'a> {
+    //entity indexing the rows, rows of entities left out are dropped
+    owner: Option<&'a IdMapping>,
+    //(offset, width, mapping) of ids in a fixed size record
+    slots: Vec<(usize, usize, &'a IdMapping)>,
+    drop_missing: bool,
+    //(offset, width) of a value numbered again from 1
+    resequence: Option<(usize, usize)>,
+}
+
+///entities and attributes of the full build, as the code generated with it declares them
+///reads the rows of a var size attribute one by one
+struct Rows {
+    sizes: BufReader<DataFile>,
+    targets: BufReader<DataFile>,
+    size_buf: Vec<u8>,
+    unit: usize,
+    buf: Vec<u8>,
+}
+
+struct Build<'a> {
+    stowage: &'a Stowage,
+    items: Vec<(&'static str, GeneratedEntity)>,
+    //entity names by struct name
+    names: HashMap<String, String>,
+}
+
+///takes the n institutions with most works, all their works, the works these reference and
+///everything these works link to, and writes the subset as a build of its own into out
+///
+///compact ids are numbered again in the order of the full build, declared links to entities
+///left out are dropped, aggregates like counts and top lists are kept as in the full build
+pub fn main(full: Stowage, out: Stowage, n: usize) -> io::Result<()> {
+    let build = Build::new(&full);
+    let selected = build.select(n);
+    let mappings: HashMap<String, IdMapping> = build
+        .items
+        .iter()
+        .filter(|(ns, _)| *ns == Works::NS)
+        .map(|(_, ge)| {
+            let ids = RENUMBERED
+                .contains(&ge.name.as_str())
+                .then(|| selected.get(&ge.name).cloned().unwrap_or_default());
+            (ge.name.clone(), IdMapping::new(ge.n, ids))
+        })
+        .collect();
+    for (name, mapping) in mappings.iter() {
+        println!("{name}: {} -> {}", mapping.old_n, mapping.new_n());
+    }
+
+    let mut new_ns: HashMap<&str, usize> = HashMap::new();
+    for (ns, ge) in build.items.iter() {
+        let in_dir = full.path_from_ns(ns);
+        let out_dir = out.path_from_ns(ns);
+        create_dir_all(&out_dir)?;
+        let new_n = match mappings.get(&ge.name) {
+            Some(mapping) if *ns == Works::NS => {
+                build.write_entity(ge, mapping, &in_dir, &out_dir)?
+            }
+            _ => build.write_attribute(ge, &mappings, &in_dir, &out_dir)?,
+        };
+        new_ns.insert(ge.name.as_str(), new_n);
+    }
+    for ns in rankless_rs::data_namespaces() {
+        build.write_code(ns, &out, &new_ns)?;
+    }
+    let out_root = out.paths.entity_csvs.parent().unwrap().to_path_buf();
+    full.config.write(&out_root.join(CONFIG_FILE))?;
+    println!("subset written to {out_root:?}");
+    Ok(())
+}
+
+impl IdMapping {
+    fn new(old_n: usize, ids: Option<IdSet>) -> Self {
+        let new = ids.map(|ids| {
+            let mut sorted: Vec<usize> = ids.into_iter().filter(|e| *e > 0).collect();
+            sorted.sort();
+            sorted
+                .into_iter()
+                .enumerate()
+                .map(|(i, old)| (old, i + 1))
+                .collect()
+        });
+        Self { old_n, new }
+    }
+
+    fn new_n(&self) -> usize {
+        match &self.new {
+            Some(new) => new.len() + 1,
+            None => self.old_n,
+        }
+    }
+
+    ///new id of an old one, None if it is left out
+    fn get(&self, old: usize) -> Option<usize> {
+        match &self.new {
+            _ if old == 0 => Some(0),
+            Some(new) => new.get(&old).copied(),
+            None => Some(old),
+        }
+    }
+
+    ///whether a row of an attribute indexed by the entity is kept, rows past the ids are
+    fn keeps_row(&self, row: usize) -> bool {
+        (row >= self.old_n) || self.get(row).is_some()
+    }
+}
+
+impl Slots {
+    fn of<M>() -> &'static str {
+        std::any::type_name::<M>().rsplit("::").next().unwrap()
+    }
+
+    ///(marker or attribute name, entity of the ids, slots)
+    fn known() -> [(&'static str, &'static str, Slots); 8] {
+        [
+            (
+                Self::of::<Top3JournalMarker>(),
+                Sources::NAME,
+                Slots::TopPairs,
+            ),
+            (
+                Self::of::<Top3AuthorMarker>(),
+                Authors::NAME,
+                Slots::TopPairs,
+            ),
+            (
+                Self::of::<InstRelMarker>(),
+                Institutions::NAME,
+                Slots::InstRels,
+            ),
+            (WorkTopSource::NAME, Sources::NAME, Slots::Whole),
+            (SourcePairsByPath::NAME, Sources::NAME, Slots::LeadPair),
+            (SourceYearQs::NAME, Sources::NAME, Slots::Key),
+            (SourceYearExternalQs::NAME, Sources::NAME, Slots::Key),
+            (HitPapers::NAME, Works::NAME, Slots::IdMapKey),
+        ]
+    }
+
+    fn offsets(self, record: usize, id_width: usize, type_name: &str) -> Vec<(usize, usize)> {
+        match self {
+            Slots::Whole | Slots::Key => vec![(0, id_width)],
+            Slots::LeadPair => vec![(0, id_width), (id_width, id_width)],
+            Slots::IdMapKey => vec![(0, ID_MAP_RECORD / 2)],
+            Slots::TopPairs => {
+                let stride = u32::S + id_width;
+                (0..record / stride)
+                    .map(|i| (i * stride + u32::S, id_width))
+                    .collect()
+            }
+            Slots::InstRels => {
+                let stride = record / array_len(type_name);
+                let year_width = <ET<Years> as ByteFixArrayInterface>::S;
+                (0..record / stride)
+                    .map(|i| (i * stride + 2 * year_width, id_width))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl<'a> Build<'a> {
+    fn new(stowage: &'a Stowage) -> Self {
+        let items = stowage.generated_entities();
+        let names = items
+            .iter()
+            .map(|(_, ge)| (ge.struct_name.clone(), ge.name.clone()))
+            .collect();
+        Self {
+            stowage,
+            items,
+            names,
+        }
+    }
+
+    fn select(&self, n: usize) -> HashMap<String, IdSet> {
+        let wcount_name = MAA::<Institutions, WorkCountMarker>::NAME;
+        let mut wcounts = Vec::new();
+        self.for_each_record(self.get(wcount_name), |i, rec| {
+            wcounts.push((read_uint(rec), i))
+        });
+        wcounts.sort_by_key(|(wc, i)| (usize::MAX - wc, *i));
+        let insts: IdSet = wcounts
+            .into_iter()
+            .filter(|(_, i)| *i > 0)
+            .take(n)
+            .map(|e| e.1)
+            .collect();
+        let mut works = self.link_targets(self.get(InstitutionWorks::NAME), &insts);
+        println!("{} works of {n} institutions", works.len());
+        works.extend(self.link_targets(self.get(WorkReferences::NAME), &works.clone()));
+        println!("{} works with their references", works.len());
+
+        let mut selected = HashMap::from([
+            (Institutions::NAME.to_string(), insts),
+            (Works::NAME.to_string(), works),
+        ]);
+        //links out of the subset are followed until nothing is added,
+        //links into works and within an entity only ever point inside it
+        let links: Vec<(&GeneratedEntity, String, String)> = self
+            .items
+            .iter()
+            .filter_map(|(_, ge)| {
+                let (s, t) = ge.link.as_ref()?;
+                let (s, t) = (self.names.get(s)?.clone(), self.names.get(t)?.clone());
+                let followed = RENUMBERED.contains(&s.as_str())
+                    && RENUMBERED.contains(&t.as_str())
+                    && (t != Works::NAME)
+                    && (s != t);
+                followed.then_some((ge, s, t))
+            })
+            .collect();
+        loop {
+            let mut added = 0;
+            for (ge, source, target) in links.iter() {
+                let Some(sources) = selected.get(source) else {
+                    continue;
+                };
+                let targets = self.link_targets(ge, sources);
+                let entry = selected.entry(target.clone()).or_default();
+                let before = entry.len();
+                entry.extend(targets);
+                added += entry.len() - before;
+            }
+            if added == 0 {
+                break;
+            }
+        }
+        selected
+    }
+
+    ///link of the same entity with its elements in step with the ones of a var attribute
+    fn aligned_link(&self, ge: &GeneratedEntity) -> Option<&GeneratedEntity> {
+        let (main, marker) = ge.marks.first()?;
+        let (_, link_marker) = ALIGNED.iter().find(|(m, _)| m() == marker)?;
+        self.items
+            .iter()
+            .map(|(_, e)| e)
+            .find(|e| e.marks.contains(&(main.clone(), link_marker().to_string())))
+    }
+
+    fn get(&self, name: &str) -> &GeneratedEntity {
+        self.items
+            .iter()
+            .find(|(_, ge)| ge.name == name)
+            .map(|e| &e.1)
+            .unwrap_or_else(|| panic!("{name} not in the build"))
+    }
+
+    fn ns_of(&self, ge: &GeneratedEntity) -> &'static str {
+        self.items
+            .iter()
+            .find(|(_, e)| e.name == ge.name)
+            .unwrap()
+            .0
+    }
+
+    fn link_targets(&self, ge: &GeneratedEntity, sources: &IdSet) -> IdSet {
+        let mut out = IdSet::new();
+        let id_width = elem_width(&ge.type_name);
+        let add = |i: usize, bytes: &[u8]| {
+            if sources.contains(&i) {
+                out.extend(bytes.chunks(id_width).map(read_uint));
+            }
+        };
+        match &ge.size_type {
+            Some(_) => self.for_each_row(ge, add),
+            None => self.for_each_record(ge, add),
+        }
+        out.remove(&0);
+        out
+    }
+
+    fn for_each_record<F: FnMut(usize, &[u8])>(&self, ge: &GeneratedEntity, f: F) {
+        let path = self.stowage.path_from_ns(self.ns_of(ge)).join(&ge.name);
+        for_each_record(&path, ge, f);
+    }
+
+    fn for_each_row<F: FnMut(usize, &[u8])>(&self, ge: &GeneratedEntity, f: F) {
+        let dir = self.stowage.path_from_ns(self.ns_of(ge)).join(&ge.name);
+        for_each_row(&dir, ge, f);
+    }
+
+    ///rewrites the id map of an entity, returns its new size
+    fn write_entity(
+        &self,
+        ge: &GeneratedEntity,
+        mapping: &IdMapping,
+        in_dir: &Path,
+        out_dir: &Path,
+    ) -> io::Result<usize> {
+        //entities counted without id maps, like authorships, only get their new size
+        if open_data(&in_dir.join(&ge.name)).is_ok() {
+            let plan = Plan {
+                owner: None,
+                slots: vec![(ID_MAP_RECORD / 2, ID_MAP_RECORD / 2, mapping)],
+                drop_missing: true,
+                resequence: None,
+            };
+            write_records(ge, &plan, in_dir, out_dir)?;
+        }
+        Ok(mapping.new_n())
+    }
+
+    ///rewrites the rows of an attribute, returns their new count
+    fn write_attribute(
+        &self,
+        ge: &GeneratedEntity,
+        mappings: &HashMap<String, IdMapping>,
+        in_dir: &Path,
+        out_dir: &Path,
+    ) -> io::Result<usize> {
+        let data = match ge.size_type {
+            Some(_) => in_dir.join(&ge.name).join("sizes"),
+            None => in_dir.join(&ge.name),
+        };
+        if open_data(&data).is_err() {
+            println!("{} declared but not in the build, skipped", ge.name);
+            return Ok(ge.n);
+        }
+        let mapping_of = |sname: &String| self.names.get(sname).and_then(|name| mappings.get(name));
+        let (owner, target) = match &ge.link {
+            Some((s, t)) => (mapping_of(s), mapping_of(t)),
+            None => {
+                let marked = ge.marks.first().and_then(|(main, _)| mapping_of(main));
+                let listed = OWNERS
+                    .iter()
+                    .find(|(name, _)| *name == ge.name)
+                    .and_then(|(_, owner)| mappings.get(*owner));
+                (marked.or(listed), None)
+            }
+        };
+        if ge.size_type.is_some() {
+            let aligned = self.aligned_link(ge).map(|link| {
+                let dir = self.stowage.path_from_ns(self.ns_of(link)).join(&link.name);
+                let (_, t) = link.link.as_ref().unwrap();
+                (Rows::open(&dir, link), mapping_of(t).unwrap())
+            });
+            return write_rows(ge, owner, target, aligned, in_dir, out_dir);
+        }
+        let known = Slots::known().into_iter().find(|(key, _, _)| {
+            (*key == ge.name) || ge.marks.iter().any(|(_, marker)| marker == key)
+        });
+        let mut plan = Plan {
+            owner,
+            slots: Vec::new(),
+            drop_missing: false,
+            resequence: None,
+        };
+        let record = record_width(&in_dir.join(&ge.name), ge);
+        if let Some(t) = target {
+            plan.slots.push((0, record, t));
+        } else if let Some((_, entity, slots)) = known {
+            let id_width = elem_width(&self.get(entity).type_name);
+            let mapping = &mappings[entity];
+            for (offset, width) in slots.offsets(record, id_width, &ge.type_name) {
+                plan.slots.push((offset, width, mapping));
+            }
+            plan.drop_missing = matches!(slots, Slots::LeadPair | Slots::Key | Slots::IdMapKey);
+            if let Slots::IdMapKey = slots {
+                plan.resequence = Some((ID_MAP_RECORD / 2, ID_MAP_RECORD / 2));
+            }
+        } else if owner.is_none() {
+            println!("{} has no known owner or ids, copied as it is", ge.name);
+        }
+        write_records(ge, &plan, in_dir, out_dir)
+    }
+
+    ///generated code of a namespace with the new sizes, with the files kept next to it
+    fn write_code(&self, ns: &str, out: &Stowage, new_ns: &HashMap<&str, usize>) -> io::Result<()> {
+        let in_dir = self.stowage.path_from_ns(ns);
+        let Ok(mut code_file) = open_data(&in_dir.join(NS_CODE_FILE)) else {
+            return Ok(());
+        };
+        let mut code = String::new();
+        code_file.read_to_string(&mut code)?;
+        let by_struct: HashMap<&str, usize> = self
+            .items
+            .iter()
+            .filter(|(e_ns, _)| *e_ns == ns)
+            .filter_map(|(_, ge)| Some((ge.struct_name.as_str(), *new_ns.get(ge.name.as_str())?)))
+            .collect();
+        let lines: Vec<String> = code
+            .split('\n')
+            .map(|line| {
+                let sname = line
+                    .strip_prefix("impl Entity for ")
+                    .and_then(|e| e.split_whitespace().next());
+                match sname.and_then(|e| Some((e, by_struct.get(e)?))) {
+                    Some((sname, new_n)) => {
+                        let old_n = self.get_struct(ns, sname).n;
+                        line.replace(
+                            &format!("const N: usize = {old_n};"),
+                            &format!("const N: usize = {new_n};"),
+                        )
+                    }
+                    None => line.to_string(),
+                }
+            })
+            .collect();
+        let out_dir = out.path_from_ns(ns);
+        create_dir_all(&out_dir)?;
+        File::create(out_dir.join(NS_CODE_FILE))?.write_all(lines.join("\n").as_bytes())?;
+
+        let mut kept = vec![CONFIG_FILE.to_string()];
+        kept.extend(
+            self.items
+                .iter()
+                .filter(|(e_ns, _)| *e_ns == Works::NS)
+                .map(|(_, ge)| SemRegistry::file_name_of(&ge.name)),
+        );
+        for name in kept {
+            if let Ok(mut src) = open_data(&in_dir.join(&name)) {
+                io::copy(&mut src, &mut File::create(out_dir.join(&name))?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_struct(&self, ns: &str, sname: &str) -> &GeneratedEntity {
+        self.items
+            .iter()
+            .find(|(e_ns, ge)| (*e_ns == ns) && (ge.struct_name == sname))
+            .map(|e| &e.1)
+            .unwrap()
+    }
+}
+
+impl Plan<'_> {
+    ///rewrites the ids of a record in place, false if it is to be dropped
+    fn rewrite(&self, rec: &mut [u8]) -> bool {
+        for (offset, width, mapping) in self.slots.iter() {
+            let slot = &mut rec[*offset..(offset + width)];
+            match mapping.get(read_uint(slot)) {
+                Some(new) => write_uint(new, slot),
+                None if self.drop_missing => return false,
+                None => write_uint(0, slot),
+            }
+        }
+        true
+    }
+}
+
+fn write_records(
+    ge: &GeneratedEntity,
+    plan: &Plan,
+    in_dir: &Path,
+    out_dir: &Path,
+) -> io::Result<usize> {
+    let mut bw = BufWriter::new(File::create(out_dir.join(&ge.name))?);
+    let mut kept = 0;
+    let mut rec = Vec::new();
+    for_each_record(&in_dir.join(&ge.name), ge, |i, old| {
+        if let Some(owner) = plan.owner {
+            if !owner.keeps_row(i) {
+                return;
+            }
+        }
+        rec.clear();
+        rec.extend_from_slice(old);
+        if !plan.rewrite(&mut rec) {
+            return;
+        }
+        kept += 1;
+        if let Some((offset, width)) = plan.resequence {
+            write_uint(kept, &mut rec[offset..(offset + width)]);
+        }
+        bw.write_all(&rec).unwrap();
+    });
+    bw.flush()?;
+    //id maps are sized by their ids, with the null one
+    Ok(if plan.resequence.is_some() {
+        kept + 1
+    } else {
+        kept
+    })
+}
+
+///aligned is a link with its elements in step with the ones of the attribute,
+///elements are dropped where the ones of the link are left out
+fn write_rows(
+    ge: &GeneratedEntity,
+    owner: Option<&IdMapping>,
+    target: Option<&IdMapping>,
+    mut aligned: Option<(Rows, &IdMapping)>,
+    in_dir: &Path,
+    out_dir: &Path,
+) -> io::Result<usize> {
+    let dir = out_dir.join(&ge.name);
+    create_dir_all(&dir)?;
+    let mut sizes = BufWriter::new(File::create(dir.join("sizes"))?);
+    let mut targets = BufWriter::new(File::create(dir.join("targets"))?);
+    let size_width = GeneratedEntity::uint_width(ge.size_type.as_ref().unwrap()).unwrap();
+    let unit = elem_width(&ge.type_name);
+    let mut kept = 0;
+    let mut size_buf = [0; 8];
+    for_each_row(&in_dir.join(&ge.name), ge, |i, bytes| {
+        let aligned_row = aligned.as_mut().map(|(rows, t)| {
+            let link_unit = rows.unit;
+            let row = rows.next_row().unwrap();
+            let kept: Vec<bool> = row
+                .chunks(link_unit)
+                .map(|e| t.get(read_uint(e)).is_some())
+                .collect();
+            kept
+        });
+        if !owner.map(|e| e.keeps_row(i)).unwrap_or(true) {
+            return;
+        }
+        let mut elems = bytes.to_vec();
+        if let Some(kept) = aligned_row {
+            elems = bytes
+                .chunks(unit)
+                .zip(kept)
+                .filter_map(|(e, k)| k.then_some(e))
+                .flatten()
+                .copied()
+                .collect();
+        }
+        if let Some(t) = target {
+            elems = bytes
+                .chunks(unit)
+                .filter_map(|e| {
+                    let new = t.get(read_uint(e))?;
+                    let mut out = e.to_vec();
+                    write_uint(new, &mut out);
+                    Some(out)
+                })
+                .flatten()
+                .collect();
+        }
+        write_uint(elems.len() / unit, &mut size_buf[..size_width]);
+        sizes.write_all(&size_buf[..size_width]).unwrap();
+        targets.write_all(&elems).unwrap();
+        kept += 1;
+    });
+    sizes.flush()?;
+    targets.flush()?;
+    Ok(kept)
+}
+
+fn for_each_record<F: FnMut(usize, &[u8])>(path: &Path, ge: &GeneratedEntity, mut f: F) {
+    let width = record_width(path, ge);
+    let mut br = BufReader::new(open_data(path).unwrap_or_else(|_| panic!("{path:?}")));
+    let mut buf = vec![0; width];
+    let mut i = 0;
+    while (width > 0) && br.read_exact(&mut buf).is_ok() {
+        f(i, &buf);
+        i += 1;
+    }
+}
+
+fn for_each_row<F: FnMut(usize, &[u8])>(dir: &Path, ge: &GeneratedEntity, mut f: F) {
+    let mut rows = Rows::open(dir, ge);
+    let mut i = 0;
+    while let Some(row) = rows.next_row() {
+        f(i, row);
+        i += 1;
+    }
+}
+
+impl Rows {
+    fn open(dir: &Path, ge: &GeneratedEntity) -> Self {
+        let size_width = GeneratedEntity::uint_width(ge.size_type.as_ref().unwrap()).unwrap();
+        Self {
+            sizes: BufReader::new(open_data(&dir.join("sizes")).unwrap()),
+            targets: BufReader::new(open_data(&dir.join("targets")).unwrap()),
+            size_buf: vec![0; size_width],
+            unit: elem_width(&ge.type_name),
+            buf: Vec::new(),
+        }
+    }
+
+    fn next_row(&mut self) -> Option<&[u8]> {
+        self.sizes.read_exact(&mut self.size_buf).ok()?;
+        self.buf.resize(read_uint(&self.size_buf) * self.unit, 0);
+        self.targets.read_exact(&mut self.buf).unwrap();
+        Some(&self.buf)
+    }
+}
+
+fn record_width(path: &Path, ge: &GeneratedEntity) -> usize {
+    //id maps count the null id in their size, other files have a record per row
+    if ge.key_type.as_deref() == Some("u64") {
+        return ID_MAP_RECORD;
+    }
+    let len = open_data(path).map(|e| e.len() as usize).unwrap_or(0);
+    len.checked_div(ge.n).unwrap_or(0)
+}
+
+///bytes of an element of a var size attribute, or of a fixed size number
+fn elem_width(type_name: &str) -> usize {
+    let inner = type_name
+        .strip_prefix("Box<[")
+        .and_then(|e| e.strip_suffix("]>"))
+        .unwrap_or(type_name);
+    GeneratedEntity::uint_width(inner).unwrap_or(1)
+}
+
+fn array_len(type_name: &str) -> usize {
+    type_name
+        .rsplit_once(';')
+        .and_then(|(_, e)| e.trim().trim_end_matches(']').parse().ok())
+        .unwrap_or(1)
+}
+
+fn read_uint(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |acc, b| (acc << 8) | *b as usize)
+}
+
+fn write_uint(v: usize, out: &mut [u8]) {
+    let width = out.len();
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = (v >> (8 * (width - 1 - i))) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankless_rs::gen::{
+        a1_entity_mapping::Countries,
+        a2_init_atts::InstCountries,
+        derive_links1::WorkAuthors,
+        derive_links3::InstitutionsWorkCount,
+        derive_links8::{AuthorsCollabWeights, AuthorsCollaborators},
+    };
+    use std::fs::{read, remove_dir_all, write};
+
+    const A1: &str = "crate::gen::a1_entity_mapping";
+
+    struct FakeBuild {
+        stowage: Stowage,
+        code: HashMap<&'static str, String>,
+    }
+
+    impl FakeBuild {
+        fn entity<E: NamespacedEntity>(&mut self, t: &str, n: usize, extra: &[String]) {
+            let sname = Slots::of::<E>();
+            let code = self.code.entry(E::NS).or_default();
+            code.push_str(&format!(
+                "impl Entity for {sname} {{ type T = {t}; const N: usize = {n}; const NAME: & str = \"{}\"; }}\n",
+                E::NAME
+            ));
+            for line in extra {
+                code.push_str(&format!("{line}\n"));
+            }
+            create_dir_all(self.stowage.path_from_ns(E::NS)).unwrap();
+        }
+
+        //ids given as openalex ids, the compact ones following them
+        fn id_map<E: NamespacedEntity>(&mut self, t: &str, oa_ids: &[u64]) {
+            let key = format!(
+                "impl MappableEntity for {} {{ type KeyType = u64; }}",
+                Slots::of::<E>()
+            );
+            self.entity::<E>(t, oa_ids.len() + 1, &[key]);
+            let bytes: Vec<u8> = oa_ids
+                .iter()
+                .zip(1u64..)
+                .flat_map(|(k, v)| [k.to_be_bytes(), v.to_be_bytes()].concat())
+                .collect();
+            write(self.stowage.path_from_ns(E::NS).join(E::NAME), bytes).unwrap();
+        }
+
+        fn fixed<E: NamespacedEntity>(
+            &mut self,
+            t: &str,
+            width: usize,
+            values: &[usize],
+            extra: &[String],
+        ) {
+            self.entity::<E>(t, values.len(), extra);
+            let mut bytes = vec![0; values.len() * width];
+            for (v, chunk) in values.iter().zip(bytes.chunks_mut(width)) {
+                write_uint(*v, chunk);
+            }
+            write(self.stowage.path_from_ns(E::NS).join(E::NAME), bytes).unwrap();
+        }
+
+        fn rows<E: NamespacedEntity>(&mut self, rows: &[&[u32]], extra: &[String]) {
+            let mut lines = vec![format!(
+                "impl VariableSizeAttribute for {} {{ type SizeType = u8; }}",
+                Slots::of::<E>()
+            )];
+            lines.extend(extra.iter().cloned());
+            self.entity::<E>("Box<[u32]>", rows.len(), &lines);
+            let dir = self.stowage.path_from_ns(E::NS).join(E::NAME);
+            create_dir_all(&dir).unwrap();
+            let sizes: Vec<u8> = rows.iter().map(|r| r.len() as u8).collect();
+            let targets: Vec<u8> = rows
+                .iter()
+                .flat_map(|r| r.iter())
+                .flat_map(|e| e.to_be_bytes())
+                .collect();
+            write(dir.join("sizes"), sizes).unwrap();
+            write(dir.join("targets"), targets).unwrap();
+        }
+
+        fn write_code(self) -> Stowage {
+            for (ns, code) in self.code.iter() {
+                write(self.stowage.path_from_ns(ns).join(NS_CODE_FILE), code).unwrap();
+            }
+            self.stowage
+        }
+    }
+
+    fn link<E>(source: &str, target: &str) -> String {
+        format!(
+            "impl Link for {} {{ type Source = {A1}::{source}; type Target = {A1}::{target}; }}",
+            Slots::of::<E>()
+        )
+    }
+
+    fn mark<E, M>(main: &str) -> String {
+        format!(
+            "impl MarkedAttribute<crate::common::{}> for {A1}::{main} {{ type AttributeEntity = {}; }}",
+            Slots::of::<M>(),
+            Slots::of::<E>()
+        )
+    }
+
+    fn read_rows<E: NamespacedEntity>(stowage: &Stowage) -> Vec<Vec<usize>> {
+        let dir = stowage.path_from_ns(E::NS).join(E::NAME);
+        let sizes = read(dir.join("sizes")).unwrap();
+        let targets = read(dir.join("targets")).unwrap();
+        let mut elems = targets.chunks(4).map(read_uint);
+        sizes
+            .iter()
+            .map(|s| elems.by_ref().take(*s as usize).collect())
+            .collect()
+    }
+
+    fn read_fixed<E: NamespacedEntity>(stowage: &Stowage, width: usize) -> Vec<usize> {
+        let bytes = read(stowage.path_from_ns(E::NS).join(E::NAME)).unwrap();
+        bytes.chunks(width).map(read_uint).collect()
+    }
+
+    #[test]
+    fn renumbered_closed_subset() {
+        let tmp = std::env::temp_dir().join(format!("rankless-subsample-{}", std::process::id()));
+        let mut fb = FakeBuild {
+            stowage: Stowage::new(tmp.join("full").to_str().unwrap()),
+            code: HashMap::new(),
+        };
+        fb.id_map::<Works>("u32", &[101, 102, 103, 104, 105, 106]);
+        fb.id_map::<Institutions>("u16", &[201, 202, 203]);
+        fb.id_map::<Authors>("u32", &[301, 302, 303]);
+        fb.id_map::<Countries>("u8", &[401, 402]);
+
+        let wc_mark = mark::<InstitutionsWorkCount, WorkCountMarker>("Institutions");
+        fb.fixed::<InstitutionsWorkCount>("u32", 4, &[0, 2, 1, 3, 0], &[wc_mark]);
+        let iw_link = link::<InstitutionWorks>("Institutions", "Works");
+        fb.rows::<InstitutionWorks>(&[&[], &[1, 2], &[3], &[4, 5, 6], &[]], &[iw_link]);
+        let ref_link = link::<WorkReferences>("Works", "Works");
+        let refs: [&[u32]; 8] = [&[], &[3], &[], &[], &[], &[2], &[], &[]];
+        fb.rows::<WorkReferences>(&refs, &[ref_link]);
+        let wa_link = link::<WorkAuthors>("Works", "Authors");
+        let work_authors: [&[u32]; 8] = [&[], &[2], &[1], &[], &[3], &[], &[], &[]];
+        fb.rows::<WorkAuthors>(&work_authors, &[wa_link]);
+        let ic_link = link::<InstCountries>("Institutions", "Countries");
+        fb.fixed::<InstCountries>("u8", 1, &[0, 1, 1, 2, 0], &[ic_link]);
+        let collab_atts = [
+            link::<AuthorsCollaborators>("Authors", "Authors"),
+            mark::<AuthorsCollaborators, CollaboratorsMarker>("Authors"),
+        ];
+        fb.rows::<AuthorsCollaborators>(&[&[], &[2, 3], &[1], &[1], &[]], &collab_atts);
+        let weights_mark = mark::<AuthorsCollabWeights, CollabWeightsMarker>("Authors");
+        fb.rows::<AuthorsCollabWeights>(&[&[], &[5, 7], &[5], &[7], &[]], &[weights_mark]);
+        let full = fb.write_code();
+
+        let out_root = tmp.join("out");
+        main(full, Stowage::read_only(out_root.to_str().unwrap()), 1).unwrap();
+        let out = Stowage::read_only(out_root.to_str().unwrap());
+
+        //institution 3 has most works, work 5 of it references work 2, which brings author 1
+        let id_map = read(out.path_from_ns(Works::NS).join(Works::NAME)).unwrap();
+        let works: Vec<usize> = id_map.chunks(8).map(read_uint).collect();
+        assert_eq!(works, vec![102, 1, 104, 2, 105, 3, 106, 4]);
+        let insts = read(out.path_from_ns(Works::NS).join(Institutions::NAME)).unwrap();
+        assert_eq!(
+            insts.chunks(8).map(read_uint).collect::<Vec<_>>(),
+            vec![203, 1]
+        );
+        let authors = read(out.path_from_ns(Works::NS).join(Authors::NAME)).unwrap();
+        let authors: Vec<usize> = authors.chunks(8).map(read_uint).collect();
+        assert_eq!(authors, vec![301, 1, 303, 2]);
+        let countries = read(out.path_from_ns(Works::NS).join(Countries::NAME)).unwrap();
+        assert_eq!(countries.len(), 2 * ID_MAP_RECORD);
+
+        assert_eq!(
+            read_rows::<InstitutionWorks>(&out),
+            vec![vec![], vec![2, 3, 4], vec![]]
+        );
+        let refs = read_rows::<WorkReferences>(&out);
+        assert_eq!(refs, vec![vec![], vec![], vec![], vec![1], vec![], vec![]]);
+        let work_authors = read_rows::<WorkAuthors>(&out);
+        assert_eq!(work_authors[1..3], [vec![1], vec![2]]);
+        assert_eq!(read_fixed::<InstCountries>(&out, 1), vec![0, 2, 0]);
+        assert_eq!(read_fixed::<InstitutionsWorkCount>(&out, 4), vec![0, 3, 0]);
+        //weights follow the collaborators left in the subset
+        let collabs = read_rows::<AuthorsCollaborators>(&out);
+        assert_eq!(collabs, vec![vec![], vec![2], vec![1], vec![]]);
+        let weights = read_rows::<AuthorsCollabWeights>(&out);
+        assert_eq!(weights, vec![vec![], vec![7], vec![7], vec![]]);
+
+        let built = out.generated_entities();
+        let n_of = |name: &str| built.iter().find(|(_, ge)| ge.name == name).unwrap().1.n;
+        assert_eq!(n_of(Works::NAME), 5);
+        assert_eq!(n_of(Institutions::NAME), 2);
+        assert_eq!(n_of(Countries::NAME), 3);
+        assert_eq!(n_of(WorkReferences::NAME), 6);
+        assert_eq!(n_of(AuthorsCollabWeights::NAME), 4);
+        remove_dir_all(&tmp).unwrap();
+    }
+}